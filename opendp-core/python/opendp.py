@@ -2,6 +2,55 @@ import ctypes
 import json
 
 
+class OpenDPException(Exception):
+    """An error returned by the OpenDP library. `variant` is the kind of error, like "TypeParse" or "FailedMembership"."""
+
+    def __init__(self, variant, message):
+        super().__init__(f"{variant}: {message}")
+        self.variant = variant
+        self.message = message
+
+
+class FfiError(ctypes.Structure):
+    _fields_ = [
+        ("variant", ctypes.c_char_p),
+        ("message", ctypes.c_char_p),
+    ]
+
+
+def ffi_result_type(ok_type):
+    """The ctypes structure of an FfiResult<T>, a tagged union of `ok_type` and an error."""
+    class FfiResultPayload(ctypes.Union):
+        _fields_ = [
+            ("Ok", ok_type),
+            ("Err", ctypes.POINTER(FfiError)),
+        ]
+
+    class FfiResult(ctypes.Structure):
+        _fields_ = [
+            ("tag", ctypes.c_uint32),
+            ("payload", FfiResultPayload),
+        ]
+    return FfiResult
+
+
+class FfiSlice(ctypes.Structure):
+    _fields_ = [
+        ("ptr", ctypes.c_void_p),
+        ("len", ctypes.c_size_t),
+    ]
+
+
+def take_str(lib, ptr):
+    """Copies out a string returned as `char *`, which is owned by the caller, and frees it."""
+    string = ctypes.cast(ptr, ctypes.c_char_p).value.decode("utf-8")
+    str_free = lib["opendp_data__str_free"]
+    str_free.argtypes = [ctypes.c_void_p]
+    str_free.restype = ffi_result_type(ctypes.c_void_p)
+    str_free(ptr)
+    return string
+
+
 class Mod:
 
     name_to_type = {
@@ -20,9 +69,11 @@ class Mod:
         "uint64_t": ctypes.c_uint64,
         "float": ctypes.c_float,
         "double": ctypes.c_double,
-        "char *": ctypes.c_char_p,
+        # Returned strings are owned by the caller, so keep the pointer to free them with take_str().
+        "char *": ctypes.c_void_p,
         "const char *": ctypes.c_char_p,
         "bool": ctypes.c_bool,
+        "size_t": ctypes.c_size_t,
     }
 
     @classmethod
@@ -37,9 +88,9 @@ class Mod:
         self._bootstrap()
 
     def _bootstrap(self):
-        spec = { "name": "bootstrap", "args": [], "ret": "const char *" }
+        spec = { "name": "bootstrap", "args": [], "ret": "char *" }
         _name, bootstrap = self._get_function(spec)
-        spec_json = bootstrap().decode("ascii")
+        spec_json = take_str(self.lib, bootstrap())
         spec = json.loads(spec_json)
         self._load(spec)
 
@@ -53,9 +104,29 @@ class Mod:
         symbol = self.prefix + name
         function = self.lib[symbol]
         function.argtypes = [self.get_type(arg[0]) for arg in spec.get("args", [])]
-        function.restype = self.get_type(spec.get("ret", "void"))
+        ret = spec.get("ret", "void")
+        if ret.startswith("FfiResult<") and ret.endswith(">"):
+            ok_type = self.get_type(ret[len("FfiResult<"):-1])
+            function.restype = ffi_result_type(ok_type)
+            return name, self._unwrap(function)
+        function.restype = self.get_type(ret)
         return name, function
 
+    def _unwrap(self, function):
+        error_free = self.lib["opendp_core__error_free"]
+        error_free.argtypes = [ctypes.POINTER(FfiError)]
+        error_free.restype = None
+
+        def wrapper(*args):
+            result = function(*args)
+            if result.tag == 0:
+                return result.payload.Ok
+            error = result.payload.Err.contents
+            exception = OpenDPException(error.variant.decode("utf-8"), error.message.decode("utf-8"))
+            error_free(result.payload.Err)
+            raise exception
+        return wrapper
+
 class OpenDP:
 
     def __init__(self, lib_path):
@@ -85,6 +156,92 @@ class OpenDP:
     def f64_p(self, f):
         return ctypes.byref(ctypes.c_double(f))
 
+    rust_to_c_type = {
+        "u8": ctypes.c_uint8,
+        "u32": ctypes.c_uint32,
+        "u64": ctypes.c_uint64,
+        "i32": ctypes.c_int32,
+        "i64": ctypes.c_int64,
+        "f32": ctypes.c_float,
+        "f64": ctypes.c_double,
+        "bool": ctypes.c_bool,
+    }
+
+    def py_to_object(self, value, type_name):
+        """Converts a number, str, pair of numbers, or list to an OpenDP object of type `type_name`, like "f64", "(f64, f64)" or "Vec<String>"."""
+        if type_name == "String":
+            buffer = value.encode("utf-8")
+            array, length = ctypes.create_string_buffer(buffer, len(buffer)), len(buffer)
+        elif type_name == "Vec<String>":
+            array, length = (ctypes.c_char_p * len(value))(*(e.encode("utf-8") for e in value)), len(value)
+        elif type_name.startswith("Vec<"):
+            element_type = self.rust_to_c_type[type_name[len("Vec<"):-1]]
+            array, length = (element_type * len(value))(*value), len(value)
+        elif type_name.startswith("("):
+            element_type = self.rust_to_c_type[type_name[1:-1].split(",")[0].strip()]
+            array, length = (element_type * 2)(*value), 2
+        else:
+            array, length = self.rust_to_c_type[type_name](value), 1
+        return self.data.slice_as_object(self.str_to_c_char_p(f"<{type_name}>"), ctypes.byref(array), length)
+
+    def object_to_py(self, obj, type_name):
+        """Converts an OpenDP object of type `type_name` back to a number, str, pair of numbers, or list."""
+        slice_p = self.data.object_as_slice(obj)
+        try:
+            slice = ctypes.cast(slice_p, ctypes.POINTER(FfiSlice)).contents
+            if type_name == "String":
+                return ctypes.string_at(slice.ptr, slice.len).decode("utf-8")
+            elif type_name == "Vec<String>":
+                array = ctypes.cast(slice.ptr, ctypes.POINTER(ctypes.c_char_p))
+                return [array[i].decode("utf-8") for i in range(slice.len)]
+            elif type_name.startswith("Vec<"):
+                element_type = self.rust_to_c_type[type_name[len("Vec<"):-1]]
+                return ctypes.cast(slice.ptr, ctypes.POINTER(element_type))[:slice.len]
+            elif type_name.startswith("("):
+                element_type = self.rust_to_c_type[type_name[1:-1].split(",")[0].strip()]
+                return tuple(ctypes.cast(slice.ptr, ctypes.POINTER(element_type))[:2])
+            else:
+                return ctypes.cast(slice.ptr, ctypes.POINTER(self.rust_to_c_type[type_name])).contents.value
+        finally:
+            self.data.slice_free(slice_p)
+
+    def measurement_check(self, measurement, d_in, d_out, distance_types):
+        """Checks whether `measurement` is `d_out`-private for inputs `d_in`-close. `distance_types` are the types of the distances, like ("f64", "f64")."""
+        return self._check(self.core.measurement_check, measurement, d_in, d_out, distance_types)
+
+    def transformation_check(self, transformation, d_in, d_out, distance_types):
+        """Checks whether `transformation` is `d_out`-stable for inputs `d_in`-close. `distance_types` are the types of the distances, like ("i32", "i32")."""
+        return self._check(self.core.transformation_check, transformation, d_in, d_out, distance_types)
+
+    def _check(self, check, this, d_in, d_out, distance_types):
+        d_in = self.py_to_object(d_in, distance_types[0])
+        try:
+            d_out = self.py_to_object(d_out, distance_types[1])
+            try:
+                return check(this, d_in, d_out)
+            finally:
+                self.data.data_free(d_out)
+        finally:
+            self.data.data_free(d_in)
+
+    def measurement_describe(self, measurement):
+        return json.loads(take_str(self.lib, self.core.measurement_describe(measurement)))
+
+    def transformation_describe(self, transformation):
+        return json.loads(take_str(self.lib, self.core.transformation_describe(transformation)))
+
+    def measurement_to_ast(self, measurement):
+        return json.loads(take_str(self.lib, self.core.measurement_to_ast(measurement)))
+
+    def measurement_from_ast(self, ast):
+        return self.core.measurement_from_ast(self.str_to_c_char_p(json.dumps(ast)))
+
+    def transformation_to_ast(self, transformation):
+        return json.loads(take_str(self.lib, self.core.transformation_to_ast(transformation)))
+
+    def transformation_from_ast(self, ast):
+        return self.core.transformation_from_ast(self.str_to_c_char_p(json.dumps(ast)))
+
     def make_chain_tt_multi(self, *transformations):
         if not transformations:
             raise Exception
@@ -94,5 +251,4 @@ class OpenDP:
             return self.make_chain_tt_multi(*transformations[:-2], self.core.make_chain_tt(transformations[-2], transformations[-1]))
 
     def to_str(self, data):
-        string = self.data.to_string(data)
-        return self.c_char_p_to_str(string)
+        return take_str(self.lib, self.data.to_string(data))
@@ -0,0 +1,336 @@
+//! Generates the bootstrap metadata and C header for the FFI entry points.
+//!
+//! Every `#[no_mangle] pub extern "C" fn` in a module listed in `MODULES` is read from its source,
+//! and described from its signature and doc comment. Arguments are documented by an `# Arguments`
+//! section in the doc comment, with one line per argument of the form ``* `name` - Description.``
+//!
+//! Ownership of a returned pointer follows from its mutability: a `*mut` result belongs to the caller, who must pass it
+//! to the matching free function (listed in `FREE_FUNCTIONS`), and a `*const` result is borrowed from an argument.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+use syn::visit::Visit;
+use syn::{Attribute, Expr, ExprCall, FnArg, GenericArgument, ItemFn, Lit, Meta, Pat, PathArguments, ReturnType, Type};
+
+const MODULES: &[&str] = &["core", "data", "meas", "trans"];
+
+/// The function that frees an owned result, by the type it points to.
+const FREE_FUNCTIONS: &[(&str, &str)] = &[
+    ("c_char", "opendp_data__str_free"),
+    ("FfiObject", "opendp_data__data_free"),
+    ("FfiSlice", "opendp_data__slice_free"),
+    ("FfiMeasurement", "opendp_core__measurement_free"),
+    ("FfiTransformation", "opendp_core__transformation_free"),
+];
+
+struct Arg {
+    c_type: String,
+    name: String,
+    doc: Option<String>,
+}
+
+struct Function {
+    symbol: String,
+    name: String,
+    doc: String,
+    type_args: Option<Arity>,
+    args: Vec<Arg>,
+    ret: String,
+    free: Option<&'static str>,
+}
+
+fn path_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => Some("()".to_owned()),
+        _ => None,
+    }
+}
+
+fn to_c_type(ty: &Type, symbol: &str) -> String {
+    if let Type::Ptr(ptr) = ty {
+        let qualifier = if ptr.mutability.is_some() { "" } else { "const " };
+        let pointee = if path_name(&ptr.elem).as_deref() == Some("c_char") { "char" } else { "void" };
+        return format!("{}{} *", qualifier, pointee)
+    }
+    let c_type = match path_name(ty).as_deref() {
+        Some("bool") | Some("c_bool") => "bool",
+        Some("c_uint") => "unsigned int",
+        Some("c_int") => "int",
+        Some("usize") => "size_t",
+        Some("u8") => "uint8_t",
+        Some("u32") => "uint32_t",
+        Some("u64") => "uint64_t",
+        Some("i32") => "int32_t",
+        Some("i64") => "int64_t",
+        Some("f32") => "float",
+        Some("f64") => "double",
+        _ => panic!("Unsupported FFI type in {}: {}", symbol, quote::ToTokens::to_token_stream(ty)),
+    };
+    c_type.to_owned()
+}
+
+/// The `T` of an `FfiResult<T>`.
+fn ffi_result_ok(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if segment.ident == "FfiResult" => match args.args.first()? {
+            GenericArgument::Type(ok) => Some(ok),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn to_c_return_type(ret: &ReturnType, symbol: &str) -> String {
+    let ty = match ret {
+        ReturnType::Default => return "void".to_owned(),
+        ReturnType::Type(_, ty) => ty,
+    };
+    match ffi_result_ok(ty) {
+        Some(ok) => format!("FfiResult<{}>", to_c_type(ok, symbol)),
+        None => to_c_type(ty, symbol),
+    }
+}
+
+/// The function that frees the result, if it's owned by the caller.
+fn to_free_function(ret: &ReturnType, symbol: &str) -> Option<&'static str> {
+    let ty = match ret {
+        ReturnType::Default => return None,
+        ReturnType::Type(_, ty) => ty.as_ref(),
+    };
+    let ptr = match ffi_result_ok(ty).unwrap_or(ty) {
+        Type::Ptr(ptr) if ptr.mutability.is_some() => ptr,
+        _ => return None,
+    };
+    let pointee = path_name(&ptr.elem);
+    // The null `*mut ()` of functions without a result doesn't need freeing.
+    if pointee.as_deref() == Some("()") {
+        return None
+    }
+    let free = FREE_FUNCTIONS.iter().find(|(name, _)| Some(*name) == pointee.as_deref()).map(|(_, free)| *free);
+    Some(free.unwrap_or_else(|| panic!("No free function for the result of {}: {}", symbol, quote::ToTokens::to_token_stream(&ptr.elem))))
+}
+
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs.iter().filter_map(|attr| match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Str(s) => Some(s.value().strip_prefix(' ').map(str::to_owned).unwrap_or_else(|| s.value())),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }).collect()
+}
+
+/// Splits a doc comment into its description, and the docs of each argument.
+fn parse_doc(attrs: &[Attribute]) -> (String, Vec<(String, String)>) {
+    let lines = doc_lines(attrs);
+    let split = lines.iter().position(|line| line.trim() == "# Arguments").unwrap_or(lines.len());
+    let doc = lines[..split].join("\n").trim().to_owned();
+    let arg_docs = lines[split..].iter().filter_map(|line| {
+        let line = line.trim().strip_prefix("* `")?;
+        let (name, doc) = line.split_at(line.find('`')?);
+        let doc = doc[1..].trim_start().strip_prefix('-').unwrap_or(&doc[1..]).trim();
+        Some((name.to_owned(), doc.to_owned()))
+    }).collect();
+    (doc, arg_docs)
+}
+
+/// The number of type arguments a function takes, which is variable when it depends on the other arguments.
+#[derive(Clone, Copy)]
+enum Arity {
+    Fixed(usize),
+    Variable,
+}
+
+/// Finds the arity passed to `TypeArgs::parse()` in the body of a function.
+#[derive(Default)]
+struct TypeArgsArity(Option<Arity>);
+
+impl<'ast> Visit<'ast> for TypeArgsArity {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(path) = call.func.as_ref() {
+            let segments: Vec<_> = path.path.segments.iter().map(|segment| segment.ident.to_string()).collect();
+            if segments.ends_with(&["TypeArgs".to_owned(), "parse".to_owned()]) {
+                self.0 = Some(match call.args.iter().nth(1) {
+                    Some(Expr::Lit(syn::ExprLit { lit: Lit::Int(count), .. })) => Arity::Fixed(count.base10_parse().unwrap()),
+                    _ => Arity::Variable,
+                });
+            }
+        }
+        syn::visit::visit_expr_call(self, call);
+    }
+}
+
+fn is_exported(item: &ItemFn) -> bool {
+    let no_mangle = item.attrs.iter().any(|attr| attr.path().is_ident("no_mangle"));
+    let extern_c = item.sig.abi.as_ref().and_then(|abi| abi.name.as_ref()).is_some_and(|name| name.value() == "C");
+    no_mangle && extern_c
+}
+
+fn parse_function(module: &str, item: &ItemFn) -> Function {
+    let symbol = item.sig.ident.to_string();
+    let prefix = format!("opendp_{}__", module);
+    let name = symbol.strip_prefix(&prefix)
+        .unwrap_or_else(|| panic!("FFI function {} must be prefixed with {}", symbol, prefix))
+        .to_owned();
+    let (doc, arg_docs) = parse_doc(&item.attrs);
+    let args: Vec<_> = item.sig.inputs.iter().map(|arg| match arg {
+        FnArg::Typed(arg) => {
+            let name = match arg.pat.as_ref() {
+                Pat::Ident(ident) => ident.ident.to_string(),
+                _ => panic!("Unsupported argument pattern in {}", symbol),
+            };
+            let doc = arg_docs.iter().find(|(arg_name, _)| *arg_name == name).map(|(_, doc)| doc.clone());
+            Arg { c_type: to_c_type(&arg.ty, &symbol), name, doc }
+        },
+        FnArg::Receiver(_) => panic!("Unsupported receiver in {}", symbol),
+    }).collect();
+    if let Some((arg_name, _)) = arg_docs.iter().find(|(arg_name, _)| args.iter().all(|arg| arg.name != *arg_name)) {
+        panic!("Documented argument {} is not an argument of {}", arg_name, symbol)
+    }
+    let mut arity = TypeArgsArity::default();
+    arity.visit_block(&item.block);
+    if args.iter().any(|arg| arg.name == "type_args") && arity.0.is_none() {
+        panic!("{} takes type_args, but never parses them with TypeArgs::parse()", symbol)
+    }
+    let ret = to_c_return_type(&item.sig.output, &symbol);
+    let free = to_free_function(&item.sig.output, &symbol);
+    Function { symbol, name, doc, type_args: arity.0, args, ret, free }
+}
+
+fn to_spec(functions: &[Function]) -> Value {
+    let functions: Vec<_> = functions.iter().map(|function| {
+        let mut spec = Map::new();
+        spec.insert("name".to_owned(), json!(function.name));
+        if !function.doc.is_empty() {
+            spec.insert("doc".to_owned(), json!(function.doc));
+        }
+        match function.type_args {
+            Some(Arity::Fixed(count)) => { spec.insert("type_args".to_owned(), json!(count)); },
+            Some(Arity::Variable) => { spec.insert("type_args".to_owned(), json!("variable")); },
+            None => (),
+        }
+        let args: Vec<_> = function.args.iter().map(|arg| match &arg.doc {
+            Some(doc) => json!([arg.c_type, arg.name, doc]),
+            None => json!([arg.c_type, arg.name]),
+        }).collect();
+        spec.insert("args".to_owned(), json!(args));
+        spec.insert("ret".to_owned(), json!(function.ret));
+        if let Some(free) = function.free {
+            spec.insert("free".to_owned(), json!(free));
+        }
+        Value::Object(spec)
+    }).collect();
+    json!({ "functions": functions })
+}
+
+fn to_c_declaration(function: &Function) -> String {
+    fn join(c_type: &str, name: &str) -> String {
+        if c_type.ends_with('*') { format!("{}{}", c_type, name) } else { format!("{} {}", c_type, name) }
+    }
+    let mut comment: Vec<String> = function.doc.lines().map(str::to_owned).collect();
+    let mut params: Vec<_> = function.args.iter().filter_map(|arg| arg.doc.as_ref().map(|doc| format!("@param {} {}", arg.name, doc))).collect();
+    if let Some(free) = function.free {
+        params.push(format!("@return Owned by the caller, who must free it with {}().", free));
+    }
+    if !comment.is_empty() && !params.is_empty() {
+        comment.push(String::new());
+    }
+    comment.extend(params);
+    let comment = if comment.is_empty() {
+        String::new()
+    } else {
+        let lines: Vec<_> = comment.iter().map(|line| format!(" *{}{}", if line.is_empty() { "" } else { " " }, line)).collect();
+        format!("/**\n{}\n */\n", lines.join("\n"))
+    };
+    let ret = c_result_name(&function.ret).unwrap_or_else(|| function.ret.clone());
+    let args: Vec<_> = function.args.iter().map(|arg| join(&arg.c_type, &arg.name)).collect();
+    let args = if args.is_empty() { "void".to_owned() } else { args.join(", ") };
+    format!("\n{}{};\n", comment, join(&ret, &format!("{}({})", function.symbol, args)))
+}
+
+/// The C name of an `FfiResult<T>` return type. Pointers share the generic `FfiResult`, and other types get their own struct.
+fn c_result_name(ret: &str) -> Option<String> {
+    let ok = ret.strip_prefix("FfiResult<")?.strip_suffix('>')?;
+    Some(if ok.ends_with('*') { "FfiResult".to_owned() } else { format!("FfiResult_{}", ok.replace(' ', "_")) })
+}
+
+fn to_c_result_struct(ret: &str) -> String {
+    let ok = &ret["FfiResult<".len()..ret.len() - 1];
+    let name = c_result_name(ret).unwrap();
+    format!("\ntypedef struct {name} {{\n    uint32_t tag;\n    union {{\n        {ok} ok;\n        FfiError *err;\n    }};\n}} {name};\n", name = name, ok = ok)
+}
+
+const HEADER_PRELUDE: &str = r#"/* Generated from the opendp-ffi sources. Do not edit. */
+
+#ifndef OPENDP_FFI_H
+#define OPENDP_FFI_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct FfiError {
+    char *variant;
+    char *message;
+} FfiError;
+
+/* The tag is 0 for Ok, and 1 for Err. Ok values are pointers, whose type is documented in the bootstrap metadata.
+ * Errors are owned by the caller, who must free them with opendp_core__error_free(). */
+typedef struct FfiResult {
+    uint32_t tag;
+    union {
+        void *ok;
+        FfiError *err;
+    };
+} FfiResult;
+
+typedef struct FfiSlice {
+    const void *ptr;
+    size_t len;
+} FfiSlice;
+"#;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut header = HEADER_PRELUDE.to_owned();
+    let mut declarations = String::new();
+    let mut result_structs = BTreeSet::new();
+    for module in MODULES {
+        let path = format!("src/{}.rs", module);
+        println!("cargo:rerun-if-changed={}", path);
+        let source = fs::read_to_string(&path).unwrap();
+        let file = syn::parse_file(&source).unwrap_or_else(|e| panic!("Failed to parse {}: {}", path, e));
+        let functions: Vec<_> = file.items.iter()
+            .filter_map(|item| match item { syn::Item::Fn(item) if is_exported(item) => Some(item), _ => None })
+            .map(|item| parse_function(module, item))
+            .collect();
+        let (bootstrap, functions): (Vec<_>, Vec<_>) = functions.into_iter().partition(|function| function.name == "bootstrap");
+        let spec = serde_json::to_string(&to_spec(&functions)).unwrap();
+        fs::write(Path::new(&out_dir).join(format!("{}.json", module)), spec).unwrap();
+        declarations.push_str(&format!("\n/* {} */\n", module));
+        for function in bootstrap.iter().chain(functions.iter()) {
+            declarations.push_str(&to_c_declaration(function));
+            if c_result_name(&function.ret).is_some_and(|name| name != "FfiResult") {
+                result_structs.insert(function.ret.clone());
+            }
+        }
+    }
+    for ret in result_structs {
+        header.push_str(&to_c_result_struct(&ret));
+    }
+    header.push_str(&declarations);
+    header.push_str("\n#endif /* OPENDP_FFI_H */\n");
+    fs::write(Path::new(&out_dir).join("opendp_ffi.h"), header).unwrap();
+}
@@ -0,0 +1,684 @@
+//! Serializable descriptions of how transformations and measurements were made, and a builder that remakes them.
+//!
+//! Every transformation and measurement made by an FFI constructor or combinator records its [`Ast`]: the name of the
+//! constructor, its type arguments, its parameters, and the ASTs of the pipelines it combines. For example, a noisy sum has the AST:
+//! ```json
+//! { "constructor": "make_chain_mt", "children": [
+//!     { "constructor": "make_base_laplace", "type_args": "<f64>", "params": { "sigma": 1.0 } },
+//!     { "constructor": "make_bounded_sum_l1", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 10.0 } }
+//! ] }
+//! ```
+//! Parameters are named after the arguments of the FFI constructor. Values passed by pointer are serialized as their type argument,
+//! lists as arrays, and dataframe domains as their description (see [`opendp::core::Domain::describe`]).
+//! Non-finite floats, which JSON numbers can't represent, are serialized as the strings `"NaN"`, `"inf"` and `"-inf"`.
+//! [`build`] remakes a pipeline by calling the same constructors, so the result has the same AST.
+
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::mem;
+use std::os::raw::{c_char, c_uint, c_void};
+use std::ptr;
+use std::str::FromStr;
+
+use opendp::data::Element;
+use opendp::dom::{AllDomain, DataFrameDomain, OptionNullDomain, VectorDomain};
+use serde::de::DeserializeOwned;
+use serde::ser::{self, Impossible};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{json, Map, Value};
+
+use crate::core::{self, FfiMeasurement, FfiObject, FfiTransformation};
+use crate::data;
+use crate::meas;
+use crate::trans;
+use crate::util;
+use crate::util::{c_bool, Error, ErrorKind, FfiResult, Type, TypeArgs, TypeDescriptor};
+
+/// A node of the AST of a transformation or measurement.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ast {
+    /// The name of the constructor or combinator, like `make_clamp`.
+    pub constructor: String,
+    /// The type arguments, like `<f64>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_args: Option<String>,
+    /// The parameters, keyed by the names of the arguments of the constructor.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub params: Map<String, Value>,
+    /// The ASTs of the pipelines combined by a combinator, in the order of its arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Ast>,
+}
+
+impl Ast {
+    pub fn new(constructor: &str) -> Self {
+        Ast { constructor: constructor.to_owned(), type_args: None, params: Map::new(), children: Vec::new() }
+    }
+
+    pub fn type_args(mut self, type_args: &TypeArgs) -> Self {
+        self.type_args = Some(type_args.descriptor());
+        self
+    }
+
+    pub fn param(mut self, name: &str, value: impl Serialize) -> Self {
+        let value = match value.serialize(FloatProbe) {
+            Ok(Some(float)) if !float.is_finite() => json!(float.to_string()),
+            _ => json!(value),
+        };
+        self.params.insert(name.to_owned(), value);
+        self
+    }
+
+    /// The AST of a combinator, or `None` if any of the pipelines it combines has no AST.
+    pub fn combine(constructor: &str, children: &[&Option<Ast>]) -> Option<Self> {
+        let children = children.iter().map(|e| e.as_ref().cloned()).collect::<Option<_>>()?;
+        Some(Ast { children, ..Ast::new(constructor) })
+    }
+
+    pub fn to_json(&self) -> String {
+        json!(self).to_string()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::new(ErrorKind::FailedParse, format!("Bogus AST: {}", e)))
+    }
+}
+
+/// A serializer that finds the value of a bare float, so that [`Ast::param`] can encode it if it's not finite.
+/// Params of other types are parsed from JSON by the constructors, so they can't hold non-finite floats.
+struct FloatProbe;
+
+macro_rules! not_a_float {
+    ($($method:ident($($arg:ty),*);)*) => {
+        $(fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> { Ok(None) })*
+    };
+}
+
+macro_rules! not_a_float_compound {
+    ($($method:ident($($arg:ty),*) -> $compound:ident;)*) => {
+        $(fn $method(self, $(_: $arg),*) -> Result<Self::$compound, Self::Error> { Err(ser::Error::custom("Not a float")) })*
+    };
+}
+
+impl Serializer for FloatProbe {
+    type Ok = Option<f64>;
+    type Error = serde_json::Error;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> { Ok(Some(v as f64)) }
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> { Ok(Some(v)) }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> { value.serialize(self) }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error> { value.serialize(self) }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _: &'static str, _: u32, _: &'static str, _: &T) -> Result<Self::Ok, Self::Error> { Ok(None) }
+    not_a_float! {
+        serialize_bool(bool); serialize_char(char); serialize_str(&str); serialize_bytes(&[u8]);
+        serialize_i8(i8); serialize_i16(i16); serialize_i32(i32); serialize_i64(i64);
+        serialize_u8(u8); serialize_u16(u16); serialize_u32(u32); serialize_u64(u64);
+        serialize_none(); serialize_unit(); serialize_unit_struct(&'static str); serialize_unit_variant(&'static str, u32, &'static str);
+    }
+    not_a_float_compound! {
+        serialize_seq(Option<usize>) -> SerializeSeq;
+        serialize_tuple(usize) -> SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> SerializeTupleVariant;
+        serialize_map(Option<usize>) -> SerializeMap;
+        serialize_struct(&'static str, usize) -> SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> SerializeStructVariant;
+    }
+}
+
+/// A transformation or measurement made by [`build`]. It's freed when dropped, unless taken with `into_transformation()`
+/// or `into_measurement()`.
+pub enum Built {
+    Transformation(*mut FfiTransformation),
+    Measurement(*mut FfiMeasurement),
+}
+
+impl Built {
+    fn as_transformation(&self) -> Result<*mut FfiTransformation, Error> {
+        match self {
+            Built::Transformation(this) => Ok(*this),
+            Built::Measurement(_) => Err(Error::new(ErrorKind::TypeMismatch, "Expected a transformation, found a measurement")),
+        }
+    }
+
+    fn as_measurement(&self) -> Result<*mut FfiMeasurement, Error> {
+        match self {
+            Built::Measurement(this) => Ok(*this),
+            Built::Transformation(_) => Err(Error::new(ErrorKind::TypeMismatch, "Expected a measurement, found a transformation")),
+        }
+    }
+
+    pub fn into_transformation(mut self) -> Result<*mut FfiTransformation, Error> {
+        self.as_transformation()?;
+        match &mut self {
+            Built::Transformation(this) => Ok(mem::replace(this, ptr::null_mut())),
+            Built::Measurement(_) => unreachable!(),
+        }
+    }
+
+    pub fn into_measurement(mut self) -> Result<*mut FfiMeasurement, Error> {
+        self.as_measurement()?;
+        match &mut self {
+            Built::Measurement(this) => Ok(mem::replace(this, ptr::null_mut())),
+            Built::Transformation(_) => unreachable!(),
+        }
+    }
+}
+
+impl Drop for Built {
+    fn drop(&mut self) {
+        match *self {
+            Built::Transformation(this) if !this.is_null() => { core::opendp_core__transformation_free(this); }
+            Built::Measurement(this) if !this.is_null() => { core::opendp_core__measurement_free(this); }
+            _ => (),
+        }
+    }
+}
+
+/// Rebuilds a dataframe domain from its description. The dataframe constructors only make columns of `VectorDomain<AllDomain<T>>`
+/// and `VectorDomain<OptionNullDomain<AllDomain<T>>>`, so each column is rebuilt from the carrier of its non-null elements.
+fn dataframe_domain(description: &Value) -> Result<DataFrameDomain, Error> {
+    fn insert_column<T: 'static + Element + Clone + PartialEq>(domain: &mut DataFrameDomain, key: &str, nullable: bool) -> Result<(), Error> {
+        if nullable {
+            domain.insert_column(key, VectorDomain::new(OptionNullDomain::new(AllDomain::<T>::new())));
+        } else {
+            domain.insert_column(key, VectorDomain::<AllDomain<T>>::new_all());
+        }
+        Ok(())
+    }
+    let unsupported = || Error::new(ErrorKind::InvalidDomain, format!("Unsupported dataframe domain {}", description));
+    if description["type"] != "DataFrameDomain" {
+        return Err(unsupported())
+    }
+    let columns = description["columns"].as_object().ok_or_else(unsupported)?;
+    let mut domain = DataFrameDomain::new();
+    for (key, column) in columns {
+        if column["type"] != "VectorDomain" {
+            return Err(unsupported())
+        }
+        let mut element_domain = &column["element_domain"];
+        let nullable = element_domain["type"] == "OptionNullDomain";
+        if nullable {
+            element_domain = &element_domain["element_domain"];
+        }
+        if element_domain["type"] != "AllDomain" {
+            return Err(unsupported())
+        }
+        let carrier = element_domain["carrier"].as_str().and_then(|e| Type::try_from(e).ok()).ok_or_else(unsupported)?;
+        dispatch!(insert_column, [(carrier, @primitives)], (&mut domain, key, nullable))?;
+    }
+    Ok(domain)
+}
+
+/// The arguments to a constructor, converted from the parameters of its AST.
+/// The converted values are owned here, so the pointers stay valid until the constructor has been called.
+struct Args<'a> {
+    ast: &'a Ast,
+    type_args: Option<(CString, TypeArgs)>,
+    strings: Vec<CString>,
+    objects: Vec<*mut FfiObject>,
+}
+
+impl<'a> Args<'a> {
+    fn new(ast: &'a Ast) -> Result<Self, Error> {
+        let type_args = ast.type_args.as_deref().map(|type_args| {
+            let parsed = TypeArgs::try_from(type_args).map_err(|_| Error::new(ErrorKind::TypeParse, format!("Bogus type args {}", type_args)))?;
+            Ok::<_, Error>((to_c_string(type_args)?, parsed))
+        }).transpose()?;
+        Ok(Args { ast, type_args, strings: Vec::new(), objects: Vec::new() })
+    }
+
+    fn type_args(&self) -> Result<*const c_char, Error> {
+        self.type_args.as_ref().map(|(type_args, _)| type_args.as_ptr())
+            .ok_or_else(|| Error::new(ErrorKind::FailedParse, format!("Missing type args of {}", self.ast.constructor)))
+    }
+
+    fn param(&self, name: &str) -> Result<&'a Value, Error> {
+        self.ast.params.get(name).ok_or_else(|| Error::new(ErrorKind::FailedParse, format!("Missing parameter {} of {}", name, self.ast.constructor)))
+    }
+
+    fn get<T: DeserializeOwned>(&self, name: &str) -> Result<T, Error> {
+        serde_json::from_value(self.param(name)?.clone()).map_err(|e| Error::new(ErrorKind::FailedParse, format!("Bogus parameter {}: {}", name, e)))
+    }
+
+    fn bool(&self, name: &str) -> Result<c_bool, Error> {
+        self.get(name).map(util::from_bool)
+    }
+
+    fn uint(&self, name: &str) -> Result<c_uint, Error> {
+        self.get(name)
+    }
+
+    fn f64(&self, name: &str) -> Result<f64, Error> {
+        from_param(self.param(name)?, name)
+    }
+
+    fn add_string(&mut self, s: &str) -> Result<*const c_char, Error> {
+        self.strings.push(to_c_string(s)?);
+        Ok(self.strings.last().unwrap().as_ptr())
+    }
+
+    fn string(&mut self, name: &str) -> Result<*const c_char, Error> {
+        let s: String = self.get(name)?;
+        self.add_string(&s)
+    }
+
+    /// A string that may be null, which is also the default when the parameter is missing.
+    fn option_string(&mut self, name: &str) -> Result<*const c_char, Error> {
+        match self.ast.params.get(name) {
+            Some(Value::String(s)) => self.add_string(s),
+            Some(Value::Null) | None => Ok(ptr::null()),
+            Some(_) => Err(Error::new(ErrorKind::FailedParse, format!("Bogus parameter {}: expected a string", name))),
+        }
+    }
+
+    /// A value serialized as JSON, like a predicate or a list.
+    fn json(&mut self, name: &str) -> Result<*const c_char, Error> {
+        let json = self.param(name)?.to_string();
+        self.add_string(&json)
+    }
+
+    /// A value of the first type argument, passed by pointer.
+    fn value(&mut self, name: &str) -> Result<*const c_void, Error> {
+        self.value_at(name, 0)
+    }
+
+    /// A value of the type argument at `index`, passed by pointer.
+    fn value_at(&mut self, name: &str, index: usize) -> Result<*const c_void, Error> {
+        let type_ = self.type_arg(index)?;
+        self.value_of(name, &type_)
+    }
+
+    /// A value of the first type argument with any `Option` stripped, like an `i32` for `<Option<i32>>`, passed by pointer.
+    fn non_null_value(&mut self, name: &str) -> Result<*const c_void, Error> {
+        let type_ = self.type_arg(0)?;
+        let type_ = match TypeDescriptor::parse(type_.descriptor) {
+            Ok(TypeDescriptor::Named(name, args)) if name == "Option" && args.len() == 1 => Type::try_from(args[0].to_string().as_str())
+                .map_err(|_| Error::new(ErrorKind::TypeParse, format!("Bogus type args {}", type_.descriptor)))?,
+            _ => type_,
+        };
+        self.value_of(name, &type_)
+    }
+
+    fn type_arg(&self, index: usize) -> Result<Type, Error> {
+        self.type_args.as_ref().and_then(|(_, type_args)| type_args.0.get(index)).cloned()
+            .ok_or_else(|| Error::new(ErrorKind::FailedParse, format!("Missing type args of {}", self.ast.constructor)))
+    }
+
+    fn value_of(&mut self, name: &str, type_: &Type) -> Result<*const c_void, Error> {
+        fn monomorphize<T: 'static + DeserializeOwned + FromStr>(value: &Value, name: &str) -> Result<*mut FfiObject, Error> {
+            Ok(FfiObject::new(from_param::<T>(value, name)?))
+        }
+        let value = self.param(name)?;
+        let object = dispatch!(monomorphize, [(type_, @primitives)], (value, name))?;
+        self.objects.push(object);
+        Ok(util::as_ref(object)?.as_erased() as *const () as *const c_void)
+    }
+
+    fn domain(&mut self, name: &str) -> Result<*const FfiObject, Error> {
+        let object = FfiObject::new(dataframe_domain(self.param(name)?)?);
+        self.objects.push(object);
+        Ok(object)
+    }
+}
+
+impl Drop for Args<'_> {
+    fn drop(&mut self) {
+        for object in self.objects.drain(..) {
+            data::opendp_data__data_free(object);
+        }
+    }
+}
+
+/// Parses a parameter, including a non-finite float encoded by [`Ast::param`] as a string.
+fn from_param<T: DeserializeOwned + FromStr>(value: &Value, name: &str) -> Result<T, Error> {
+    serde_json::from_value(value.clone()).or_else(|e| match value {
+        Value::String(s) if s.parse::<f64>().is_ok_and(|e| !e.is_finite()) => s.parse().map_err(|_| e),
+        _ => Err(e),
+    }).map_err(|e| Error::new(ErrorKind::FailedParse, format!("Bogus parameter {}: {}", name, e)))
+}
+
+fn to_c_string(s: &str) -> Result<CString, Error> {
+    CString::new(s).map_err(|_| Error::new(ErrorKind::FailedParse, "String contains a null byte"))
+}
+
+fn transformation(result: FfiResult<*mut FfiTransformation>) -> Result<Built, Error> {
+    result.into_result().map(Built::Transformation)
+}
+
+fn measurement(result: FfiResult<*mut FfiMeasurement>) -> Result<Built, Error> {
+    result.into_result().map(Built::Measurement)
+}
+
+/// Builds the child of a combinator of one pipeline.
+fn child(ast: &Ast) -> Result<Built, Error> {
+    match ast.children.as_slice() {
+        [child] => build(child),
+        _ => Err(Error::new(ErrorKind::FailedParse, format!("Expected 1 child of {}, got {}", ast.constructor, ast.children.len()))),
+    }
+}
+
+/// Builds the two children of a combinator.
+fn children(ast: &Ast) -> Result<(Built, Built), Error> {
+    match ast.children.as_slice() {
+        [child0, child1] => Ok((build(child0)?, build(child1)?)),
+        _ => Err(Error::new(ErrorKind::FailedParse, format!("Expected 2 children of {}, got {}", ast.constructor, ast.children.len()))),
+    }
+}
+
+/// Remakes the transformation or measurement described by an AST, by calling the constructors and combinators it names.
+pub fn build(ast: &Ast) -> Result<Built, Error> {
+    let mut args = Args::new(ast)?;
+    match ast.constructor.as_str() {
+        // Combinators
+        "make_chain_mt" => {
+            let (measurement1, transformation0) = children(ast)?;
+            measurement(core::opendp_core__make_chain_mt(measurement1.as_measurement()?, transformation0.as_transformation()?))
+        }
+        "make_chain_tt" => {
+            let (transformation1, transformation0) = children(ast)?;
+            transformation(core::opendp_core__make_chain_tt(transformation1.as_transformation()?, transformation0.as_transformation()?))
+        }
+        "make_composition" => {
+            let (measurement0, measurement1) = children(ast)?;
+            measurement(core::opendp_core__make_composition(measurement0.as_measurement()?, measurement1.as_measurement()?))
+        }
+        "make_parallel_composition" => measurement(core::opendp_core__make_parallel_composition(child(ast)?.as_measurement()?)),
+        "make_subsample" => measurement(core::opendp_core__make_subsample(child(ast)?.as_measurement()?, args.json("sampler")?)),
+        "make_sample_and_aggregate" => transformation(trans::opendp_trans__make_sample_and_aggregate(args.type_args()?, child(ast)?.as_transformation()?, args.uint("k")?, args.value_at("lower", 1)?, args.value_at("upper", 1)?)),
+        "make_sample_and_aggregate_median" => measurement(meas::opendp_meas__make_sample_and_aggregate_median(args.type_args()?, child(ast)?.as_transformation()?, args.uint("k")?, args.value_at("lower", 1)?, args.value_at("upper", 1)?, args.json("candidates")?, args.f64("scale")?)),
+        "make_sample_and_aggregate_mean" => measurement(meas::opendp_meas__make_sample_and_aggregate_mean(args.type_args()?, child(ast)?.as_transformation()?, args.uint("k")?, args.value_at("lower", 1)?, args.value_at("upper", 1)?, args.f64("sigma")?)),
+        // Transformations
+        "make_identity" => transformation(trans::opendp_trans__make_identity(args.type_args()?)),
+        "make_split_lines" => transformation(trans::opendp_trans__make_split_lines()),
+        "make_parse_series" => transformation(trans::opendp_trans__make_parse_series(args.type_args()?, args.bool("impute")?)),
+        "make_parse_series_option" => transformation(trans::opendp_trans__make_parse_series_option(args.type_args()?)),
+        "make_parse_series_inherent" => transformation(trans::opendp_trans__make_parse_series_inherent(args.type_args()?)),
+        "make_impute_constant" => transformation(trans::opendp_trans__make_impute_constant(args.type_args()?, args.non_null_value("constant")?)),
+        "make_impute_uniform_float" => transformation(trans::opendp_trans__make_impute_uniform_float(args.type_args()?, args.non_null_value("lower")?, args.non_null_value("upper")?)),
+        "make_drop_null" => transformation(trans::opendp_trans__make_drop_null(args.type_args()?)),
+        "make_is_null" => transformation(trans::opendp_trans__make_is_null(args.type_args()?)),
+        "make_cast" => transformation(trans::opendp_trans__make_cast(args.type_args()?)),
+        "make_cast_default" => transformation(trans::opendp_trans__make_cast_default(args.type_args()?, args.value_at("default_value", 1)?)),
+        "make_cast_inherent" => transformation(trans::opendp_trans__make_cast_inherent(args.type_args()?)),
+        "make_is_equal" => transformation(trans::opendp_trans__make_is_equal(args.type_args()?, args.value("value")?)),
+        "make_split_records" => transformation(trans::opendp_trans__make_split_records(args.option_string("separator")?)),
+        "make_create_dataframe" => transformation(trans::opendp_trans__make_create_dataframe(args.uint("col_count")?)),
+        "make_split_dataframe" => transformation(trans::opendp_trans__make_split_dataframe(args.option_string("separator")?, args.uint("col_count")?)),
+        "make_split_csv" => transformation(trans::opendp_trans__make_split_csv(args.json("keys")?, args.option_string("separator")?, args.option_string("quote")?, args.option_string("escape")?, args.bool("has_header")?)),
+        "make_parse_jsonl" => transformation(trans::opendp_trans__make_parse_jsonl(args.type_args()?, args.json("keys")?)),
+        "make_read_arrow_ipc" => transformation(trans::opendp_trans__make_read_arrow_ipc(args.type_args()?, args.json("keys")?)),
+        "make_read_parquet" => transformation(trans::opendp_trans__make_read_parquet(args.type_args()?, args.json("keys")?)),
+        "make_parse_column" => transformation(trans::opendp_trans__make_parse_column(args.type_args()?, args.domain("input_domain")?, args.string("key")?, args.bool("impute")?)),
+        "make_select_column" => transformation(trans::opendp_trans__make_select_column(args.type_args()?, args.domain("input_domain")?, args.string("key")?)),
+        "make_select_column_symmetric" => transformation(trans::opendp_trans__make_select_column_symmetric(args.type_args()?, args.domain("input_domain")?, args.string("key")?)),
+        "make_select_column_option" => transformation(trans::opendp_trans__make_select_column_option(args.type_args()?, args.domain("input_domain")?, args.string("key")?)),
+        "make_hamming_to_symmetric" => transformation(trans::opendp_trans__make_hamming_to_symmetric(args.type_args()?)),
+        "make_hamming_to_symmetric_nullable" => transformation(trans::opendp_trans__make_hamming_to_symmetric_nullable(args.type_args()?)),
+        "make_hamming_to_symmetric_dataframe" => transformation(trans::opendp_trans__make_hamming_to_symmetric_dataframe(args.domain("input_domain")?)),
+        "make_filter" => transformation(trans::opendp_trans__make_filter(args.type_args()?, args.json("predicate")?)),
+        "make_filter_dataframe" => transformation(trans::opendp_trans__make_filter_dataframe(args.type_args()?, args.domain("input_domain")?, args.string("key")?, args.json("predicate")?)),
+        "make_hamming_to_user" => transformation(trans::opendp_trans__make_hamming_to_user(args.domain("input_domain")?)),
+        "make_bound_user_contributions" => transformation(trans::opendp_trans__make_bound_user_contributions(args.type_args()?, args.domain("input_domain")?, args.string("id_key")?, args.uint("k")?)),
+        "make_partition_by" => transformation(trans::opendp_trans__make_partition_by(args.type_args()?, args.domain("input_domain")?, args.string("key")?, args.json("keys")?)),
+        "make_clamp" => transformation(trans::opendp_trans__make_clamp(args.type_args()?, args.value("lower")?, args.value("upper")?)),
+        "make_clamp_symmetric" => transformation(trans::opendp_trans__make_clamp_symmetric(args.type_args()?, args.value("lower")?, args.value("upper")?)),
+        "make_clamp_categorical" => transformation(trans::opendp_trans__make_clamp_categorical(args.type_args()?, args.json("categories")?, args.value("null")?)),
+        "make_find_bin" => transformation(trans::opendp_trans__make_find_bin(args.type_args()?, args.json("edges")?)),
+        "make_find" => transformation(trans::opendp_trans__make_find(args.type_args()?, args.json("categories")?)),
+        "make_bounded_sum_l1" => transformation(trans::opendp_trans__make_bounded_sum_l1(args.type_args()?, args.value("lower")?, args.value("upper")?)),
+        "make_bounded_sum_symmetric" => transformation(trans::opendp_trans__make_bounded_sum_symmetric(args.type_args()?, args.value("lower")?, args.value("upper")?)),
+        "make_bounded_sum_l2" => transformation(trans::opendp_trans__make_bounded_sum_l2(args.type_args()?, args.value("lower")?, args.value("upper")?)),
+        "make_count_l1" => transformation(trans::opendp_trans__make_count_l1(args.type_args()?)),
+        "make_count_l2" => transformation(trans::opendp_trans__make_count_l2(args.type_args()?)),
+        "make_lipschitz_mul" => transformation(trans::opendp_trans__make_lipschitz_mul(args.type_args()?, args.value("c")?)),
+        "make_shift" => transformation(trans::opendp_trans__make_shift(args.type_args()?, args.value("shift")?)),
+        "make_affine" => transformation(trans::opendp_trans__make_affine(args.type_args()?, args.value("a")?, args.value("b")?)),
+        "make_lipschitz_mul_vec" => transformation(trans::opendp_trans__make_lipschitz_mul_vec(args.type_args()?, args.value("lower")?, args.value("upper")?, args.value("c")?)),
+        "make_shift_vec" => transformation(trans::opendp_trans__make_shift_vec(args.type_args()?, args.value("lower")?, args.value("upper")?, args.value("shift")?)),
+        "make_affine_vec" => transformation(trans::opendp_trans__make_affine_vec(args.type_args()?, args.value("lower")?, args.value("upper")?, args.value("a")?, args.value("b")?)),
+        // Measurements
+        "make_base_laplace" => measurement(meas::opendp_meas__make_base_laplace(args.type_args()?, args.f64("sigma")?)),
+        "make_base_laplace_vec" => measurement(meas::opendp_meas__make_base_laplace_vec(args.type_args()?, args.f64("sigma")?)),
+        "make_base_gaussian" => measurement(meas::opendp_meas__make_base_gaussian(args.type_args()?, args.f64("sigma")?)),
+        "make_private_quantile" => measurement(meas::opendp_meas__make_private_quantile(args.type_args()?, args.value("lower")?, args.value("upper")?, args.json("candidates")?, args.f64("alpha")?, args.f64("scale")?)),
+        constructor => Err(Error::new(ErrorKind::FailedParse, format!("Unknown constructor {}", constructor))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use opendp::core::Domain;
+
+    use super::*;
+
+    fn ast(value: Value) -> Ast {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn to_ast(built: &Built) -> Ast {
+        let json = match *built {
+            Built::Transformation(this) => core::opendp_core__transformation_to_ast(this),
+            Built::Measurement(this) => core::opendp_core__measurement_to_ast(this),
+        };
+        Ast::from_json(&util::into_string(json.into_result().unwrap()).unwrap()).unwrap()
+    }
+
+    fn to_c_string(json: &Value) -> CString {
+        CString::new(json.to_string()).unwrap()
+    }
+
+    /// A dataframe domain with a column `a` of strings, a column `b` of floats and a column `c` of nullable bools.
+    fn dataframe() -> Value {
+        let mut domain = DataFrameDomain::new();
+        domain.insert_column("a", VectorDomain::<AllDomain<String>>::new_all());
+        domain.insert_column("b", VectorDomain::<AllDomain<f64>>::new_all());
+        domain.insert_column("c", VectorDomain::new(OptionNullDomain::new(AllDomain::<bool>::new())));
+        domain.describe()
+    }
+
+    fn constructor_asts() -> Vec<Value> {
+        let laplace = json!({ "constructor": "make_base_laplace", "type_args": "<f64>", "params": { "sigma": 1.0 } });
+        let clamp = json!({ "constructor": "make_clamp", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 10.0 } });
+        let bounded_sum = json!({ "constructor": "make_bounded_sum_l1", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 10.0 } });
+        let quantile = json!({ "constructor": "make_private_quantile", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 10.0, "candidates": [1.0, 5.0, 9.0], "alpha": 0.5, "scale": 1.0 } });
+        let count = json!({ "constructor": "make_count_l1", "type_args": "<f64>" });
+        // A noisy sum of the column `b` of a dataframe.
+        let column_sum = json!({ "constructor": "make_chain_mt", "children": [
+            laplace,
+            { "constructor": "make_chain_tt", "children": [
+                bounded_sum,
+                { "constructor": "make_chain_tt", "children": [
+                    clamp,
+                    { "constructor": "make_select_column", "type_args": "<f64>", "params": { "input_domain": dataframe(), "key": "b" } },
+                ] },
+            ] },
+        ] });
+        vec![
+            json!({ "constructor": "make_chain_mt", "children": [laplace, bounded_sum] }),
+            json!({ "constructor": "make_chain_tt", "children": [bounded_sum, clamp] }),
+            json!({ "constructor": "make_composition", "children": [laplace, laplace] }),
+            json!({ "constructor": "make_parallel_composition", "children": [column_sum] }),
+            json!({ "constructor": "make_subsample", "params": { "sampler": { "fixed_size": { "population_size": 100, "sample_size": 10 } } }, "children": [quantile] }),
+            json!({ "constructor": "make_identity", "type_args": "<String>" }),
+            json!({ "constructor": "make_split_lines" }),
+            json!({ "constructor": "make_parse_series", "type_args": "<f64>", "params": { "impute": true } }),
+            json!({ "constructor": "make_parse_series_option", "type_args": "<i32>" }),
+            json!({ "constructor": "make_parse_series_inherent", "type_args": "<f64>" }),
+            json!({ "constructor": "make_impute_constant", "type_args": "<Option<String>>", "params": { "constant": "missing" } }),
+            json!({ "constructor": "make_impute_uniform_float", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 1.0 } }),
+            json!({ "constructor": "make_drop_null", "type_args": "<Option<i32>>" }),
+            json!({ "constructor": "make_is_null", "type_args": "<f32>" }),
+            json!({ "constructor": "make_cast", "type_args": "<String, u8>" }),
+            json!({ "constructor": "make_cast_default", "type_args": "<String, i32>", "params": { "default_value": -1 } }),
+            json!({ "constructor": "make_cast_inherent", "type_args": "<String, f64>" }),
+            json!({ "constructor": "make_is_equal", "type_args": "<i32>", "params": { "value": 3 } }),
+            json!({ "constructor": "make_split_records", "params": { "separator": null } }),
+            json!({ "constructor": "make_create_dataframe", "params": { "col_count": 2 } }),
+            json!({ "constructor": "make_split_dataframe", "params": { "separator": ";", "col_count": 2 } }),
+            json!({ "constructor": "make_split_csv", "params": { "keys": ["a,b", "c"], "separator": ";", "quote": "'", "escape": null, "has_header": true } }),
+            json!({ "constructor": "make_parse_jsonl", "type_args": "<String, f64>", "params": { "keys": ["a,b", "c"] } }),
+            json!({ "constructor": "make_read_arrow_ipc", "type_args": "<i32, String>", "params": { "keys": ["a", "b"] } }),
+            json!({ "constructor": "make_read_parquet", "type_args": "<f64>", "params": { "keys": ["a"] } }),
+            json!({ "constructor": "make_parse_column", "type_args": "<f64>", "params": { "input_domain": dataframe(), "key": "a", "impute": false } }),
+            json!({ "constructor": "make_select_column", "type_args": "<f64>", "params": { "input_domain": dataframe(), "key": "b" } }),
+            json!({ "constructor": "make_select_column_symmetric", "type_args": "<f64>", "params": { "input_domain": dataframe(), "key": "b" } }),
+            json!({ "constructor": "make_select_column_option", "type_args": "<bool>", "params": { "input_domain": dataframe(), "key": "c" } }),
+            json!({ "constructor": "make_hamming_to_symmetric", "type_args": "<f64>" }),
+            json!({ "constructor": "make_hamming_to_symmetric_nullable", "type_args": "<Option<i32>>" }),
+            json!({ "constructor": "make_hamming_to_symmetric_dataframe", "params": { "input_domain": dataframe() } }),
+            json!({ "constructor": "make_filter", "type_args": "<i32>", "params": { "predicate": { "and": [{ "ge": 65 }, { "not": { "eq": 99 } }] } } }),
+            json!({ "constructor": "make_filter_dataframe", "type_args": "<f64>", "params": { "input_domain": dataframe(), "key": "b", "predicate": { "between": [1.0, 5.0] } } }),
+            json!({ "constructor": "make_hamming_to_user", "params": { "input_domain": dataframe() } }),
+            json!({ "constructor": "make_bound_user_contributions", "type_args": "<String>", "params": { "input_domain": dataframe(), "id_key": "a", "k": 3 } }),
+            json!({ "constructor": "make_partition_by", "type_args": "<String>", "params": { "input_domain": dataframe(), "key": "a", "keys": ["x,y", "z"] } }),
+            clamp,
+            json!({ "constructor": "make_clamp_symmetric", "type_args": "<i32>", "params": { "lower": 0, "upper": 10 } }),
+            json!({ "constructor": "make_clamp_categorical", "type_args": "<String>", "params": { "categories": ["x,y", "z"], "null": "other" } }),
+            json!({ "constructor": "make_sample_and_aggregate", "type_args": "<f64, u32>", "params": { "k": 5, "lower": 0, "upper": 100 }, "children": [count] }),
+            json!({ "constructor": "make_find_bin", "type_args": "<f64>", "params": { "edges": [0.0, 2.5, 10.0] } }),
+            json!({ "constructor": "make_find", "type_args": "<String>", "params": { "categories": ["x,y", "z"] } }),
+            bounded_sum,
+            json!({ "constructor": "make_bounded_sum_symmetric", "type_args": "<f64>", "params": { "lower": -1.5, "upper": 1.0 } }),
+            json!({ "constructor": "make_bounded_sum_l2", "type_args": "<i32>", "params": { "lower": -1, "upper": 1 } }),
+            json!({ "constructor": "make_count_l1", "type_args": "<f64>" }),
+            json!({ "constructor": "make_count_l2", "type_args": "<bool>" }),
+            json!({ "constructor": "make_lipschitz_mul", "type_args": "<f64>", "params": { "c": 2.0 } }),
+            json!({ "constructor": "make_shift", "type_args": "<i64>", "params": { "shift": -3 } }),
+            json!({ "constructor": "make_affine", "type_args": "<f64>", "params": { "a": 2.0, "b": 1.0 } }),
+            json!({ "constructor": "make_lipschitz_mul_vec", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 1.0, "c": 2.0 } }),
+            json!({ "constructor": "make_shift_vec", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 1.0, "shift": 0.5 } }),
+            json!({ "constructor": "make_affine_vec", "type_args": "<f64>", "params": { "lower": 0.0, "upper": 1.0, "a": 2.0, "b": 1.0 } }),
+            laplace,
+            json!({ "constructor": "make_base_laplace_vec", "type_args": "<f64>", "params": { "sigma": 2.0 } }),
+            json!({ "constructor": "make_base_gaussian", "type_args": "<f64>", "params": { "sigma": 1.5 } }),
+            quantile,
+            json!({ "constructor": "make_sample_and_aggregate_median", "type_args": "<f64, u32>", "params": { "k": 5, "lower": 0, "upper": 100, "candidates": [10, 50, 90], "scale": 1.0 }, "children": [count] }),
+            json!({ "constructor": "make_sample_and_aggregate_mean", "type_args": "<f64, u32>", "params": { "k": 5, "lower": 0, "upper": 100, "sigma": 2.0 }, "children": [count] }),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_constructors() {
+        for value in constructor_asts() {
+            let expected = ast(value);
+            let built = match build(&expected) {
+                Ok(built) => built,
+                // The readers are only made when the library is built with their features.
+                Err(e) if e.kind == ErrorKind::NotImplemented && expected.constructor.starts_with("make_read_") => continue,
+                Err(e) => panic!("{}: {}", expected.constructor, e.message),
+            };
+            assert_eq!(to_ast(&built), expected);
+            assert_eq!(Ast::from_json(&expected.to_json()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_covers_constructors() {
+        let spec_names = |bootstrap: extern "C" fn() -> *mut c_char| -> BTreeSet<String> {
+            let spec: Value = serde_json::from_str(&util::into_string(bootstrap()).unwrap()).unwrap();
+            spec["functions"].as_array().unwrap().iter().map(|e| e["name"].as_str().unwrap().to_owned()).filter(|e| e.starts_with("make_")).collect()
+        };
+        let mut expected = spec_names(trans::opendp_trans__bootstrap);
+        expected.extend(spec_names(meas::opendp_meas__bootstrap));
+        expected.extend(spec_names(core::opendp_core__bootstrap));
+        let covered: BTreeSet<_> = constructor_asts().into_iter().map(|e| ast(e).constructor).collect();
+        assert_eq!(covered, expected);
+    }
+
+    #[test]
+    fn test_round_trip_pipeline() {
+        let type_args = CString::new("<f64>").unwrap();
+        let (lower, upper) = (0.0_f64, 10.0_f64);
+        let bounds = (&lower as *const f64 as *const c_void, &upper as *const f64 as *const c_void);
+        let clamp = Built::Transformation(trans::opendp_trans__make_clamp(type_args.as_ptr(), bounds.0, bounds.1).into_result().unwrap());
+        let bounded_sum = Built::Transformation(trans::opendp_trans__make_bounded_sum_l1(type_args.as_ptr(), bounds.0, bounds.1).into_result().unwrap());
+        let chain = Built::Transformation(core::opendp_core__make_chain_tt(bounded_sum.as_transformation().unwrap(), clamp.as_transformation().unwrap()).into_result().unwrap());
+        let json = to_c_string(&json!(to_ast(&chain)));
+        let rebuilt = Built::Transformation(core::opendp_core__transformation_from_ast(json.as_ptr()).into_result().unwrap());
+        assert_eq!(to_ast(&rebuilt), to_ast(&chain));
+
+        let invoke = |built: &Built| -> f64 {
+            let arg = FfiObject::new(vec![1.0_f64, 2.0, 30.0]);
+            let res = core::opendp_core__transformation_invoke(built.as_transformation().unwrap(), arg).into_result().unwrap();
+            data::opendp_data__data_free(arg);
+            util::into_owned(res).unwrap().into_owned().unwrap()
+        };
+        assert_eq!(invoke(&rebuilt), 13.0);
+        assert_eq!(invoke(&rebuilt), invoke(&chain));
+    }
+
+    #[test]
+    fn test_round_trip_floats() {
+        // Without the float_roundtrip feature of serde_json, this would be parsed as the next float up.
+        let sigma = 93.33561522131723;
+        let type_args = CString::new("<f64>").unwrap();
+        let laplace = Built::Measurement(meas::opendp_meas__make_base_laplace(type_args.as_ptr(), sigma).into_result().unwrap());
+        let expected = to_ast(&laplace);
+        assert_eq!(expected.params["sigma"].as_f64(), Some(sigma));
+        let rebuilt = build(&Ast::from_json(&expected.to_json()).unwrap()).unwrap();
+        assert_eq!(to_ast(&rebuilt), expected);
+
+        // Non-finite floats are encoded as strings, rather than as nulls that can't be rebuilt.
+        let (lower, upper) = (f64::NEG_INFINITY, f64::INFINITY);
+        let bounds = (&lower as *const f64 as *const c_void, &upper as *const f64 as *const c_void);
+        let clamp = Built::Transformation(trans::opendp_trans__make_clamp(type_args.as_ptr(), bounds.0, bounds.1).into_result().unwrap());
+        let expected = to_ast(&clamp);
+        assert_eq!(expected.params["lower"], json!("-inf"));
+        assert_eq!(expected.params["upper"], json!("inf"));
+        let rebuilt = build(&Ast::from_json(&expected.to_json()).unwrap()).unwrap();
+        assert_eq!(to_ast(&rebuilt), expected);
+        let laplace = Built::Measurement(meas::opendp_meas__make_base_laplace(type_args.as_ptr(), f64::NAN).into_result().unwrap());
+        let expected = to_ast(&laplace);
+        assert_eq!(expected.params["sigma"], json!("NaN"));
+        assert!(build(&expected).is_ok());
+
+        // Only floats are decoded from strings.
+        let kind = |value: Value| build(&ast(value)).err().unwrap().kind;
+        assert_eq!(kind(json!({ "constructor": "make_clamp", "type_args": "<i32>", "params": { "lower": "-inf", "upper": "1" } })), ErrorKind::FailedParse);
+        assert_eq!(kind(json!({ "constructor": "make_base_laplace", "type_args": "<f64>", "params": { "sigma": "1.0" } })), ErrorKind::FailedParse);
+    }
+
+    #[test]
+    fn test_no_ast() {
+        let measurement = Built::Measurement(FfiMeasurement::new_from_types(opendp::meas::make_base_laplace::<f64>(1.0)));
+        let error = core::opendp_core__measurement_to_ast(measurement.as_measurement().unwrap()).into_result().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Ffi);
+
+        // A pipeline with a part that has no AST has none either.
+        let laplace = ast(json!({ "constructor": "make_base_laplace", "type_args": "<f64>", "params": { "sigma": 1.0 } }));
+        let laplace = build(&laplace).unwrap();
+        let composition = core::opendp_core__make_composition(measurement.as_measurement().unwrap(), laplace.as_measurement().unwrap()).into_result().unwrap();
+        let composition = Built::Measurement(composition);
+        assert_eq!(util::as_ref(composition.as_measurement().unwrap()).unwrap().ast, None);
+    }
+
+    #[test]
+    fn test_bogus_asts() {
+        let kind = |value: Value| build(&ast(value)).err().unwrap().kind;
+        assert_eq!(kind(json!({ "constructor": "make_bogus" })), ErrorKind::FailedParse);
+        assert_eq!(kind(json!({ "constructor": "make_clamp", "type_args": "<f64>", "params": { "lower": 0.0 } })), ErrorKind::FailedParse);
+        assert_eq!(kind(json!({ "constructor": "make_clamp", "params": { "lower": 0.0, "upper": 1.0 } })), ErrorKind::FailedParse);
+        assert_eq!(kind(json!({ "constructor": "make_clamp", "type_args": "<f64>", "params": { "lower": "zero", "upper": 1.0 } })), ErrorKind::FailedParse);
+        assert_eq!(kind(json!({ "constructor": "make_chain_tt", "children": [{ "constructor": "make_split_lines" }] })), ErrorKind::FailedParse);
+        assert_eq!(Ast::from_json(r#"{ "constructor": "make_split_lines", "parameters": {} }"#).unwrap_err().kind, ErrorKind::FailedParse);
+
+        // An AST of the wrong kind is rejected, and what was built for it is freed.
+        let json = to_c_string(&json!({ "constructor": "make_split_lines" }));
+        let error = core::opendp_core__measurement_from_ast(json.as_ptr()).into_result().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TypeMismatch);
+        let json = to_c_string(&json!({ "constructor": "make_composition", "children": [{ "constructor": "make_split_lines" }, { "constructor": "make_split_lines" }] }));
+        let error = core::opendp_core__measurement_from_ast(json.as_ptr()).into_result().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TypeMismatch);
+    }
+}
@@ -1,19 +1,31 @@
+use std::ffi::CString;
+use std::mem;
 use std::mem::transmute;
 use std::os::raw::c_char;
+use std::rc::Rc;
 
 use opendp::core;
-use opendp::core::{Domain, Measure, MeasureGlue, Measurement, Metric, MetricGlue, Transformation};
+use opendp::core::{Domain, FixedSizeSampler, Function, Measure, MeasureGlue, Measurement, Metric, MetricGlue, PoissonSampler, Sampler, Transformation};
+use opendp::dist::{AmplifiableMeasure, HammingDistance, MaxDivergence, SmoothedMaxDivergence, SymmetricDistance, UserDistance};
+use opendp::dom::{AllDomain, BoxDomain, DataFrameDomain, IntervalDomain, PairDomain, VectorDomain};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
+use crate::ast;
+use crate::ast::Ast;
 use crate::util;
-use crate::util::Type;
+use crate::util::{c_bool, Error, ErrorKind, FfiError, FfiResult, Type};
 
+/// A value of any type, erased so that it can cross FFI. The value is owned, and dropped with its real type.
 pub struct FfiObject {
     pub type_: Type,
-    pub value: Box<()>,
+    value: *mut (),
 }
 
 impl FfiObject {
+    /// Wraps an erased value, which must be a `Box` of the type described by `type_`.
     pub fn new_typed(type_: Type, value: Box<()>) -> *mut FfiObject {
+        let value = Box::into_raw(value);
         let object = FfiObject { type_, value };
         util::into_raw(object)
     }
@@ -24,17 +36,38 @@ impl FfiObject {
         Self::new_typed(type_, value)
     }
 
-    // pub fn into_owned<T>(self) -> T {
-    //     // TODO: Check T against self.type_.
-    //     let value = Box::into_raw(self.value) as *mut T;
-    //     ffi_utils::into_owned(value)
-    // }
+    fn check_type<T: 'static>(&self) -> Result<(), Error> {
+        let type_ = Type::new::<T>();
+        if self.type_ == type_ {
+            Ok(())
+        } else {
+            Err(Error::new(ErrorKind::TypeMismatch, format!("Expected {}, found {}", type_.name, self.type_.name)))
+        }
+    }
+
+    pub fn as_ref<T: 'static>(&self) -> Result<&T, Error> {
+        self.check_type::<T>()?;
+        Ok(unsafe { &*(self.value as *const T) })
+    }
+
+    #[allow(dead_code)]  // Not yet needed by any FFI function.
+    pub fn into_owned<T: 'static>(self) -> Result<T, Error> {
+        self.check_type::<T>()?;
+        let value = self.value as *mut T;
+        // Ownership of the value moves to the result, so don't drop it along with self.
+        mem::forget(self);
+        Ok(*unsafe { Box::from_raw(value) })
+    }
+
+    /// The erased value, for passing to a glued [`Function`](opendp::core::Function).
+    pub fn as_erased(&self) -> &() {
+        unsafe { &*(self.value as *const ()) }
+    }
+}
 
-    pub fn as_ref<T>(&self) -> &T {
-        // TODO: Check type.
-        let value = self.value.as_ref() as *const () as *const T;
-        let value = unsafe { value.as_ref() };
-        value.unwrap()
+impl Drop for FfiObject {
+    fn drop(&mut self) {
+        unsafe { self.type_.drop_value(self.value) }
     }
 }
 
@@ -48,17 +81,26 @@ fn new_domain_types<D: 'static + Domain>() -> (Type, Type) {
 pub struct FfiMetricGlue<D: Domain, M: Metric> {
     pub domain_type: Type,
     pub domain_carrier: Type,
+    pub metric_type: Type,
+    pub metric_distance: Type,
     pub metric_glue: MetricGlue<D, M>,
 }
 impl<D: 'static + Domain, M: 'static + Metric> FfiMetricGlue<D, M> {
     pub fn new() -> Self {
         let (domain_type, domain_carrier) = new_domain_types::<D>();
+        let metric_type = Type::new::<M>();
+        let metric_distance = Type::new::<M::Distance>();
         let metric_glue = MetricGlue::new();
-        Self::new_explicit(domain_type, domain_carrier, metric_glue)
+        Self::new_explicit(domain_type, domain_carrier, metric_type, metric_distance, metric_glue)
     }
 
-    pub fn new_explicit(domain_type: Type, domain_carrier: Type, metric_glue: MetricGlue<D, M>) -> Self {
-        FfiMetricGlue { domain_type, domain_carrier, metric_glue }
+    pub fn new_explicit(domain_type: Type, domain_carrier: Type, metric_type: Type, metric_distance: Type, metric_glue: MetricGlue<D, M>) -> Self {
+        FfiMetricGlue { domain_type, domain_carrier, metric_type, metric_distance, metric_glue }
+    }
+}
+impl<D: 'static + Domain, M: 'static + Metric> Default for FfiMetricGlue<D, M> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -66,16 +108,25 @@ impl<D: 'static + Domain, M: 'static + Metric> FfiMetricGlue<D, M> {
 pub struct FfiMeasureGlue<D: Domain, M: Measure> {
     pub domain_type: Type,
     pub domain_carrier: Type,
+    pub measure_type: Type,
+    pub measure_distance: Type,
     pub measure_glue: MeasureGlue<D, M>,
 }
 impl<D: 'static + Domain, M: 'static + Measure> FfiMeasureGlue<D, M> {
     pub fn new() -> Self {
         let (domain_type, domain_carrier) = new_domain_types::<D>();
+        let measure_type = Type::new::<M>();
+        let measure_distance = Type::new::<M::Distance>();
         let measure_glue = MeasureGlue::new();
-        Self::new_explicit(domain_type, domain_carrier, measure_glue)
+        Self::new_explicit(domain_type, domain_carrier, measure_type, measure_distance, measure_glue)
     }
-    pub fn new_explicit(domain_type: Type, domain_carrier: Type, measure_glue: MeasureGlue<D, M>) -> Self {
-        FfiMeasureGlue { domain_type, domain_carrier, measure_glue }
+    pub fn new_explicit(domain_type: Type, domain_carrier: Type, measure_type: Type, measure_distance: Type, measure_glue: MeasureGlue<D, M>) -> Self {
+        FfiMeasureGlue { domain_type, domain_carrier, measure_type, measure_distance, measure_glue }
+    }
+}
+impl<D: 'static + Domain, M: 'static + Measure> Default for FfiMeasureGlue<D, M> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -102,130 +153,648 @@ pub struct FfiMeasurement {
     pub input_glue: FfiMetricGlue<FfiDomain, FfiMetric>,
     pub output_glue: FfiMeasureGlue<FfiDomain, FfiMeasure>,
     pub value: Box<Measurement<FfiDomain, FfiDomain, FfiMetric, FfiMeasure>>,
+    /// How the measurement was constructed, if it was by an FFI constructor.
+    pub ast: Option<Ast>,
 }
 
 impl FfiMeasurement {
     pub fn new_from_types<ID: 'static + Domain, OD: 'static + Domain, IM: 'static + Metric, OM: 'static + Measure>(value: Measurement<ID, OD, IM, OM>) -> *mut FfiMeasurement {
+        Self::new_from_ast(None, value)
+    }
+
+    /// Like [`FfiMeasurement::new_from_types`], recording the AST of the constructor.
+    pub fn new_from_ast<ID: 'static + Domain, OD: 'static + Domain, IM: 'static + Metric, OM: 'static + Measure>(ast: Option<Ast>, value: Measurement<ID, OD, IM, OM>) -> *mut FfiMeasurement {
         let input_glue = FfiMetricGlue::<ID, IM>::new();
         let input_glue = unsafe { transmute(input_glue) };
         let output_glue = FfiMeasureGlue::<OD, OM>::new();
         let output_glue = unsafe { transmute(output_glue) };
-        Self::new(input_glue, output_glue, value)
+        Self::new(input_glue, output_glue, value, ast)
     }
 
-    pub fn new<ID: 'static + Domain, OD: 'static + Domain, IM: Metric, OM: Measure>(input_glue: FfiMetricGlue<FfiDomain, FfiMetric>, output_glue: FfiMeasureGlue<FfiDomain, FfiMeasure>, value: Measurement<ID, OD, IM, OM>) -> *mut FfiMeasurement {
+    pub fn new<ID: 'static + Domain, OD: 'static + Domain, IM: Metric, OM: Measure>(input_glue: FfiMetricGlue<FfiDomain, FfiMetric>, output_glue: FfiMeasureGlue<FfiDomain, FfiMeasure>, value: Measurement<ID, OD, IM, OM>, ast: Option<Ast>) -> *mut FfiMeasurement {
         let value = util::into_box(value);
-        let ffi_measurement = FfiMeasurement { input_glue, output_glue, value };
+        let ffi_measurement = FfiMeasurement { input_glue, output_glue, value, ast };
         util::into_raw(ffi_measurement)
     }
+
+    /// The measurement with its real types, for combinators that can't work on erased values. Fails unless the types match the glue.
+    fn as_typed<ID: 'static + Domain, OD: 'static + Domain, IM: 'static + Metric, OM: 'static + Measure>(&self) -> Result<&Measurement<ID, OD, IM, OM>, Error> {
+        check_type(&Type::new::<OD>(), &self.output_glue.domain_type)?;
+        let measurement = self.as_typed_input::<ID, IM, OM>()?;
+        Ok(unsafe { &*(measurement as *const Measurement<ID, FfiDomain, IM, OM> as *const Measurement<ID, OD, IM, OM>) })
+    }
+
+    /// Like `as_typed()`, but leaves the output domain erased.
+    fn as_typed_input<ID: 'static + Domain, IM: 'static + Metric, OM: 'static + Measure>(&self) -> Result<&Measurement<ID, FfiDomain, IM, OM>, Error> {
+        check_type(&Type::new::<ID>(), &self.input_glue.domain_type)?;
+        check_type(&Type::new::<IM>(), &self.input_glue.metric_type)?;
+        check_type(&Type::new::<OM>(), &self.output_glue.measure_type)?;
+        Ok(unsafe { &*(self.value.as_ref() as *const Measurement<FfiDomain, FfiDomain, FfiMetric, FfiMeasure> as *const Measurement<ID, FfiDomain, IM, OM>) })
+    }
+}
+
+impl Drop for FfiMeasurement {
+    fn drop(&mut self) {
+        // The erased domains would be dropped as FfiDomain, so drop them through the glue with their real types.
+        let input_domain = mem::replace(&mut self.value.input_domain, Box::new(FfiDomain));
+        (self.input_glue.metric_glue.domain_drop)(input_domain);
+        let output_domain = mem::replace(&mut self.value.output_domain, Box::new(FfiDomain));
+        (self.output_glue.measure_glue.domain_drop)(output_domain);
+    }
 }
 
 pub struct FfiTransformation {
     pub input_glue: FfiMetricGlue<FfiDomain, FfiMetric>,
     pub output_glue: FfiMetricGlue<FfiDomain, FfiMetric>,
     pub value: Box<Transformation<FfiDomain, FfiDomain, FfiMetric, FfiMetric>>,
+    /// How the transformation was constructed, if it was by an FFI constructor.
+    pub ast: Option<Ast>,
 }
 
 impl FfiTransformation {
     pub fn new_from_types<ID: 'static + Domain, OD: 'static + Domain, IM: 'static + Metric, OM: 'static + Metric>(value: Transformation<ID, OD, IM, OM>) -> *mut FfiTransformation {
+        Self::new_from_ast(None, value)
+    }
+
+    /// Like [`FfiTransformation::new_from_types`], recording the AST of the constructor.
+    pub fn new_from_ast<ID: 'static + Domain, OD: 'static + Domain, IM: 'static + Metric, OM: 'static + Metric>(ast: Option<Ast>, value: Transformation<ID, OD, IM, OM>) -> *mut FfiTransformation {
         let input_glue = FfiMetricGlue::<ID, IM>::new();
         let input_glue = unsafe { transmute(input_glue) };
         let output_glue = FfiMetricGlue::<OD, OM>::new();
         let output_glue = unsafe { transmute(output_glue) };
-        Self::new(input_glue, output_glue, value)
+        Self::new(input_glue, output_glue, value, ast)
     }
 
-    pub fn new<ID: 'static + Domain, OD: 'static + Domain, IM: Metric, OM: Metric>(input_glue: FfiMetricGlue<FfiDomain, FfiMetric>, output_glue: FfiMetricGlue<FfiDomain, FfiMetric>, value: Transformation<ID, OD, IM, OM>) -> *mut FfiTransformation {
+    pub fn new<ID: 'static + Domain, OD: 'static + Domain, IM: Metric, OM: Metric>(input_glue: FfiMetricGlue<FfiDomain, FfiMetric>, output_glue: FfiMetricGlue<FfiDomain, FfiMetric>, value: Transformation<ID, OD, IM, OM>, ast: Option<Ast>) -> *mut FfiTransformation {
         let value = util::into_box(value);
-        let ffi_transformation = FfiTransformation { input_glue, output_glue, value };
+        let ffi_transformation = FfiTransformation { input_glue, output_glue, value, ast };
         util::into_raw(ffi_transformation)
     }
+
+    /// The function of the transformation, with its real carrier types, for constructors that take a function. Fails unless
+    /// the carriers match the glue.
+    pub fn typed_function<ID: Domain, OD: Domain>(&self) -> Result<Function<ID, OD>, Error> where
+        ID::Carrier: 'static, OD::Carrier: 'static {
+        check_type(&Type::new::<ID::Carrier>(), &self.input_glue.domain_carrier)?;
+        check_type(&Type::new::<OD::Carrier>(), &self.output_glue.domain_carrier)?;
+        let function = self.value.function.function.clone();
+        let function = unsafe { transmute::<Rc<dyn Fn(&()) -> Box<()>>, Rc<dyn Fn(&ID::Carrier) -> Box<OD::Carrier>>>(function) };
+        Ok(Function { function })
+    }
+}
+
+impl Drop for FfiTransformation {
+    fn drop(&mut self) {
+        // The erased domains would be dropped as FfiDomain, so drop them through the glue with their real types.
+        let input_domain = mem::replace(&mut self.value.input_domain, Box::new(FfiDomain));
+        (self.input_glue.metric_glue.domain_drop)(input_domain);
+        let output_domain = mem::replace(&mut self.value.output_domain, Box::new(FfiDomain));
+        (self.output_glue.metric_glue.domain_drop)(output_domain);
+    }
+}
+
+fn check_type(expected: &Type, found: &Type) -> Result<(), Error> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::TypeMismatch, format!("Expected {}, found {}", expected.descriptor, found.descriptor)))
+    }
+}
+
+/// Applies a measurement to an argument.
+///
+/// # Arguments
+/// * `this` - The measurement.
+/// * `arg` - An object of the measurement's input carrier type.
+#[no_mangle]
+pub extern "C" fn opendp_core__measurement_invoke(this: *const FfiMeasurement, arg: *const FfiObject) -> FfiResult<*mut FfiObject> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let arg = util::as_ref(arg)?;
+        check_type(&this.input_glue.domain_carrier, &arg.type_)?;
+        let res_type = this.output_glue.domain_carrier.clone();
+        let res = this.value.function.eval_ffi(arg.as_erased());
+        Ok(FfiObject::new_typed(res_type, res))
+    })
+}
+
+/// Checks whether a measurement is `d_out`-private for inputs that are `d_in`-close.
+///
+/// # Arguments
+/// * `this` - The measurement.
+/// * `d_in` - An object of the distance type of the input metric, like `i32`.
+/// * `d_out` - An object of the distance type of the output measure, like `f64`.
+#[no_mangle]
+pub extern "C" fn opendp_core__measurement_check(this: *const FfiMeasurement, d_in: *const FfiObject, d_out: *const FfiObject) -> FfiResult<c_bool> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let d_in = util::as_ref(d_in)?;
+        let d_out = util::as_ref(d_out)?;
+        check_type(&this.input_glue.metric_distance, &d_in.type_)?;
+        check_type(&this.output_glue.measure_distance, &d_out.type_)?;
+        let res = this.value.privacy_relation.eval(d_in.as_erased(), d_out.as_erased())?;
+        Ok(util::from_bool(res))
+    })
+}
+
+/// Returns a JSON description of the domains, metric and measure of a measurement.
+///
+/// The description is an object with the keys `input_domain`, `output_domain`, `input_metric` and `output_measure`.
+///
+/// # Arguments
+/// * `this` - The measurement.
+#[no_mangle]
+pub extern "C" fn opendp_core__measurement_describe(this: *const FfiMeasurement) -> FfiResult<*mut c_char> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let description = json!({
+            "input_domain": (this.input_glue.metric_glue.domain_describe)(&this.value.input_domain),
+            "output_domain": (this.output_glue.measure_glue.domain_describe)(&this.value.output_domain),
+            "input_metric": (this.input_glue.metric_glue.metric_describe)(&this.value.input_metric),
+            "output_measure": (this.output_glue.measure_glue.measure_describe)(&this.value.output_measure),
+        });
+        Ok(util::into_c_char_p(description.to_string()))
+    })
+}
+
+/// Frees a measurement.
+#[no_mangle]
+pub extern "C" fn opendp_core__measurement_free(this: *mut FfiMeasurement) -> FfiResult<*mut ()> {
+    util::ffi_result(|| {
+        util::into_owned(this)?;
+        Ok(util::ffi_unit())
+    })
+}
+
+/// Applies a transformation to an argument.
+///
+/// # Arguments
+/// * `this` - The transformation.
+/// * `arg` - An object of the transformation's input carrier type.
+#[no_mangle]
+pub extern "C" fn opendp_core__transformation_invoke(this: *const FfiTransformation, arg: *const FfiObject) -> FfiResult<*mut FfiObject> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let arg = util::as_ref(arg)?;
+        check_type(&this.input_glue.domain_carrier, &arg.type_)?;
+        let res_type = this.output_glue.domain_carrier.clone();
+        let res = this.value.function.eval_ffi(arg.as_erased());
+        Ok(FfiObject::new_typed(res_type, res))
+    })
+}
+
+/// Checks whether a transformation is `d_out`-stable for inputs that are `d_in`-close.
+///
+/// # Arguments
+/// * `this` - The transformation.
+/// * `d_in` - An object of the distance type of the input metric, like `i32`.
+/// * `d_out` - An object of the distance type of the output metric.
+#[no_mangle]
+pub extern "C" fn opendp_core__transformation_check(this: *const FfiTransformation, d_in: *const FfiObject, d_out: *const FfiObject) -> FfiResult<c_bool> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let d_in = util::as_ref(d_in)?;
+        let d_out = util::as_ref(d_out)?;
+        check_type(&this.input_glue.metric_distance, &d_in.type_)?;
+        check_type(&this.output_glue.metric_distance, &d_out.type_)?;
+        let res = this.value.stability_relation.eval(d_in.as_erased(), d_out.as_erased())?;
+        Ok(util::from_bool(res))
+    })
+}
+
+/// Returns a JSON description of the domains and metrics of a transformation.
+///
+/// The description is an object with the keys `input_domain`, `output_domain`, `input_metric` and `output_metric`.
+///
+/// # Arguments
+/// * `this` - The transformation.
+#[no_mangle]
+pub extern "C" fn opendp_core__transformation_describe(this: *const FfiTransformation) -> FfiResult<*mut c_char> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let description = json!({
+            "input_domain": (this.input_glue.metric_glue.domain_describe)(&this.value.input_domain),
+            "output_domain": (this.output_glue.metric_glue.domain_describe)(&this.value.output_domain),
+            "input_metric": (this.input_glue.metric_glue.metric_describe)(&this.value.input_metric),
+            "output_metric": (this.output_glue.metric_glue.metric_describe)(&this.value.output_metric),
+        });
+        Ok(util::into_c_char_p(description.to_string()))
+    })
+}
+
+/// Returns a copy of the output domain of a transformation, as an object.
+#[no_mangle]
+pub extern "C" fn opendp_core__transformation_output_domain(this: *const FfiTransformation) -> FfiResult<*mut FfiObject> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let domain = (this.output_glue.metric_glue.domain_clone)(&this.value.output_domain);
+        // The glue returns the real output domain behind the erased FfiDomain, so just take ownership of the pointer.
+        let domain = unsafe { Box::from_raw(Box::into_raw(domain) as *mut ()) };
+        Ok(FfiObject::new_typed(this.output_glue.domain_type.clone(), domain))
+    })
+}
+
+/// Frees a transformation.
+#[no_mangle]
+pub extern "C" fn opendp_core__transformation_free(this: *mut FfiTransformation) -> FfiResult<*mut ()> {
+    util::ffi_result(|| {
+        util::into_owned(this)?;
+        Ok(util::ffi_unit())
+    })
+}
+
+/// Chains a measurement after a transformation.
+///
+/// Fails with `TypeMismatch` unless the transformation outputs the domain and metric that the measurement takes.
+///
+/// # Arguments
+/// * `measurement1` - The measurement, applied second.
+/// * `transformation0` - The transformation, applied first.
+#[no_mangle]
+pub extern "C" fn opendp_core__make_chain_mt(measurement1: *mut FfiMeasurement, transformation0: *mut FfiTransformation) -> FfiResult<*mut FfiMeasurement> {
+    util::ffi_result(|| {
+        let transformation0 = util::as_ref(transformation0)?;
+        let measurement1 = util::as_ref(measurement1)?;
+        check_type(&measurement1.input_glue.domain_type, &transformation0.output_glue.domain_type)?;
+        check_type(&measurement1.input_glue.metric_type, &transformation0.output_glue.metric_type)?;
+        let input_glue = transformation0.input_glue.clone();
+        let x_glue = transformation0.output_glue.clone();
+        let output_glue = measurement1.output_glue.clone();
+        let measurement = core::make_chain_mt_glue(&measurement1.value, &transformation0.value, &input_glue.metric_glue, &x_glue.metric_glue, &output_glue.measure_glue);
+        let ast = Ast::combine("make_chain_mt", &[&measurement1.ast, &transformation0.ast]);
+        Ok(FfiMeasurement::new(input_glue, output_glue, measurement, ast))
+    })
 }
 
+/// Chains two transformations.
+///
+/// Fails with `TypeMismatch` unless `transformation0` outputs the domain and metric that `transformation1` takes.
+///
+/// # Arguments
+/// * `transformation1` - The transformation applied second.
+/// * `transformation0` - The transformation applied first.
 #[no_mangle]
-pub extern "C" fn opendp_core__measurement_invoke(this: *const FfiMeasurement, arg: *const FfiObject) -> *mut FfiObject {
-    let this = util::as_ref(this);
-    let arg = util::as_ref(arg);
-    assert_eq!(arg.type_, this.input_glue.domain_carrier);
-    let res_type = this.output_glue.domain_carrier.clone();
-    let res = this.value.function.eval_ffi(&arg.value);
-    FfiObject::new_typed(res_type, res)
+pub extern "C" fn opendp_core__make_chain_tt(transformation1: *mut FfiTransformation, transformation0: *mut FfiTransformation) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let transformation0 = util::as_ref(transformation0)?;
+        let transformation1 = util::as_ref(transformation1)?;
+        check_type(&transformation1.input_glue.domain_type, &transformation0.output_glue.domain_type)?;
+        check_type(&transformation1.input_glue.metric_type, &transformation0.output_glue.metric_type)?;
+        let input_glue = transformation0.input_glue.clone();
+        let x_glue = transformation0.output_glue.clone();
+        let output_glue = transformation1.output_glue.clone();
+        let transformation = core::make_chain_tt_glue(&transformation1.value, &transformation0.value, &input_glue.metric_glue, &x_glue.metric_glue, &output_glue.metric_glue);
+        let ast = Ast::combine("make_chain_tt", &[&transformation1.ast, &transformation0.ast]);
+        Ok(FfiTransformation::new(input_glue, output_glue, transformation, ast))
+    })
+}
+
+/// Composes two measurements on the same input, returning a pair of their outputs.
+///
+/// Fails with `TypeMismatch` unless the measurements take the same domain and metric, and have the same measure.
+#[no_mangle]
+pub extern "C" fn opendp_core__make_composition(measurement0: *mut FfiMeasurement, measurement1: *mut FfiMeasurement) -> FfiResult<*mut FfiMeasurement> {
+    util::ffi_result(|| {
+        let measurement0 = util::as_ref(measurement0)?;
+        let measurement1 = util::as_ref(measurement1)?;
+        check_type(&measurement0.input_glue.domain_type, &measurement1.input_glue.domain_type)?;
+        check_type(&measurement0.input_glue.metric_type, &measurement1.input_glue.metric_type)?;
+        check_type(&measurement0.output_glue.measure_type, &measurement1.output_glue.measure_type)?;
+        let input_glue = measurement0.input_glue.clone();
+        let output_glue0 = measurement0.output_glue.clone();
+        let output_glue1 = measurement1.output_glue.clone();
+        let output_glue_domain_type = Type::new::<FfiDomain>();
+        let output_glue_domain_carrier = Type::new_box_pair(&output_glue0.domain_carrier, &output_glue1.domain_carrier);
+        let output_glue_measure_type = output_glue0.measure_type.clone();
+        let output_glue_measure_distance = output_glue0.measure_distance.clone();
+        // The output domain is a PairDomain of the erased domains, so build its glue from the parts and erase it too.
+        let output_glue_measure_glue = MeasureGlue::new_pair(&output_glue0.measure_glue, &output_glue1.measure_glue);
+        let output_glue_measure_glue = unsafe { transmute::<MeasureGlue<PairDomain<BoxDomain<FfiDomain>, BoxDomain<FfiDomain>>, FfiMeasure>, MeasureGlue<FfiDomain, FfiMeasure>>(output_glue_measure_glue) };
+        let output_glue = FfiMeasureGlue::<FfiDomain, FfiMeasure>::new_explicit(output_glue_domain_type, output_glue_domain_carrier, output_glue_measure_type, output_glue_measure_distance, output_glue_measure_glue);
+        let measurement = core::make_composition_glue(&measurement0.value, &measurement1.value, &input_glue.metric_glue, &output_glue0.measure_glue, &output_glue1.measure_glue);
+        let ast = Ast::combine("make_composition", &[&measurement0.ast, &measurement1.ast]);
+        Ok(FfiMeasurement::new(input_glue, output_glue, measurement, ast))
+    })
+}
+
+/// Applies a measurement to each partition of its input, as made by `opendp_trans__make_partition_by()`.
+///
+/// The results are collected into a vector, so the real output domain of the measurement must be one of the
+/// `AllDomain<T>` or `VectorDomain<AllDomain<T>>` of a number type, as made by the measurement constructors.
+///
+/// # Arguments
+/// * `measurement` - The measurement to apply to each partition, whose input domain is a dataframe domain.
+#[no_mangle]
+pub extern "C" fn opendp_core__make_parallel_composition(measurement: *mut FfiMeasurement) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<OD, IM, OM>(measurement: &FfiMeasurement, ast: Option<Ast>) -> Result<*mut FfiMeasurement, Error> where
+        OD: 'static + Domain, IM: 'static + Metric, OM: 'static + Measure {
+        let measurement = core::make_parallel_composition(measurement.as_typed::<DataFrameDomain, OD, IM, OM>()?);
+        Ok(FfiMeasurement::new_from_ast(ast, measurement))
+    }
+    util::ffi_result(|| {
+        let measurement = util::as_ref(measurement)?;
+        let ast = Ast::combine("make_parallel_composition", &[&measurement.ast]);
+        dispatch!(monomorphize, [
+            (measurement.output_glue.domain_type, [
+                AllDomain<u32>, AllDomain<u64>, AllDomain<i32>, AllDomain<i64>, AllDomain<f32>, AllDomain<f64>, AllDomain<u8>,
+                VectorDomain<AllDomain<u32>>, VectorDomain<AllDomain<u64>>, VectorDomain<AllDomain<i32>>, VectorDomain<AllDomain<i64>>,
+                VectorDomain<AllDomain<f32>>, VectorDomain<AllDomain<f64>>, VectorDomain<AllDomain<u8>>]),
+            (measurement.input_glue.metric_type, [HammingDistance, SymmetricDistance, UserDistance]),
+            (measurement.output_glue.measure_type, [MaxDivergence, SmoothedMaxDivergence])
+        ], (measurement, ast))
+    })
 }
 
+/// The sampler of `opendp_core__make_subsample()`, serialized as JSON like `{ "poisson": { "rate": 0.1 } }`
+/// or `{ "fixed_size": { "population_size": 1000, "sample_size": 100 } }`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SamplerSpec {
+    Poisson(PoissonSampler),
+    FixedSize(FixedSizeSampler),
+}
+
+/// The element domain of a vector domain, so that dispatching on the type of a vector domain gives the type of its elements.
+trait VectorElement {
+    type Element: 'static + Domain;
+}
+impl<D: 'static + Domain> VectorElement for VectorDomain<D> {
+    type Element = D;
+}
+
+/// Runs a measurement on a subsample of its input, with the privacy relation tightened by amplification.
+///
+/// The input domain of the measurement must be a `VectorDomain` of the `AllDomain<T>` of a primitive type,
+/// or of the `IntervalDomain<T>` of a number type. The Poisson sampler needs the input metric to be `SymmetricDistance`,
+/// and the fixed size sampler needs `HammingDistance`.
+///
+/// # Arguments
+/// * `measurement` - The measurement to run on the subsample.
+/// * `sampler` - The sampler, serialized as JSON like `{ "poisson": { "rate": 0.1 } }` or `{ "fixed_size": { "population_size": 1000, "sample_size": 100 } }`.
 #[no_mangle]
-pub extern "C" fn opendp_core__measurement_free(this: *mut FfiMeasurement) {
-    util::into_owned(this);
+pub extern "C" fn opendp_core__make_subsample(measurement: *mut FfiMeasurement, sampler: *const c_char) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<ID, OM>(measurement: &FfiMeasurement, sampler: SamplerSpec, ast: Option<Ast>) -> Result<*mut FfiMeasurement, Error> where
+        ID: VectorElement, <ID::Element as Domain>::Carrier: Clone, OM: 'static + AmplifiableMeasure {
+        match sampler {
+            SamplerSpec::Poisson(sampler) => subsample::<ID::Element, SymmetricDistance, OM, _>(measurement, sampler, ast),
+            SamplerSpec::FixedSize(sampler) => subsample::<ID::Element, HammingDistance, OM, _>(measurement, sampler, ast),
+        }
+    }
+    fn subsample<D, IM, OM, S>(measurement: &FfiMeasurement, sampler: S, ast: Option<Ast>) -> Result<*mut FfiMeasurement, Error> where
+        D: 'static + Domain, D::Carrier: Clone, IM: 'static + Metric<Distance=i32>, OM: 'static + AmplifiableMeasure, S: Sampler<IM> {
+        // The output of the measurement is passed through, so it stays erased, and is cloned with the glue.
+        let typed = measurement.as_typed_input::<VectorDomain<D>, IM, OM>()?;
+        let output_glue = unsafe { transmute::<MeasureGlue<FfiDomain, FfiMeasure>, MeasureGlue<FfiDomain, OM>>(measurement.output_glue.measure_glue.clone()) };
+        let value = core::make_subsample_glue(typed, sampler, &output_glue)?;
+        Ok(FfiMeasurement::new(measurement.input_glue.clone(), measurement.output_glue.clone(), value, ast))
+    }
+    util::ffi_result(|| {
+        let measurement = util::as_ref(measurement)?;
+        let sampler: SamplerSpec = serde_json::from_str(util::to_str(sampler)?).map_err(|e| Error::new(ErrorKind::FailedParse, format!("Bogus sampler: {}", e)))?;
+        let ast = Ast::combine("make_subsample", &[&measurement.ast]).map(|e| e.param("sampler", &sampler));
+        dispatch!(monomorphize, [
+            (measurement.input_glue.domain_type, [
+                VectorDomain<AllDomain<u32>>, VectorDomain<AllDomain<u64>>, VectorDomain<AllDomain<i32>>, VectorDomain<AllDomain<i64>>, VectorDomain<AllDomain<f32>>,
+                VectorDomain<AllDomain<f64>>, VectorDomain<AllDomain<bool>>, VectorDomain<AllDomain<String>>, VectorDomain<AllDomain<u8>>,
+                VectorDomain<IntervalDomain<u32>>, VectorDomain<IntervalDomain<u64>>, VectorDomain<IntervalDomain<i32>>, VectorDomain<IntervalDomain<i64>>,
+                VectorDomain<IntervalDomain<f32>>, VectorDomain<IntervalDomain<f64>>, VectorDomain<IntervalDomain<u8>>]),
+            (measurement.output_glue.measure_type, [MaxDivergence, SmoothedMaxDivergence])
+        ], (measurement, sampler, ast))
+    })
 }
 
+fn no_ast() -> Error {
+    Error::new(ErrorKind::Ffi, "Not made by an FFI constructor, so there is no AST")
+}
+
+/// Returns the AST of a measurement as JSON, describing the constructors and combinators that made it.
+///
+/// # Arguments
+/// * `this` - The measurement.
 #[no_mangle]
-pub extern "C" fn opendp_core__transformation_invoke(this: *const FfiTransformation, arg: *const FfiObject) -> *mut FfiObject {
-    let this = util::as_ref(this);
-    let arg = util::as_ref(arg);
-    assert_eq!(arg.type_, this.input_glue.domain_carrier);
-    let res_type = this.output_glue.domain_carrier.clone();
-    let res = this.value.function.eval_ffi(&arg.value);
-    FfiObject::new_typed(res_type, res)
+pub extern "C" fn opendp_core__measurement_to_ast(this: *const FfiMeasurement) -> FfiResult<*mut c_char> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let ast = this.ast.as_ref().ok_or_else(no_ast)?;
+        Ok(util::into_c_char_p(ast.to_json()))
+    })
 }
 
+/// Reconstructs a measurement from the JSON of its AST, as returned by `opendp_core__measurement_to_ast()`.
+///
+/// # Arguments
+/// * `ast` - The AST, as JSON.
 #[no_mangle]
-pub extern "C" fn opendp_core__transformation_free(this: *mut FfiTransformation) {
-    util::into_owned(this);
+pub extern "C" fn opendp_core__measurement_from_ast(ast: *const c_char) -> FfiResult<*mut FfiMeasurement> {
+    util::ffi_result(|| {
+        let ast = Ast::from_json(util::to_str(ast)?)?;
+        ast::build(&ast)?.into_measurement()
+    })
 }
 
+/// Returns the AST of a transformation as JSON, describing the constructors and combinators that made it.
+///
+/// # Arguments
+/// * `this` - The transformation.
 #[no_mangle]
-pub extern "C" fn opendp_core__make_chain_mt(measurement1: *mut FfiMeasurement, transformation0: *mut FfiTransformation) -> *mut FfiMeasurement {
-    let transformation0 = util::as_ref(transformation0);
-    let measurement1 = util::as_ref(measurement1);
-    assert_eq!(transformation0.output_glue.domain_type, measurement1.input_glue.domain_type);
-    let input_glue = transformation0.input_glue.clone();
-    let x_glue = transformation0.output_glue.clone();
-    let output_glue = measurement1.output_glue.clone();
-    let measurement = core::make_chain_mt_glue(&measurement1.value, &transformation0.value, &input_glue.metric_glue, &x_glue.metric_glue, &output_glue.measure_glue);
-    FfiMeasurement::new(input_glue, output_glue, measurement)
+pub extern "C" fn opendp_core__transformation_to_ast(this: *const FfiTransformation) -> FfiResult<*mut c_char> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let ast = this.ast.as_ref().ok_or_else(no_ast)?;
+        Ok(util::into_c_char_p(ast.to_json()))
+    })
 }
 
+/// Reconstructs a transformation from the JSON of its AST, as returned by `opendp_core__transformation_to_ast()`.
+///
+/// # Arguments
+/// * `ast` - The AST, as JSON.
 #[no_mangle]
-pub extern "C" fn opendp_core__make_chain_tt(transformation1: *mut FfiTransformation, transformation0: *mut FfiTransformation) -> *mut FfiTransformation {
-    let transformation0 = util::as_ref(transformation0);
-    let transformation1 = util::as_ref(transformation1);
-    assert_eq!(transformation0.output_glue.domain_type, transformation1.input_glue.domain_type);
-    let input_glue = transformation0.input_glue.clone();
-    let x_glue = transformation0.output_glue.clone();
-    let output_glue = transformation1.output_glue.clone();
-    let transformation = core::make_chain_tt_glue(&transformation1.value, &transformation0.value, &input_glue.metric_glue, &x_glue.metric_glue, &output_glue.metric_glue);
-    FfiTransformation::new(input_glue, output_glue, transformation)
+pub extern "C" fn opendp_core__transformation_from_ast(ast: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let ast = Ast::from_json(util::to_str(ast)?)?;
+        ast::build(&ast)?.into_transformation()
+    })
 }
 
+/// Frees an error returned in an `FfiResult`.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn opendp_core__make_composition(measurement0: *mut FfiMeasurement, measurement1: *mut FfiMeasurement) -> *mut FfiMeasurement {
-    let measurement0 = util::as_ref(measurement0);
-    let measurement1 = util::as_ref(measurement1);
-    assert_eq!(measurement0.input_glue.domain_type, measurement1.input_glue.domain_type);
-    let input_glue = measurement0.input_glue.clone();
-    let output_glue0 = measurement0.output_glue.clone();
-    let output_glue1 = measurement1.output_glue.clone();
-    // TODO: output_glue for composition.
-    let output_glue_domain_type = Type::new::<FfiDomain>();
-    let output_glue_domain_carrier = Type::new_box_pair(&output_glue0.domain_carrier, &output_glue1.domain_carrier);
-    let output_glue_measure_glue = output_glue0.measure_glue.clone();
-    let output_glue = FfiMeasureGlue::<FfiDomain, FfiMeasure>::new_explicit(output_glue_domain_type, output_glue_domain_carrier, output_glue_measure_glue);
-    let measurement = core::make_composition_glue(&measurement0.value, &measurement1.value, &input_glue.metric_glue, &output_glue0.measure_glue, &output_glue1.measure_glue);
-    FfiMeasurement::new(input_glue, output_glue, measurement)
+pub extern "C" fn opendp_core__error_free(this: *mut FfiError) {
+    if this.is_null() { return }
+    let this = unsafe { Box::from_raw(this) };
+    unsafe {
+        drop(CString::from_raw(this.variant));
+        drop(CString::from_raw(this.message));
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn opendp_core__bootstrap() -> *const c_char {
-    let spec =
-r#"{
-"functions": [
-    { "name": "measurement_invoke", "args": [ ["const void *", "this"], ["void *", "arg"] ], "ret": "void *" },
-    { "name": "measurement_free", "args": [ ["void *", "this"] ] },
-    { "name": "transformation_invoke", "args": [ ["const void *", "this"], ["void *", "arg"] ], "ret": "void *" },
-    { "name": "transformation_free", "args": [ ["void *", "this"] ] },
-    { "name": "make_chain_mt", "args": [ ["void *", "measurement"], ["void *", "transformation"] ], "ret": "void *" },
-    { "name": "make_chain_tt", "args": [ ["void *", "transformation1"], ["void *", "transformation0"] ], "ret": "void *" },
-    { "name": "make_composition", "args": [ ["void *", "transformation0"], ["void *", "transformation1"] ], "ret": "void *" }
-]
-}"#;
-    util::bootstrap(spec)
+pub extern "C" fn opendp_core__bootstrap() -> *mut c_char {
+    util::bootstrap(include_str!(concat!(env!("OUT_DIR"), "/core.json")))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    struct DropFlag(Rc<Cell<bool>>);
+    impl Drop for DropFlag {
+        fn drop(&mut self) { self.0.set(true) }
+    }
+
+    #[test]
+    fn test_ffi_object_type_check() {
+        let object = util::into_owned(FfiObject::new(42_i32)).unwrap();
+        assert_eq!(object.as_ref::<i32>(), Ok(&42));
+        assert_eq!(object.as_ref::<f64>().unwrap_err().kind, ErrorKind::TypeMismatch);
+        assert_eq!(object.into_owned::<i32>(), Ok(42));
+    }
+
+    fn check(result: FfiResult<c_bool>) -> Result<bool, ErrorKind> {
+        result.into_result().map(util::to_bool).map_err(|e| e.kind)
+    }
+
+    #[test]
+    fn test_measurement_check() {
+        let measurement = FfiMeasurement::new_from_types(opendp::meas::make_base_laplace::<f64>(2.0));
+        let (d_in, d_out, small_d_out, bad_d_in) = (FfiObject::new(1.0_f64), FfiObject::new(0.5_f64), FfiObject::new(0.25_f64), FfiObject::new(1_i32));
+        assert_eq!(check(opendp_core__measurement_check(measurement, d_in, d_out)), Ok(true));
+        assert_eq!(check(opendp_core__measurement_check(measurement, d_in, small_d_out)), Ok(false));
+        assert_eq!(check(opendp_core__measurement_check(measurement, bad_d_in, d_out)), Err(ErrorKind::TypeMismatch));
+        for object in [d_in, d_out, small_d_out, bad_d_in].iter() {
+            util::into_owned(*object).unwrap();
+        }
+        util::into_owned(measurement).unwrap();
+    }
+
+    #[test]
+    fn test_transformation_check() {
+        let transformation = FfiTransformation::new_from_types(opendp::trans::make_count_l1::<i32>());
+        let (d_in, d_out) = (FfiObject::new(2_i32), FfiObject::new(1_i32));
+        assert_eq!(check(opendp_core__transformation_check(transformation, d_in, d_in)), Ok(true));
+        assert_eq!(check(opendp_core__transformation_check(transformation, d_in, d_out)), Ok(false));
+        util::into_owned(d_in).unwrap();
+        util::into_owned(d_out).unwrap();
+        util::into_owned(transformation).unwrap();
+    }
+
+    #[test]
+    fn test_chain_check_not_implemented() {
+        let measurement1 = FfiMeasurement::new_from_types(opendp::meas::make_base_laplace::<f64>(1.0));
+        let transformation0 = FfiTransformation::new_from_types(opendp::trans::make_bounded_sum_l1::<f64>(0.0, 10.0));
+        let chain = opendp_core__make_chain_mt(measurement1, transformation0).into_result().unwrap();
+        let (d_in, d_out) = (FfiObject::new(1_i32), FfiObject::new(20.0_f64));
+        assert_eq!(check(opendp_core__measurement_check(chain, d_in, d_out)), Err(ErrorKind::NotImplemented));
+        util::into_owned(d_in).unwrap();
+        util::into_owned(d_out).unwrap();
+        for measurement in [measurement1, chain].iter() {
+            util::into_owned(*measurement).unwrap();
+        }
+        util::into_owned(transformation0).unwrap();
+    }
+
+    #[test]
+    fn test_chain_metric_mismatch() {
+        // The parsed vector has the domain the filter takes, but under HammingDistance rather than SymmetricDistance.
+        let parse = FfiTransformation::new_from_types(opendp::trans::make_parse_series::<i32>(false));
+        let filter = FfiTransformation::new_from_types(opendp::trans::make_filter::<i32>(opendp::trans::Predicate::Ge(0)));
+        assert_eq!(opendp_core__make_chain_tt(filter, parse).into_result().unwrap_err().kind, ErrorKind::TypeMismatch);
+        let convert = FfiTransformation::new_from_types(opendp::trans::make_hamming_to_symmetric(VectorDomain::<AllDomain<i32>>::new_all()));
+        let converted = opendp_core__make_chain_tt(convert, parse).into_result().unwrap();
+        let chain = opendp_core__make_chain_tt(filter, converted).into_result().unwrap();
+        for transformation in [parse, filter, convert, converted, chain].iter() {
+            util::into_owned(*transformation).unwrap();
+        }
+
+        let sum = FfiTransformation::new_from_types(opendp::trans::make_bounded_sum_l2::<f64>(0.0, 10.0));
+        let laplace = FfiMeasurement::new_from_types(opendp::meas::make_base_laplace::<f64>(1.0));
+        assert_eq!(opendp_core__make_chain_mt(laplace, sum).into_result().unwrap_err().kind, ErrorKind::TypeMismatch);
+        let gaussian = FfiMeasurement::new_from_types(opendp::meas::make_base_gaussian::<f64>(1.0));
+        assert_eq!(opendp_core__make_composition(laplace, gaussian).into_result().unwrap_err().kind, ErrorKind::TypeMismatch);
+        util::into_owned(sum).unwrap();
+        for measurement in [laplace, gaussian].iter() {
+            util::into_owned(*measurement).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_subsample() {
+        let quantile = FfiMeasurement::new_from_types(opendp::meas::make_private_quantile::<f64>(0.0, 10.0, vec![1.0, 5.0, 9.0], 0.5, 1.0).unwrap());
+        let sampler = CString::new(r#"{ "fixed_size": { "population_size": 100, "sample_size": 10 } }"#).unwrap();
+        let subsample = opendp_core__make_subsample(quantile, sampler.as_ptr()).into_result().unwrap();
+        let arg = FfiObject::new(vec![5.0_f64; 100]);
+        let res = opendp_core__measurement_invoke(subsample, arg).into_result().unwrap();
+        let res: f64 = util::into_owned(res).unwrap().into_owned().unwrap();
+        assert!([1.0, 5.0, 9.0].contains(&res));
+        util::into_owned(arg).unwrap();
+
+        // The measurement must take a vector, with the metric of the sampler.
+        let laplace = FfiMeasurement::new_from_types(opendp::meas::make_base_laplace::<f64>(1.0));
+        assert_eq!(opendp_core__make_subsample(laplace, sampler.as_ptr()).into_result().unwrap_err().kind, ErrorKind::UnsupportedType);
+        let sampler = CString::new(r#"{ "poisson": { "rate": 0.1 } }"#).unwrap();
+        assert_eq!(opendp_core__make_subsample(quantile, sampler.as_ptr()).into_result().unwrap_err().kind, ErrorKind::TypeMismatch);
+        let sampler = CString::new(r#"{ "fixed_size": { "population_size": 10, "sample_size": 100 } }"#).unwrap();
+        assert_eq!(opendp_core__make_subsample(quantile, sampler.as_ptr()).into_result().unwrap_err().kind, ErrorKind::InvalidArgument);
+        let sampler = CString::new(r#"{ "bogus": {} }"#).unwrap();
+        assert_eq!(opendp_core__make_subsample(quantile, sampler.as_ptr()).into_result().unwrap_err().kind, ErrorKind::FailedParse);
+        for measurement in [quantile, subsample, laplace].iter() {
+            util::into_owned(*measurement).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_typed_function() {
+        let count = FfiTransformation::new_from_types(opendp::trans::make_count_l1::<f64>());
+        let function = util::as_ref(count).unwrap().typed_function::<VectorDomain<AllDomain<f64>>, AllDomain<u32>>().unwrap();
+        assert_eq!(function.eval(&vec![1.0, 2.0]), 2);
+        let error = util::as_ref(count).unwrap().typed_function::<VectorDomain<AllDomain<f64>>, AllDomain<f64>>().err().unwrap();
+        assert_eq!(error.kind, ErrorKind::TypeMismatch);
+        util::into_owned(count).unwrap();
+    }
+
+    fn describe(result: FfiResult<*mut c_char>) -> serde_json::Value {
+        match result {
+            FfiResult::Ok(res) => serde_json::from_str(&util::into_string(res).unwrap()).unwrap(),
+            FfiResult::Err(_) => panic!("describe failed"),
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        let transformation = FfiTransformation::new_from_types(opendp::trans::make_bounded_sum_l1::<f64>(0.0, 10.0));
+        let description = describe(opendp_core__transformation_describe(transformation));
+        assert_eq!(description["input_domain"]["element_domain"]["upper"], json!({ "included": 10.0 }));
+        assert_eq!(description["output_domain"], json!({ "type": "AllDomain", "carrier": "f64" }));
+        assert_eq!(description["input_metric"]["type"], "HammingDistance");
+        assert_eq!(description["output_metric"], json!({ "type": "L1Sensitivity", "distance": "f64" }));
+        util::into_owned(transformation).unwrap();
+
+        let measurement0 = FfiMeasurement::new_from_types(opendp::meas::make_base_laplace::<f64>(1.0));
+        let measurement1 = FfiMeasurement::new_from_types(opendp::meas::make_base_laplace::<f64>(2.0));
+        let composition = match opendp_core__make_composition(measurement0, measurement1) {
+            FfiResult::Ok(composition) => composition,
+            FfiResult::Err(_) => panic!("composition failed"),
+        };
+        let description = describe(opendp_core__measurement_describe(composition));
+        assert_eq!(description["output_domain"]["type"], "PairDomain");
+        assert_eq!(description["output_domain"]["element_domains"][1], json!({ "type": "AllDomain", "carrier": "f64" }));
+        assert_eq!(description["output_measure"]["type"], "MaxDivergence");
+        for measurement in [measurement0, measurement1, composition].iter() {
+            util::into_owned(*measurement).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_ffi_object_drop() {
+        let dropped = Rc::new(Cell::new(false));
+        let object = util::into_owned(FfiObject::new(DropFlag(dropped.clone()))).unwrap();
+        assert!(!dropped.get());
+        drop(object);
+        assert!(dropped.get());
+    }
 }
@@ -1,53 +1,344 @@
+use std::any::Any;
 use std::collections::HashMap;
-use std::os::raw::c_char;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::slice;
 
-use opendp::data::Data;
+use opendp::data::{Data, Form};
 
 use crate::core::FfiObject;
 use crate::util;
+use crate::util::{Error, ErrorKind, FfiResult, TypeArgs};
 
+/// A pointer to `len` elements, for passing data across FFI without going through strings.
+///
+/// The layout of the elements depends on the type of the data:
+/// * `T` (a number or `bool`): a single `T`, with `len` 1.
+/// * `(T, T)`: two contiguous `T`s, with `len` 2.
+/// * `String`: `len` bytes of UTF-8, not NUL-terminated.
+/// * `Vec<T>`: `len` contiguous `T`s.
+/// * `Vec<String>`: `len` NUL-terminated `char *`s.
+/// * `HashMap<String, Data>`: two pointers, to `len` NUL-terminated `char *` keys, and to `len` `FfiObject *` columns.
+#[repr(C)]
+pub struct FfiSlice {
+    pub ptr: *const c_void,
+    pub len: usize,
+}
+
+/// An [`FfiSlice`] that owns the buffer it points to. The slice comes first, so a pointer to this is a valid pointer to the slice.
+#[repr(C)]
+struct OwnedSlice {
+    slice: FfiSlice,
+    _buffer: Box<dyn Any>,
+}
+
+impl OwnedSlice {
+    /// `ptr` must point into `buffer`, which must not move its contents when moved itself (like a `Box` or `Vec`).
+    fn new(ptr: *const c_void, len: usize, buffer: impl Any) -> Self {
+        OwnedSlice { slice: FfiSlice { ptr, len }, _buffer: Box::new(buffer) }
+    }
+}
+
+fn as_slice<'a, T>(raw: *const c_void, len: usize) -> Result<&'a [T], Error> {
+    if len == 0 { return Ok(&[]) }
+    if raw.is_null() { return Err(Error::new(ErrorKind::Ffi, "Null pointer")) }
+    if !(raw as *const T).is_aligned() {
+        return Err(Error::new(ErrorKind::Ffi, format!("Pointer isn't aligned to {} bytes", std::mem::align_of::<T>())))
+    }
+    Ok(unsafe { slice::from_raw_parts(raw as *const T, len) })
+}
+
+/// The element types that cross FFI in a fixed-size representation, read as `Repr` and converted.
+trait Primitive: 'static + Copy {
+    type Repr: Copy;
+    fn from_repr(repr: Self::Repr) -> Self;
+}
+macro_rules! impl_native_primitive {
+    ($($ty:ty),+) => {
+        $(impl Primitive for $ty {
+            type Repr = Self;
+            fn from_repr(repr: Self) -> Self { repr }
+        })+
+    }
+}
+impl_native_primitive!(u8, u32, u64, i32, i64, f32, f64);
+// Foreign callers may pass any byte for a bool, and reading a byte other than 0 or 1 as a `bool` is undefined behavior.
+impl Primitive for bool {
+    type Repr = u8;
+    fn from_repr(repr: u8) -> Self { repr != 0 }
+}
+
+trait FromSlice: Sized {
+    fn from_slice(raw: *const c_void, len: usize) -> Result<Self, Error>;
+}
+
+impl<T: Primitive> FromSlice for T {
+    fn from_slice(raw: *const c_void, len: usize) -> Result<Self, Error> {
+        if len != 1 {
+            return Err(Error::new(ErrorKind::Ffi, format!("Expected a slice of length 1 for a scalar, got {}", len)))
+        }
+        Ok(T::from_repr(as_slice::<T::Repr>(raw, len)?[0]))
+    }
+}
+
+impl<T: Primitive> FromSlice for (T, T) {
+    fn from_slice(raw: *const c_void, len: usize) -> Result<Self, Error> {
+        if len != 2 {
+            return Err(Error::new(ErrorKind::Ffi, format!("Expected a slice of length 2 for a pair, got {}", len)))
+        }
+        let elements = as_slice::<T::Repr>(raw, len)?;
+        Ok((T::from_repr(elements[0]), T::from_repr(elements[1])))
+    }
+}
+
+impl FromSlice for String {
+    fn from_slice(raw: *const c_void, len: usize) -> Result<Self, Error> {
+        let bytes = as_slice::<u8>(raw, len)?.to_vec();
+        String::from_utf8(bytes).map_err(|_| Error::new(ErrorKind::Ffi, "Bad UTF-8 string"))
+    }
+}
+
+impl<T: Primitive> FromSlice for Vec<T> {
+    fn from_slice(raw: *const c_void, len: usize) -> Result<Self, Error> {
+        Ok(as_slice::<T::Repr>(raw, len)?.iter().map(|e| T::from_repr(*e)).collect())
+    }
+}
+
+impl FromSlice for Vec<String> {
+    fn from_slice(raw: *const c_void, len: usize) -> Result<Self, Error> {
+        as_slice::<*const c_char>(raw, len)?.iter().map(|e| util::to_str(*e).map(|e| e.to_owned())).collect()
+    }
+}
+
+fn object_to_data(object: &FfiObject) -> Result<Data, Error> {
+    fn monomorphize<T: 'static + Form + Clone>(object: &FfiObject) -> Result<Data, Error> {
+        Ok(Data::new(object.as_ref::<T>()?.clone()))
+    }
+    dispatch!(monomorphize, [(object.type_, [Vec<u8>, Vec<u32>, Vec<u64>, Vec<i32>, Vec<i64>, Vec<f32>, Vec<f64>, Vec<bool>, Vec<String>])], (object))
+}
+
+impl FromSlice for HashMap<String, Data> {
+    fn from_slice(raw: *const c_void, len: usize) -> Result<Self, Error> {
+        let parts = as_slice::<*const c_void>(raw, 2)?;
+        let keys = Vec::<String>::from_slice(parts[0], len)?;
+        let columns = as_slice::<*const FfiObject>(parts[1], len)?;
+        keys.into_iter().zip(columns).map(|(key, column)| Ok((key, object_to_data(util::as_ref(*column)?)?))).collect()
+    }
+}
+
+trait ToSlice {
+    fn to_slice(&self) -> Result<OwnedSlice, Error>;
+}
+
+impl<T: Primitive> ToSlice for T {
+    fn to_slice(&self) -> Result<OwnedSlice, Error> {
+        let buffer = Box::new(*self);
+        Ok(OwnedSlice::new(&*buffer as *const T as *const c_void, 1, buffer))
+    }
+}
+
+impl<T: Primitive> ToSlice for (T, T) {
+    fn to_slice(&self) -> Result<OwnedSlice, Error> {
+        let buffer = Box::new([self.0, self.1]);
+        Ok(OwnedSlice::new(buffer.as_ptr() as *const c_void, 2, buffer))
+    }
+}
+
+impl ToSlice for String {
+    fn to_slice(&self) -> Result<OwnedSlice, Error> {
+        let buffer = self.clone().into_bytes();
+        Ok(OwnedSlice::new(buffer.as_ptr() as *const c_void, buffer.len(), buffer))
+    }
+}
+
+impl<T: Primitive> ToSlice for Vec<T> {
+    fn to_slice(&self) -> Result<OwnedSlice, Error> {
+        let buffer = self.clone();
+        Ok(OwnedSlice::new(buffer.as_ptr() as *const c_void, buffer.len(), buffer))
+    }
+}
+
+impl ToSlice for Vec<String> {
+    fn to_slice(&self) -> Result<OwnedSlice, Error> {
+        let strings = self.iter().map(|e| CString::new(e.as_str())).collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::new(ErrorKind::Ffi, "String contains a NUL byte"))?;
+        let pointers: Vec<*const c_char> = strings.iter().map(|e| e.as_ptr()).collect();
+        Ok(OwnedSlice::new(pointers.as_ptr() as *const c_void, pointers.len(), (strings, pointers)))
+    }
+}
+
+fn data_to_object(data: &Data) -> Result<*mut FfiObject, Error> {
+    fn monomorphize<T: 'static + Form + Clone>(data: &Data) -> Option<*mut FfiObject> {
+        data.try_as_form::<T>().map(|e| FfiObject::new(e.clone()))
+    }
+    monomorphize::<Vec<u8>>(data)
+        .or_else(|| monomorphize::<Vec<u32>>(data))
+        .or_else(|| monomorphize::<Vec<u64>>(data))
+        .or_else(|| monomorphize::<Vec<i32>>(data))
+        .or_else(|| monomorphize::<Vec<i64>>(data))
+        .or_else(|| monomorphize::<Vec<f32>>(data))
+        .or_else(|| monomorphize::<Vec<f64>>(data))
+        .or_else(|| monomorphize::<Vec<bool>>(data))
+        .or_else(|| monomorphize::<Vec<String>>(data))
+        .ok_or_else(|| Error::new(ErrorKind::UnsupportedType, format!("No match for column {:?}", data)))
+}
+
+/// The columns of a dataframe slice, which are freed along with it.
+struct Columns(Vec<*mut FfiObject>);
+
+impl Drop for Columns {
+    fn drop(&mut self) {
+        for column in self.0.drain(..) {
+            drop(util::into_owned(column));
+        }
+    }
+}
+
+impl ToSlice for HashMap<String, Data> {
+    fn to_slice(&self) -> Result<OwnedSlice, Error> {
+        let (keys, columns): (Vec<String>, Vec<&Data>) = self.iter().map(|(k, v)| (k.clone(), v)).unzip();
+        let keys = keys.to_slice()?;
+        let columns = Columns(columns.into_iter().map(data_to_object).collect::<Result<_, _>>()?);
+        let parts = Box::new([keys.slice.ptr, columns.0.as_ptr() as *const c_void]);
+        Ok(OwnedSlice::new(parts.as_ptr() as *const c_void, self.len(), (keys, columns, parts)))
+    }
+}
+
+/// Constructs an object of type `type_args` from the elements at `raw`, laid out as described in [`FfiSlice`].
+/// The elements are copied, so the caller keeps ownership of `raw`.
+///
+/// # Arguments
+/// * `type_args` - The type of the object, like `<Vec<f64>>`.
+/// * `raw` - A pointer to the elements.
+/// * `len` - The number of elements.
 #[no_mangle]
-pub extern "C" fn opendp_data__from_string(p: *const c_char) -> *mut FfiObject {
-    let s = util::to_str(p).to_owned();
-    FfiObject::new(s)
+pub extern "C" fn opendp_data__slice_as_object(type_args: *const c_char, raw: *const c_void, len: usize) -> FfiResult<*mut FfiObject> {
+    fn monomorphize<T: 'static + FromSlice>(raw: *const c_void, len: usize) -> Result<*mut FfiObject, Error> {
+        Ok(FfiObject::new(T::from_slice(raw, len)?))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        dispatch!(monomorphize, [(type_args.0[0], [
+            u8, u32, u64, i32, i64, f32, f64, bool, String, (f32, f32), (f64, f64),
+            Vec<u8>, Vec<u32>, Vec<u64>, Vec<i32>, Vec<i64>, Vec<f32>, Vec<f64>, Vec<bool>, Vec<String>,
+            HashMap<String, Data>
+        ])], (raw, len))
+    })
 }
 
+/// Returns the elements of `this`, laid out as described in [`FfiSlice`].
+/// The elements are copied into a new slice, which the caller must free with `opendp_data__slice_free()`.
 #[no_mangle]
-pub extern "C" fn opendp_data__to_string(this: *const FfiObject) -> *const c_char {
-    fn monomorphize<T: std::fmt::Debug>(this: &FfiObject) -> *const c_char {
-        let this = this.as_ref::<T>();
-        // FIXME: Figure out how to implement general to_string().
-        let string = format!("{:?}", this);
-        // FIXME: Leaks string.
-        util::into_c_char_p(string)
+pub extern "C" fn opendp_data__object_as_slice(this: *const FfiObject) -> FfiResult<*mut FfiSlice> {
+    fn monomorphize<T: 'static + ToSlice>(this: &FfiObject) -> Result<*mut FfiSlice, Error> {
+        let slice = this.as_ref::<T>()?.to_slice()?;
+        Ok(util::into_raw(slice) as *mut FfiSlice)
     }
-    let this = util::as_ref(this);
-    let type_arg = &this.type_;
-    dispatch!(monomorphize, [(type_arg, [
-        u32, u64, i32, i64, f32, f64, bool, String, u8, Data,
-        Vec<u32>, Vec<u64>, Vec<i32>, Vec<i64>, Vec<f32>, Vec<f64>, Vec<bool>, Vec<String>, Vec<u8>, Vec<Data>, Vec<Vec<String>>,
-        HashMap<String, Data>,
-        // FIXME: The following are for Python demo use of compositions. Need to figure this out!!!
-        (Box<i32>, Box<f64>),
-        (Box<i32>, Box<u32>),
-        (Box<(Box<f64>, Box<f64>)>, Box<f64>)
-    ])], (this))
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        dispatch!(monomorphize, [(this.type_, [
+            u8, u32, u64, i32, i64, f32, f64, bool, String, (f32, f32), (f64, f64),
+            Vec<u8>, Vec<u32>, Vec<u64>, Vec<i32>, Vec<i64>, Vec<f32>, Vec<f64>, Vec<bool>, Vec<String>,
+            HashMap<String, Data>
+        ])], (this))
+    })
+}
+
+/// Frees a slice returned by `opendp_data__object_as_slice()`, along with its elements.
+#[no_mangle]
+pub extern "C" fn opendp_data__slice_free(this: *mut FfiSlice) -> FfiResult<*mut ()> {
+    util::ffi_result(|| {
+        util::into_owned(this as *mut OwnedSlice)?;
+        Ok(util::ffi_unit())
+    })
 }
 
+/// Constructs a `String` object.
 #[no_mangle]
-pub extern "C" fn opendp_data__data_free(this: *mut FfiObject) {
-    util::into_owned(this);
+pub extern "C" fn opendp_data__from_string(p: *const c_char) -> FfiResult<*mut FfiObject> {
+    util::ffi_result(|| {
+        let s = util::to_str(p)?.to_owned();
+        Ok(FfiObject::new(s))
+    })
 }
 
+/// Returns a debug representation of an object.
 #[no_mangle]
-pub extern "C" fn opendp_data__bootstrap() -> *const c_char {
-    let spec =
-r#"{
-"functions": [
-    { "name": "from_string", "args": [ ["const char *", "s"] ], "ret": "void *" },
-    { "name": "to_string", "args": [ ["void *", "this"] ], "ret": "const char *" },
-    { "name": "data_free", "args": [ ["void *", "this"] ] }
-]
-}"#;
-    util::bootstrap(spec)
+pub extern "C" fn opendp_data__to_string(this: *const FfiObject) -> FfiResult<*mut c_char> {
+    util::ffi_result(|| {
+        let this = util::as_ref(this)?;
+        let string = unsafe { this.type_.debug_value(this.as_erased()) }
+            .ok_or_else(|| Error::new(ErrorKind::UnsupportedType, format!("No debug representation for {}", this.type_.descriptor)))?;
+        Ok(util::into_c_char_p(string))
+    })
+}
+
+/// Frees a string returned by the library, like the result of `opendp_data__to_string()`.
+#[no_mangle]
+pub extern "C" fn opendp_data__str_free(this: *mut c_char) -> FfiResult<*mut ()> {
+    util::ffi_result(|| {
+        util::into_string(this)?;
+        Ok(util::ffi_unit())
+    })
+}
+
+/// Frees an object.
+#[no_mangle]
+pub extern "C" fn opendp_data__data_free(this: *mut FfiObject) -> FfiResult<*mut ()> {
+    util::ffi_result(|| {
+        util::into_owned(this)?;
+        Ok(util::ffi_unit())
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn opendp_data__bootstrap() -> *mut c_char {
+    util::bootstrap(include_str!(concat!(env!("OUT_DIR"), "/data.json")))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: 'static + FromSlice + ToSlice>(value: &T) -> T {
+        let slice = value.to_slice().unwrap();
+        T::from_slice(slice.slice.ptr, slice.slice.len).unwrap()
+    }
+
+    #[test]
+    fn test_slice_round_trip() {
+        assert_eq!(round_trip(&1.5_f64), 1.5);
+        assert_eq!(round_trip(&"hello".to_owned()), "hello");
+        assert_eq!(round_trip(&(0.5_f64, 1e-6_f64)), (0.5, 1e-6));
+        assert_eq!(round_trip(&vec![1_i32, 2, 3]), vec![1, 2, 3]);
+        assert_eq!(round_trip(&vec!["a".to_owned(), "bc".to_owned()]), vec!["a".to_owned(), "bc".to_owned()]);
+        let dataframe: HashMap<String, Data> = vec![
+            ("a".to_owned(), Data::new(vec![1_i64, 2])),
+            ("b".to_owned(), Data::new(vec!["x".to_owned(), "y".to_owned()])),
+        ].into_iter().collect();
+        assert_eq!(round_trip(&dataframe), dataframe);
+    }
+
+    #[test]
+    fn test_slice_bool_bytes() {
+        let bytes = [0_u8, 1, 2, 255];
+        assert!(bool::from_slice(&bytes[2] as *const u8 as *const c_void, 1).unwrap());
+        assert_eq!(Vec::<bool>::from_slice(bytes.as_ptr() as *const c_void, 4).unwrap(), vec![false, true, true, true]);
+        assert_eq!(round_trip(&vec![true, false]), vec![true, false]);
+    }
+
+    #[test]
+    fn test_slice_misaligned() {
+        let values = [0_u64; 2];
+        let raw = (values.as_ptr() as *const u8).wrapping_add(1) as *const c_void;
+        assert_eq!(u64::from_slice(raw, 1).unwrap_err().kind, ErrorKind::Ffi);
+    }
+
+    #[test]
+    fn test_slice_as_object_bad_length() {
+        let value = 1_i32;
+        let result = i32::from_slice(&value as *const i32 as *const c_void, 2);
+        assert_eq!(result.unwrap_err().kind, ErrorKind::Ffi);
+    }
 }
@@ -8,9 +8,7 @@ dispatch!(func, [(rt_type1, [u32, u64])], (arg1, arg2))
 match rt_type1.descriptor().as_str() {
     stringify!( u32 ) => func::<u32>(arg1, arg2),
     stringify!( u64 ) => func::<u64>(arg1, arg2),
-    rt_typeector => {
-        ::std::rt::begin_panic_fmt(&{})
-    }
+    rt_type => Err(Error::new(ErrorKind::UnsupportedType, ...))
 }
 
 TWO TYPE ARGS:
@@ -20,23 +18,19 @@ match rt_type1.descriptor().as_str() {
     stringify!( u32 ) => match rt_type1.descriptor().as_str() {
         stringify!( i32 ) => func::<u32, i32>(arg1, arg2),
         stringify!( i64 ) => func::<u32, i64>(arg1, arg2),
-        rt_type => {
-            ::std::rt::begin_panic_fmt(&{})
-        }
+        rt_type => Err(Error::new(ErrorKind::UnsupportedType, ...))
     },
     stringify!( u64 ) => match rt_type1.descriptor().as_str() {
         stringify!( i32 ) => func::<u64, i32>(arg1, arg2),
         stringify!( i64 ) => func::<u64, i64>(arg1, arg2),
-        rt_type => {
-            ::std::rt::begin_panic_fmt(&{})
-        }
+        rt_type => Err(Error::new(ErrorKind::UnsupportedType, ...))
     },
-    rt_type => {
-        ::std::rt::begin_panic_fmt(&{})
-    }
+    rt_type => Err(Error::new(ErrorKind::UnsupportedType, ...))
 }
 
 AND SO ON...
+
+The dispatched function must return a Result<_, util::Error>, so that an unsupported type is returned as an error.
 */
 // dispatch!(func, [(rt_type1, [u32, u64]), (rt_type2, [i32, i64]), (rt_type3, [f32, f64])], (arg1, arg2))
 macro_rules! dispatch {
@@ -73,10 +67,13 @@ macro_rules! disp_expand {
     ($function:ident, ($rt_type:expr, @numbers),                 $rt_dispatch_types:tt, $type_args:tt, $args:tt) => {
         disp_expand!($function, ($rt_type, [u32, u64, i32, i64, f32, f64, u8]), $rt_dispatch_types, $type_args, $args)
     };
+    ($function:ident, ($rt_type:expr, @nullables),               $rt_dispatch_types:tt, $type_args:tt, $args:tt) => {
+        disp_expand!($function, ($rt_type, [Option<u32>, Option<u64>, Option<i32>, Option<i64>, Option<f32>, Option<f64>, Option<bool>, Option<String>, Option<u8>, f32, f64]), $rt_dispatch_types, $type_args, $args)
+    };
     ($function:ident, ($rt_type:expr, [$($dispatch_type:ty),+]), $rt_dispatch_types:tt, $type_args:tt, $args:tt) => {
-        match $rt_type.id {
-            $(x if x == std::any::TypeId::of::<$dispatch_type>() => disp_1!($function, $rt_dispatch_types, $type_args, $dispatch_type, $args)),+,
-            _ => panic!("No match for concrete type {:?}/{}", $rt_type.id, $rt_type.descriptor)
+        match $rt_type.descriptor {
+            $(x if x == $crate::util::Type::new::<$dispatch_type>().descriptor => disp_1!($function, $rt_dispatch_types, $type_args, $dispatch_type, $args)),+,
+            _ => Err($crate::util::Error::new($crate::util::ErrorKind::UnsupportedType, format!("No match for concrete type {}", $rt_type.descriptor)))
         }
     };
 }
@@ -63,6 +63,14 @@
 //!
 //! In order
 //!
+//! ## Type Descriptors
+//!
+//! Type arguments are passed as strings like `"<Vec<i32>, (f64, f64)>"`, using Rust syntax, and parsed recursively into a `TypeDescriptor`.
+//! Each descriptor is looked up in a registry of `Type`s, which are interned by their canonical descriptor (the Rust type, without module paths).
+//! Any type constructed in Rust with `Type::new()` is registered on first use, and the common primitive and collection types are registered up front.
+//! The pairs returned by compositions, like `(Box<i32>, Box<(Box<f64>, Box<f64>)>)`, are assembled from their parts at runtime, so they nest
+//! to any depth without being listed anywhere.
+//!
 //! # Combinators
 //!
 //! The dispatch pattern works well when the Cartesian product of all possible generic type parameters is relatively small. But if there is a large
@@ -73,6 +81,20 @@
 //! ## Glue Structs
 //! ##
 //!
+//! # ASTs
+//!
+//! Every transformation and measurement made by the FFI constructors and combinators records how it was made, as an [`ast::Ast`].
+//! `opendp_core__transformation_to_ast()` returns the AST as JSON, and `opendp_core__transformation_from_ast()` rebuilds the same
+//! transformation from that JSON in a single call (likewise for measurements). This lets clients store a pipeline, or send it elsewhere to be rebuilt.
+//!
+//! # Error Handling
+//!
+//! Panics must not unwind across the C boundary, so every fallible FFI function returns an `FfiResult<T>`:
+//! a C tagged union holding either the result, or an `FfiError` with the kind of error (`variant`) and a `message`.
+//! The body of each function runs inside `util::ffi_result()`, which also catches any panic and returns it as an error
+//! of kind `Panic`. The caller owns the error, and must free it with `opendp_core__error_free()`.
+//! In the bootstrap metadata, these functions have a return type like `"FfiResult<void *>"`.
+//!
 //! # Memory Management
 //!
 //! # Bootstrap Metadata
@@ -82,7 +104,17 @@
 //! the module `opendp_ffi::core` has a function `core::opendp_core__bootstrap`. This function returns a `*const c_char` containing a JSON object
 //! specifying the FFI entry points of the module.
 //!
-//! // TODO: Show JSON example.
+//! The metadata is generated by the build script from the signatures and doc comments of the `#[no_mangle]` functions, so it can't drift
+//! from the code. Each function lists its C argument and return types, the number of type arguments it parses (or `"variable"`, if that
+//! depends on the other arguments), and any docs from an `# Arguments` section in its doc comment:
+//! ```json
+//! { "functions": [
+//!     { "name": "make_clamp", "doc": "Constructs a transformation that clamps the elements of a vector.", "type_args": 1,
+//!       "args": [ ["const char *", "type_args", "The type of the elements, like `<f64>`."], ["const void *", "lower", "..."], ["const void *", "upper", "..."] ],
+//!       "ret": "FfiResult<void *>" }
+//! ] }
+//! ```
+//! The build script also generates a C header declaring every function, which is available as [`C_HEADER`].
 
 #[macro_use]
 extern crate lazy_static;
@@ -90,8 +122,237 @@ extern crate lazy_static;
 #[macro_use]
 mod dispatch;
 
-mod core;
-mod data;
-mod meas;
-mod trans;
-mod util;
+// The modules are public so that native bindings (like `opendp-py`) can build on the erased types, rather than going through C.
+pub mod ast;
+pub mod core;
+pub mod data;
+pub mod meas;
+pub mod trans;
+pub mod util;
+
+/// The C header declaring the FFI entry points, generated along with the bootstrap metadata.
+pub const C_HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/opendp_ffi.h"));
+
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::collections::BTreeMap;
+    use std::ffi::CString;
+    use std::os::raw::{c_char, c_void};
+    use std::ptr;
+
+    use serde_json::Value;
+
+    use crate::core::{FfiObject, FfiTransformation};
+    use crate::util::FfiResult;
+
+    use super::*;
+
+    /// An allocator that counts the allocations live on each thread, so that tests running in parallel don't disturb each other.
+    struct CountingAllocator;
+
+    thread_local! {
+        static LIVE_ALLOCATIONS: Cell<isize> = const { Cell::new(0) };
+    }
+
+    fn count_allocation(delta: isize) {
+        let _ = LIVE_ALLOCATIONS.try_with(|live| live.set(live.get() + delta));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            count_allocation(1);
+            System.alloc(layout)
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            count_allocation(-1);
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Runs `f` twice, and returns the number of allocations left live by the second run.
+    /// The first run registers the types that `f` uses, which are kept for the life of the process.
+    fn leaked_allocations(f: impl Fn()) -> isize {
+        f();
+        let before = LIVE_ALLOCATIONS.with(Cell::get);
+        f();
+        LIVE_ALLOCATIONS.with(Cell::get) - before
+    }
+
+    fn ok<T>(result: FfiResult<T>) -> T {
+        match result {
+            FfiResult::Ok(ok) => ok,
+            FfiResult::Err(error) => panic!("{}", util::to_str(util::as_ref(error).unwrap().message).unwrap()),
+        }
+    }
+
+    fn as_object<T: Copy>(type_args: &str, values: &[T]) -> *mut FfiObject {
+        let type_args = CString::new(type_args).unwrap();
+        ok(data::opendp_data__slice_as_object(type_args.as_ptr(), values.as_ptr() as *const c_void, values.len()))
+    }
+
+    fn as_ptr<T>(value: &T) -> *const c_void {
+        value as *const T as *const c_void
+    }
+
+    /// Finds the exported functions in a module's source, along with their number of arguments.
+    fn exported_functions(source: &str) -> BTreeMap<String, usize> {
+        source.split("#[no_mangle]\npub extern \"C\" fn ").skip(1).map(|rest| {
+            let name = &rest[..rest.find('(').unwrap()];
+            let args = &rest[name.len() + 1..rest.find(')').unwrap()];
+            (name.to_owned(), args.matches(": ").count())
+        }).collect()
+    }
+
+    fn spec(bootstrap: extern "C" fn() -> *mut c_char) -> Value {
+        serde_json::from_str(&util::into_string(bootstrap()).unwrap()).unwrap()
+    }
+
+    fn spec_functions(module: &str, bootstrap: extern "C" fn() -> *mut c_char) -> BTreeMap<String, usize> {
+        let spec = spec(bootstrap);
+        spec["functions"].as_array().unwrap().iter().map(|function| {
+            let name = format!("opendp_{}__{}", module, function["name"].as_str().unwrap());
+            (name, function["args"].as_array().unwrap().len())
+        }).collect()
+    }
+
+    #[test]
+    fn test_bootstrap_covers_exports() {
+        let modules: [(&str, &str, extern "C" fn() -> *mut c_char); 4] = [
+            ("core", include_str!("core.rs"), core::opendp_core__bootstrap),
+            ("data", include_str!("data.rs"), data::opendp_data__bootstrap),
+            ("meas", include_str!("meas.rs"), meas::opendp_meas__bootstrap),
+            ("trans", include_str!("trans.rs"), trans::opendp_trans__bootstrap),
+        ];
+        for (module, source, bootstrap) in modules.iter() {
+            let mut exported = exported_functions(source);
+            let bootstrap_name = format!("opendp_{}__bootstrap", module);
+            assert_eq!(exported.remove(&bootstrap_name), Some(0));
+            assert_eq!(spec_functions(module, *bootstrap), exported);
+            for name in exported.keys().chain(Some(&bootstrap_name)) {
+                assert!(C_HEADER.contains(&format!("{}(", name)), "{} is missing from the header", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bootstrap_type_args() {
+        let spec = spec(trans::opendp_trans__bootstrap);
+        let find = |name: &str| spec["functions"].as_array().unwrap().iter().find(|function| function["name"] == name).unwrap().clone();
+        assert_eq!(find("make_identity")["type_args"], 1);
+        assert_eq!(find("make_identity")["args"][0][1], "type_args");
+        assert_eq!(find("make_cast_default")["type_args"], 2);
+        assert_eq!(find("make_parse_jsonl")["type_args"], "variable");
+        assert_eq!(find("make_split_lines").get("type_args"), None);
+    }
+
+    #[test]
+    fn test_bootstrap_free_functions() {
+        let core = spec(core::opendp_core__bootstrap);
+        let data = spec(data::opendp_data__bootstrap);
+        let find = |spec: &Value, name: &str| spec["functions"].as_array().unwrap().iter().find(|function| function["name"] == name).unwrap().clone();
+        assert_eq!(find(&core, "make_chain_tt")["free"], "opendp_core__transformation_free");
+        assert_eq!(find(&core, "measurement_describe")["free"], "opendp_data__str_free");
+        assert_eq!(find(&data, "object_as_slice")["free"], "opendp_data__slice_free");
+        assert_eq!(find(&data, "from_string")["free"], "opendp_data__data_free");
+        assert_eq!(find(&data, "data_free").get("free"), None);
+        assert!(C_HEADER.contains("@return Owned by the caller, who must free it with opendp_data__str_free()."));
+    }
+
+    #[test]
+    fn test_no_leaks_objects() {
+        let leaked = leaked_allocations(|| {
+            let hello = CString::new("hello").unwrap();
+            let object = ok(data::opendp_data__from_string(hello.as_ptr()));
+            ok(data::opendp_data__str_free(ok(data::opendp_data__to_string(object))));
+            ok(data::opendp_data__data_free(object));
+
+            let object = as_object("<Vec<f64>>", &[1.0, 2.0, 3.0]);
+            ok(data::opendp_data__slice_free(ok(data::opendp_data__object_as_slice(object))));
+            ok(data::opendp_data__data_free(object));
+
+            ok(data::opendp_data__data_free(FfiObject::new(vec!["a".to_owned(), "b".to_owned()])));
+        });
+        assert_eq!(leaked, 0);
+    }
+
+    #[test]
+    fn test_no_leaks_transformations() {
+        let leaked = leaked_allocations(|| {
+            let type_args = CString::new("<f64>").unwrap();
+            let clamp = ok(trans::opendp_trans__make_clamp(type_args.as_ptr(), as_ptr(&0.0), as_ptr(&10.0)));
+            let bounded_sum = ok(trans::opendp_trans__make_bounded_sum_l1(type_args.as_ptr(), as_ptr(&0.0), as_ptr(&10.0)));
+            let chain = ok(core::opendp_core__make_chain_tt(bounded_sum, clamp));
+            let arg = as_object("<Vec<f64>>", &[1.0, 2.0, 30.0]);
+            ok(data::opendp_data__data_free(ok(core::opendp_core__transformation_invoke(chain, arg))));
+            ok(data::opendp_data__data_free(ok(core::opendp_core__transformation_output_domain(chain))));
+            ok(data::opendp_data__str_free(ok(core::opendp_core__transformation_describe(chain))));
+            let ast = ok(core::opendp_core__transformation_to_ast(chain));
+            ok(core::opendp_core__transformation_free(ok(core::opendp_core__transformation_from_ast(ast))));
+            ok(data::opendp_data__str_free(ast));
+            let (d_in, d_out) = (FfiObject::new(1_i32), FfiObject::new(10.0_f64));
+            ok(core::opendp_core__transformation_check(clamp, d_in, d_in));
+            assert!(core::opendp_core__transformation_check(chain, d_in, d_out).into_result().is_err());
+            for object in [arg, d_in, d_out].iter() {
+                ok(data::opendp_data__data_free(*object));
+            }
+            for transformation in [clamp, bounded_sum, chain].iter() {
+                ok(core::opendp_core__transformation_free(*transformation));
+            }
+            ok(core::opendp_core__transformation_free(FfiTransformation::new_from_types(opendp::trans::make_count_l1::<i32>())));
+        });
+        assert_eq!(leaked, 0);
+    }
+
+    #[test]
+    fn test_no_leaks_measurements() {
+        let leaked = leaked_allocations(|| {
+            let type_args = CString::new("<f64>").unwrap();
+            let base_laplace = ok(meas::opendp_meas__make_base_laplace(type_args.as_ptr(), 1.0));
+            let composition = ok(core::opendp_core__make_composition(base_laplace, base_laplace));
+            let bounded_sum = ok(trans::opendp_trans__make_bounded_sum_l1(type_args.as_ptr(), as_ptr(&0.0), as_ptr(&10.0)));
+            let arg = as_object("<Vec<f64>>", &[1.0, 2.0, 3.0]);
+            let chain = ok(core::opendp_core__make_chain_mt(base_laplace, bounded_sum));
+            ok(data::opendp_data__data_free(ok(core::opendp_core__measurement_invoke(chain, arg))));
+            let scalar = FfiObject::new(1.0_f64);
+            ok(data::opendp_data__data_free(ok(core::opendp_core__measurement_invoke(composition, scalar))));
+            ok(data::opendp_data__str_free(ok(core::opendp_core__measurement_describe(composition))));
+            let ast = ok(core::opendp_core__measurement_to_ast(composition));
+            ok(core::opendp_core__measurement_free(ok(core::opendp_core__measurement_from_ast(ast))));
+            ok(data::opendp_data__str_free(ast));
+            ok(core::opendp_core__measurement_check(base_laplace, scalar, scalar));
+            for object in [arg, scalar].iter() {
+                ok(data::opendp_data__data_free(*object));
+            }
+            for measurement in [base_laplace, composition, chain].iter() {
+                ok(core::opendp_core__measurement_free(*measurement));
+            }
+            ok(core::opendp_core__transformation_free(bounded_sum));
+        });
+        assert_eq!(leaked, 0);
+    }
+
+    #[test]
+    fn test_no_leaks_errors_and_bootstrap() {
+        let leaked = leaked_allocations(|| {
+            let type_args = CString::new("<Bogus>").unwrap();
+            match trans::opendp_trans__make_identity(type_args.as_ptr()) {
+                FfiResult::Ok(_) => panic!("Expected an error"),
+                FfiResult::Err(error) => core::opendp_core__error_free(error),
+            }
+            match data::opendp_data__str_free(ptr::null_mut()) {
+                FfiResult::Ok(_) => panic!("Expected an error"),
+                FfiResult::Err(error) => core::opendp_core__error_free(error),
+            }
+            for bootstrap in [core::opendp_core__bootstrap, data::opendp_data__bootstrap, meas::opendp_meas__bootstrap, trans::opendp_trans__bootstrap].iter() {
+                ok(data::opendp_data__str_free(bootstrap()));
+            }
+        });
+        assert_eq!(leaked, 0);
+    }
+}
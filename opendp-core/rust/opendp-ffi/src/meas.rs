@@ -1,56 +1,163 @@
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_uint, c_void};
 
+use opendp::dom::{AllDomain, VectorDomain};
 use opendp::meas;
 use opendp::meas::{AddNoise, OpendpInto};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-use crate::core::FfiMeasurement;
+use crate::ast::Ast;
+use crate::core::{FfiMeasurement, FfiTransformation};
 use crate::util;
-use crate::util::TypeArgs;
+use crate::util::{Error, FfiResult, TypeArgs};
 
+/// Constructs a measurement that adds Laplace noise to a scalar.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `sigma` - The scale of the noise.
 #[no_mangle]
-pub extern "C" fn opendp_meas__make_base_laplace(type_args: *const c_char, sigma: f64) -> *mut FfiMeasurement {
-    fn monomorphize<T>(sigma: f64) -> *mut FfiMeasurement where
+pub extern "C" fn opendp_meas__make_base_laplace(type_args: *const c_char, sigma: f64) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<T>(ast: Ast, sigma: f64) -> Result<*mut FfiMeasurement, Error> where
         T: 'static + Copy + PartialEq + OpendpInto<f64>,
         f64: OpendpInto<T> {
         let measurement = meas::make_base_laplace::<T>(sigma);
-        FfiMeasurement::new_from_types(measurement)
+        Ok(FfiMeasurement::new_from_ast(Some(ast), measurement))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @numbers)], (sigma))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_base_laplace").type_args(&type_args).param("sigma", sigma);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, sigma))
+    })
 }
 
+/// Constructs a measurement that adds Laplace noise to each element of a vector.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `sigma` - The scale of the noise.
 #[no_mangle]
-pub extern "C" fn opendp_meas__make_base_laplace_vec(type_args: *const c_char, sigma: f64) -> *mut FfiMeasurement {
-    fn monomorphize<T>(sigma: f64) -> *mut FfiMeasurement where
+pub extern "C" fn opendp_meas__make_base_laplace_vec(type_args: *const c_char, sigma: f64) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<T>(ast: Ast, sigma: f64) -> Result<*mut FfiMeasurement, Error> where
         T: 'static + Copy + PartialEq + OpendpInto<f64>,
         f64: OpendpInto<T> {
         let measurement = meas::make_base_laplace_vec::<T>(sigma);
-        FfiMeasurement::new_from_types(measurement)
+        Ok(FfiMeasurement::new_from_ast(Some(ast), measurement))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @numbers)], (sigma))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_base_laplace_vec").type_args(&type_args).param("sigma", sigma);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, sigma))
+    })
 }
 
+/// Constructs a measurement that adds Gaussian noise to a scalar.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `sigma` - The standard deviation of the noise.
 #[no_mangle]
-pub extern "C" fn opendp_meas__make_base_gaussian(type_args: *const c_char, sigma: f64) -> *mut FfiMeasurement {
-    fn monomorphize<T>(sigma: f64) -> *mut FfiMeasurement where
+pub extern "C" fn opendp_meas__make_base_gaussian(type_args: *const c_char, sigma: f64) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<T>(ast: Ast, sigma: f64) -> Result<*mut FfiMeasurement, Error> where
         T: 'static + Copy + PartialEq + AddNoise {
         let measurement = meas::make_base_gaussian::<T>(sigma);
-        FfiMeasurement::new_from_types(measurement)
+        Ok(FfiMeasurement::new_from_ast(Some(ast), measurement))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @numbers)], (sigma))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_base_gaussian").type_args(&type_args).param("sigma", sigma);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, sigma))
+    })
 }
 
+/// Constructs a measurement that releases a quantile with the exponential mechanism.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound of the elements.
+/// * `upper` - A pointer to the upper bound of the elements.
+/// * `candidates` - The candidate outputs, as a JSON array.
+/// * `alpha` - The quantile, between 0 and 1.
+/// * `scale` - The scale of the exponential mechanism.
 #[no_mangle]
-pub extern "C" fn opendp_meas__bootstrap() -> *const c_char {
-    let spec =
-r#"{
-"functions": [
-    { "name": "make_base_laplace", "args": [ ["const char *", "selector"], ["double", "sigma"] ], "ret": "void *" },
-    { "name": "make_base_laplace_vec", "args": [ ["const char *", "selector"], ["double", "sigma"] ], "ret": "void *" },
-    { "name": "make_base_gaussian", "args": [ ["const char *", "selector"], ["double", "sigma"] ], "ret": "void *" }
-]
-}"#;
-    util::bootstrap(spec)
+pub extern "C" fn opendp_meas__make_private_quantile(type_args: *const c_char, lower: *const c_void, upper: *const c_void, candidates: *const c_char, alpha: f64, scale: f64) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void, candidates: *const c_char, alpha: f64, scale: f64) -> Result<*mut FfiMeasurement, Error> where
+        T: 'static + Serialize + DeserializeOwned + Clone + PartialOrd {
+        let lower = util::as_ref(lower as *const T)?.clone();
+        let upper = util::as_ref(upper as *const T)?.clone();
+        let candidates = util::parse_list(candidates, "candidates")?;
+        let ast = ast.param("lower", &lower).param("upper", &upper).param("candidates", &candidates).param("alpha", alpha).param("scale", scale);
+        let measurement = meas::make_private_quantile::<T>(lower, upper, candidates, alpha, scale)?;
+        Ok(FfiMeasurement::new_from_ast(Some(ast), measurement))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_private_quantile").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper, candidates, alpha, scale))
+    })
+}
+
+/// Constructs a measurement that releases the median of the function of a transformation over `k` random chunks of the input,
+/// with the exponential mechanism.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements of the input, and the type of the results, like `<f64, f64>`.
+/// * `transformation` - The transformation whose function is applied to each chunk, from a vector of the input elements to a result.
+/// * `k` - The number of chunks.
+/// * `lower` - A pointer to the lower bound of the results.
+/// * `upper` - A pointer to the upper bound of the results.
+/// * `candidates` - The candidate outputs, as a JSON array.
+/// * `scale` - The scale of the exponential mechanism.
+#[no_mangle]
+pub extern "C" fn opendp_meas__make_sample_and_aggregate_median(type_args: *const c_char, transformation: *const FfiTransformation, k: c_uint, lower: *const c_void, upper: *const c_void, candidates: *const c_char, scale: f64) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<TI, T>(ast: Option<Ast>, transformation: &FfiTransformation, k: usize, lower: *const c_void, upper: *const c_void, candidates: *const c_char, scale: f64) -> Result<*mut FfiMeasurement, Error> where
+        TI: 'static + Clone, T: 'static + Serialize + DeserializeOwned + Copy + PartialOrd {
+        let function = transformation.typed_function::<VectorDomain<AllDomain<TI>>, AllDomain<T>>()?;
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let candidates = util::parse_list(candidates, "candidates")?;
+        let ast = ast.map(|e| e.param("k", k).param("lower", lower).param("upper", upper).param("candidates", &candidates).param("scale", scale));
+        let measurement = meas::make_sample_and_aggregate_median::<TI, T>(function, k, lower, upper, candidates, scale)?;
+        Ok(FfiMeasurement::new_from_ast(ast, measurement))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 2)?;
+        let transformation = util::as_ref(transformation)?;
+        let ast = Ast::combine("make_sample_and_aggregate_median", &[&transformation.ast]).map(|e| e.type_args(&type_args));
+        dispatch!(monomorphize, [(type_args.0[0], @primitives), (type_args.0[1], @numbers)], (ast, transformation, k as usize, lower, upper, candidates, scale))
+    })
+}
+
+/// Constructs a measurement that releases the mean of the function of a transformation over `k` random chunks of the input,
+/// with Laplace noise.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements of the input, and the type of the results, like `<f64, f64>`.
+/// * `transformation` - The transformation whose function is applied to each chunk, from a vector of the input elements to a result.
+/// * `k` - The number of chunks.
+/// * `lower` - A pointer to the lower bound of the results.
+/// * `upper` - A pointer to the upper bound of the results.
+/// * `sigma` - The scale of the noise.
+#[no_mangle]
+pub extern "C" fn opendp_meas__make_sample_and_aggregate_mean(type_args: *const c_char, transformation: *const FfiTransformation, k: c_uint, lower: *const c_void, upper: *const c_void, sigma: f64) -> FfiResult<*mut FfiMeasurement> {
+    fn monomorphize<TI, T>(ast: Option<Ast>, transformation: &FfiTransformation, k: usize, lower: *const c_void, upper: *const c_void, sigma: f64) -> Result<*mut FfiMeasurement, Error> where
+        TI: 'static + Clone, T: 'static + Serialize + Copy + PartialOrd + Into<f64> {
+        let function = transformation.typed_function::<VectorDomain<AllDomain<TI>>, AllDomain<T>>()?;
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let ast = ast.map(|e| e.param("k", k).param("lower", lower).param("upper", upper).param("sigma", sigma));
+        let measurement = meas::make_sample_and_aggregate_mean::<TI, T>(function, k, lower, upper, sigma)?;
+        Ok(FfiMeasurement::new_from_ast(ast, measurement))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 2)?;
+        let transformation = util::as_ref(transformation)?;
+        let ast = Ast::combine("make_sample_and_aggregate_mean", &[&transformation.ast]).map(|e| e.type_args(&type_args));
+        dispatch!(monomorphize, [(type_args.0[0], @primitives), (type_args.0[1], [u32, i32, f32, f64, u8])], (ast, transformation, k as usize, lower, upper, sigma))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn opendp_meas__bootstrap() -> *mut c_char {
+    util::bootstrap(include_str!(concat!(env!("OUT_DIR"), "/meas.json")))
 }
@@ -1,169 +1,1075 @@
 use std::fmt::Debug;
+use std::hash::Hash;
 use std::iter::Sum;
 use std::os::raw::{c_char, c_uint, c_void};
 use std::str::FromStr;
 
+use opendp::core::Domain;
 use opendp::data::{Element, Form};
+use opendp::dom::{AllDomain, DataFrameDomain, InherentNullDomain, NullableDomain, OptionNullDomain, VectorDomain};
 use opendp::trans;
+use opendp::dom::InherentNull;
+use opendp::trans::{CastFrom, CheckedArithmetic, CsvOptions, Float, InputColumn, InputElement, Predicate};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-use crate::core::FfiTransformation;
+use crate::ast::Ast;
+use crate::core::{FfiObject, FfiTransformation};
 use crate::util;
-use crate::util::c_bool;
+use crate::util::{c_bool, Error, ErrorKind, FfiResult};
 use crate::util::TypeArgs;
 
+fn to_dataframe_domain<'a>(input_domain: *const FfiObject) -> Result<&'a DataFrameDomain, Error> {
+    util::as_ref(input_domain)?.as_ref()
+}
+
+/// Constructs a transformation that returns its input.
+///
+/// # Arguments
+/// * `type_args` - The type of the input, like `<String>`.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_identity(type_args: *const c_char) -> *mut FfiTransformation {
-    fn monomorphize<T: 'static + Form + Clone>() -> *mut FfiTransformation {
+pub extern "C" fn opendp_trans__make_identity(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T: 'static + Form + Clone>(ast: Ast) -> Result<*mut FfiTransformation, Error> {
         let transformation = trans::make_identity::<T>();
-        FfiTransformation::new_from_types(transformation)
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @primitives)], ())
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_identity").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast))
+    })
 }
 
+/// Constructs a transformation that splits a string into lines.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_split_lines() -> *mut FfiTransformation {
-    let transformation = trans::make_split_lines();
-    FfiTransformation::new_from_types(transformation)
+pub extern "C" fn opendp_trans__make_split_lines() -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let transformation = trans::make_split_lines();
+        Ok(FfiTransformation::new_from_ast(Some(Ast::new("make_split_lines")), transformation))
+    })
 }
 
+/// Constructs a transformation that parses a vector of strings.
+///
+/// # Arguments
+/// * `type_args` - The type to parse to, like `<f64>`.
+/// * `impute` - Whether to replace unparseable elements with the default, rather than fail.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_parse_series(type_args: *const c_char, impute: c_bool) -> *mut FfiTransformation {
-    fn monomorphize<T>(impute: bool) -> *mut FfiTransformation where
+pub extern "C" fn opendp_trans__make_parse_series(type_args: *const c_char, impute: c_bool) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, impute: bool) -> Result<*mut FfiTransformation, Error> where
         T: 'static + FromStr + Default, T::Err: Debug {
         let transformation = trans::make_parse_series::<T>(impute);
-        FfiTransformation::new_from_types(transformation)
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    let impute = util::to_bool(impute);
-    dispatch!(monomorphize, [(type_args.0[0], @primitives)], (impute))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let impute = util::to_bool(impute);
+        let ast = Ast::new("make_parse_series").type_args(&type_args).param("impute", impute);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast, impute))
+    })
 }
 
+/// Constructs a transformation that parses a vector of strings, with null for elements that fail to parse.
+///
+/// # Arguments
+/// * `type_args` - The type to parse to, like `<f64>`.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_split_records(separator: *const c_char) -> *mut FfiTransformation {
-    let separator = util::to_option_str(separator);
-    let transformation = trans::make_split_records(separator);
-    FfiTransformation::new_from_types(transformation)
+pub extern "C" fn opendp_trans__make_parse_series_option(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T: 'static + FromStr>(ast: Ast) -> Result<*mut FfiTransformation, Error> {
+        let transformation = trans::make_parse_series_option::<T>();
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_parse_series_option").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast))
+    })
 }
 
+/// Constructs a transformation that parses a vector of strings, with the type's inherent null value (NaN) for elements that fail to parse.
+///
+/// # Arguments
+/// * `type_args` - The type to parse to, like `<f64>`.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_create_dataframe(col_count: c_uint) -> *mut FfiTransformation {
-    let col_count = col_count as usize;
-    let transformation = trans::make_create_dataframe(col_count);
-    FfiTransformation::new_from_types(transformation)
+pub extern "C" fn opendp_trans__make_parse_series_inherent(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T: 'static + FromStr + InherentNull>(ast: Ast) -> Result<*mut FfiTransformation, Error> {
+        let transformation = trans::make_parse_series_inherent::<T>();
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_parse_series_inherent").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], [f32, f64])], (ast))
+    })
+}
+
+/// The carrier of an element that may be null, which determines its domain: `Option<T>` for an `OptionNullDomain`,
+/// or a float for an `InherentNullDomain`, whose null is NaN.
+trait NullableCarrier: 'static {
+    type NonNull: 'static;
+    type Domain: 'static + NullableDomain<Carrier=Self, NonNull=Self::NonNull>;
+}
+impl<T: 'static> NullableCarrier for Option<T> {
+    type NonNull = T;
+    type Domain = OptionNullDomain<AllDomain<T>>;
+}
+impl NullableCarrier for f32 {
+    type NonNull = f32;
+    type Domain = InherentNullDomain<AllDomain<f32>>;
+}
+impl NullableCarrier for f64 {
+    type NonNull = f64;
+    type Domain = InherentNullDomain<AllDomain<f64>>;
+}
+
+/// Constructs a transformation that replaces each null element of a vector with a constant.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<Option<i32>>`, or `<f64>` for floats whose null is NaN.
+/// * `constant` - A pointer to the value to replace nulls with, of the non-null type, like `i32` for `<Option<i32>>`.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_impute_constant(type_args: *const c_char, constant: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, constant: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: NullableCarrier, T::NonNull: Serialize + Clone {
+        let constant = util::as_ref(constant as *const T::NonNull)?.clone();
+        let ast = ast.param("constant", &constant);
+        let transformation = trans::make_impute_constant::<T::Domain>(constant);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_impute_constant").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @nullables)], (ast, constant))
+    })
+}
+
+/// Constructs a transformation that replaces each null element of a vector with a value drawn uniformly from `[lower, upper)`.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<Option<f64>>`, or `<f64>` for floats whose null is NaN.
+/// * `lower` - A pointer to the lower bound, of the non-null type, like `f64` for `<Option<f64>>`.
+/// * `upper` - A pointer to the upper bound, of the non-null type.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_impute_uniform_float(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: NullableCarrier, T::NonNull: Serialize + Float {
+        let lower = *util::as_ref(lower as *const T::NonNull)?;
+        let upper = *util::as_ref(upper as *const T::NonNull)?;
+        let ast = ast.param("lower", lower).param("upper", upper);
+        let transformation = trans::make_impute_uniform_float::<T::Domain>(lower, upper)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_impute_uniform_float").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], [Option<f32>, Option<f64>, f32, f64])], (ast, lower, upper))
+    })
+}
+
+/// Constructs a transformation that removes the null elements of a vector.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<Option<i32>>`, or `<f64>` for floats whose null is NaN.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_drop_null(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast) -> Result<*mut FfiTransformation, Error> where
+        T: NullableCarrier, T::NonNull: Clone {
+        let transformation = trans::make_drop_null::<T::Domain>();
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_drop_null").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @nullables)], (ast))
+    })
+}
+
+/// Constructs a transformation that tests each element of a vector for null.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<Option<i32>>`, or `<f64>` for floats whose null is NaN.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_is_null(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T: NullableCarrier>(ast: Ast) -> Result<*mut FfiTransformation, Error> {
+        let transformation = trans::make_is_null::<T::Domain>();
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_is_null").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @nullables)], (ast))
+    })
+}
+
+/// Constructs a transformation that casts the elements of a vector, with null for elements that fail to cast.
+///
+/// # Arguments
+/// * `type_args` - The input and output types, like `<String, i32>`.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_cast(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<TI, TO>(ast: Ast) -> Result<*mut FfiTransformation, Error> where
+        TI: 'static, TO: 'static + CastFrom<TI> {
+        let transformation = trans::make_cast::<TI, TO>();
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 2)?;
+        let ast = Ast::new("make_cast").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives), (type_args.0[1], @primitives)], (ast))
+    })
+}
+
+/// Constructs a transformation that casts the elements of a vector, replacing failed casts with a default value.
+///
+/// # Arguments
+/// * `type_args` - The input and output types, like `<String, i32>`.
+/// * `default_value` - A pointer to the value of the output type to use for elements that fail to cast.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_cast_default(type_args: *const c_char, default_value: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<TI, TO>(ast: Ast, default_value: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        TI: 'static, TO: 'static + CastFrom<TI> + Serialize + Clone {
+        let default_value = util::as_ref(default_value as *const TO)?.clone();
+        let ast = ast.param("default_value", &default_value);
+        let transformation = trans::make_cast_default::<TI, TO>(default_value);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 2)?;
+        let ast = Ast::new("make_cast_default").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives), (type_args.0[1], @primitives)], (ast, default_value))
+    })
+}
+
+/// Constructs a transformation that casts the elements of a vector, replacing failed casts with the type's inherent null value.
+///
+/// # Arguments
+/// * `type_args` - The input and output types, like `<String, f64>`.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_cast_inherent(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<TI, TO>(ast: Ast) -> Result<*mut FfiTransformation, Error> where
+        TI: 'static, TO: 'static + CastFrom<TI> + InherentNull {
+        let transformation = trans::make_cast_inherent::<TI, TO>();
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 2)?;
+        let ast = Ast::new("make_cast_inherent").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives), (type_args.0[1], [f32, f64])], (ast))
+    })
+}
+
+/// Constructs a transformation that compares each element of a vector to a value.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `value` - A pointer to the value to compare to.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_is_equal(type_args: *const c_char, value: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<TI>(ast: Ast, value: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        TI: 'static + Serialize + Clone + PartialEq {
+        let value = util::as_ref(value as *const TI)?.clone();
+        let ast = ast.param("value", &value);
+        let transformation = trans::make_is_equal::<TI>(value);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_is_equal").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, value))
+    })
 }
 
+/// Constructs a transformation that splits each line into fields.
+///
+/// # Arguments
+/// * `separator` - The field separator, or null for a comma.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_split_dataframe(separator: *const c_char, col_count: c_uint) -> *mut FfiTransformation {
-    let separator = util::to_option_str(separator);
-    let col_count = col_count as usize;
-    let transformation = trans::make_split_dataframe(separator, col_count);
-    FfiTransformation::new_from_types(transformation)
+pub extern "C" fn opendp_trans__make_split_records(separator: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let separator = util::to_option_str(separator)?;
+        let ast = Ast::new("make_split_records").param("separator", separator);
+        let transformation = trans::make_split_records(separator);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    })
 }
 
+/// Constructs a transformation that turns records into a dataframe with numbered columns.
+///
+/// # Arguments
+/// * `col_count` - The number of columns.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_parse_column(type_args: *const c_char, key: *const c_char, impute: c_bool) -> *mut FfiTransformation {
-    fn monomorphize<T>(key: &str, impute: bool) -> *mut FfiTransformation where
+pub extern "C" fn opendp_trans__make_create_dataframe(col_count: c_uint) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let col_count = col_count as usize;
+        let ast = Ast::new("make_create_dataframe").param("col_count", col_count);
+        let transformation = trans::make_create_dataframe(col_count);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    })
+}
+
+/// Constructs a transformation that splits a string into a dataframe with numbered columns.
+///
+/// # Arguments
+/// * `separator` - The field separator, or null for a comma.
+/// * `col_count` - The number of columns.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_split_dataframe(separator: *const c_char, col_count: c_uint) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let separator = util::to_option_str(separator)?;
+        let col_count = col_count as usize;
+        let ast = Ast::new("make_split_dataframe").param("separator", separator).param("col_count", col_count);
+        let transformation = trans::make_split_dataframe(separator, col_count);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    })
+}
+
+/// Constructs a transformation that parses CSV into a dataframe of string columns.
+///
+/// # Arguments
+/// * `keys` - The names of the columns, as a JSON array.
+/// * `separator` - The field separator, or null for a comma.
+/// * `quote` - The quote character, or null for a double quote (`"`).
+/// * `escape` - The character escaping quotes inside quoted fields, or null to escape quotes by doubling them.
+/// * `has_header` - Whether the first line is a header, whose names are matched against `keys` to pick the columns, failing with `Panic` on invoke if any of `keys` is missing from it.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_split_csv(keys: *const c_char, separator: *const c_char, quote: *const c_char, escape: *const c_char, has_header: c_bool) -> FfiResult<*mut FfiTransformation> {
+    fn to_option_char(p: *const c_char) -> Result<Option<char>, Error> {
+        util::to_option_str(p)?.map(|e| e.chars().next().ok_or_else(|| Error::new(ErrorKind::Ffi, "Empty character argument"))).transpose()
+    }
+    util::ffi_result(|| {
+        let default = CsvOptions::default();
+        let separator = to_option_char(separator)?.unwrap_or(default.separator);
+        let quote = to_option_char(quote)?.unwrap_or(default.quote);
+        let escape = to_option_char(escape)?;
+        let has_header = util::to_bool(has_header);
+        let keys: Vec<String> = util::parse_list(keys, "keys")?;
+        let ast = Ast::new("make_split_csv").param("keys", &keys).param("separator", separator).param("quote", quote).param("escape", escape).param("has_header", has_header);
+        let options = CsvOptions { separator, quote, escape, has_header };
+        let transformation = trans::make_split_csv(keys, options);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    })
+}
+
+/// The columns read by the input transformations, with the type argument of each key.
+fn to_input_columns(type_args: &TypeArgs, keys: &[String]) -> Result<Vec<InputColumn>, Error> {
+    fn monomorphize<T: InputElement>(key: &str) -> Result<InputColumn, Error> {
+        Ok(InputColumn::new::<T>(key))
+    }
+    keys.iter().zip(type_args.0.iter()).map(|(key, type_arg)| {
+        dispatch!(monomorphize, [(type_arg, @primitives)], (key))
+    }).collect()
+}
+
+/// Constructs a transformation that parses JSON lines into a dataframe.
+/// Each column contains `Option<T>`, with null for values that are null, missing or not a valid `T`.
+///
+/// # Arguments
+/// * `type_args` - The type of each column, like `<i32, String>`.
+/// * `keys` - The names of the columns, as a JSON array.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_parse_jsonl(type_args: *const c_char, keys: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let keys: Vec<String> = util::parse_list(keys, "keys")?;
+        let type_args = TypeArgs::parse(type_args, keys.len())?;
+        let ast = Ast::new("make_parse_jsonl").type_args(&type_args).param("keys", &keys);
+        let transformation = trans::make_parse_jsonl(to_input_columns(&type_args, &keys)?);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    })
+}
+
+/// Constructs a transformation that reads Apache Arrow IPC data (file or stream format) into a dataframe.
+/// Each column contains `Option<T>`, with null for values that are null, missing or can't be cast to `T`.
+///
+/// Fails with `NotImplemented` if the library was built without the `arrow` feature. Invoking it on invalid or truncated data fails with `Panic`.
+///
+/// # Arguments
+/// * `type_args` - The type of each column, like `<i32, String>`.
+/// * `keys` - The names of the columns, as a JSON array.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_read_arrow_ipc(type_args: *const c_char, keys: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    #[cfg(feature = "arrow")]
+    fn make(ast: Ast, columns: Vec<InputColumn>) -> Result<*mut FfiTransformation, Error> {
+        Ok(FfiTransformation::new_from_ast(Some(ast), trans::make_read_arrow_ipc(columns)))
+    }
+    #[cfg(not(feature = "arrow"))]
+    fn make(_ast: Ast, _columns: Vec<InputColumn>) -> Result<*mut FfiTransformation, Error> {
+        Err(Error::new(ErrorKind::NotImplemented, "make_read_arrow_ipc needs the arrow feature"))
+    }
+    util::ffi_result(|| {
+        let keys: Vec<String> = util::parse_list(keys, "keys")?;
+        let type_args = TypeArgs::parse(type_args, keys.len())?;
+        let ast = Ast::new("make_read_arrow_ipc").type_args(&type_args).param("keys", &keys);
+        make(ast, to_input_columns(&type_args, &keys)?)
+    })
+}
+
+/// Constructs a transformation that reads an Apache Parquet file into a dataframe.
+/// Each column contains `Option<T>`, with null for values that are null, missing or can't be cast to `T`.
+///
+/// Fails with `NotImplemented` if the library was built without the `parquet` feature. Invoking it on invalid or truncated data fails with `Panic`.
+///
+/// # Arguments
+/// * `type_args` - The type of each column, like `<i32, String>`.
+/// * `keys` - The names of the columns, as a JSON array.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_read_parquet(type_args: *const c_char, keys: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    #[cfg(feature = "parquet")]
+    fn make(ast: Ast, columns: Vec<InputColumn>) -> Result<*mut FfiTransformation, Error> {
+        Ok(FfiTransformation::new_from_ast(Some(ast), trans::make_read_parquet(columns)))
+    }
+    #[cfg(not(feature = "parquet"))]
+    fn make(_ast: Ast, _columns: Vec<InputColumn>) -> Result<*mut FfiTransformation, Error> {
+        Err(Error::new(ErrorKind::NotImplemented, "make_read_parquet needs the parquet feature"))
+    }
+    util::ffi_result(|| {
+        let keys: Vec<String> = util::parse_list(keys, "keys")?;
+        let type_args = TypeArgs::parse(type_args, keys.len())?;
+        let ast = Ast::new("make_read_parquet").type_args(&type_args).param("keys", &keys);
+        make(ast, to_input_columns(&type_args, &keys)?)
+    })
+}
+
+/// Constructs a transformation that parses one column of a dataframe.
+///
+/// # Arguments
+/// * `type_args` - The type to parse to, like `<f64>`.
+/// * `input_domain` - The dataframe domain of the input.
+/// * `key` - The name of the column.
+/// * `impute` - Whether to replace unparseable elements with the default, rather than fail.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_parse_column(type_args: *const c_char, input_domain: *const FfiObject, key: *const c_char, impute: c_bool) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, input_domain: &DataFrameDomain, key: &str, impute: bool) -> Result<*mut FfiTransformation, Error> where
         T: 'static + Element + Clone + PartialEq + FromStr + Default, T::Err: Debug {
-        let transformation = trans::make_parse_column::<T>(key, impute);
-        FfiTransformation::new_from_types(transformation)
+        let transformation = trans::make_parse_column::<T>(input_domain, key, impute)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let key = util::to_str(key)?;
+        let impute = util::to_bool(impute);
+        let ast = Ast::new("make_parse_column").type_args(&type_args).param("input_domain", input_domain.describe()).param("key", key).param("impute", impute);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast, input_domain, key, impute))
+    })
+}
+
+/// Constructs a transformation that selects one column of a dataframe.
+///
+/// # Arguments
+/// * `type_args` - The type of the column, like `<f64>`.
+/// * `input_domain` - The dataframe domain of the input.
+/// * `key` - The name of the column.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_select_column(type_args: *const c_char, input_domain: *const FfiObject, key: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, input_domain: &DataFrameDomain, key: &str) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Element + Clone + PartialEq {
+        let transformation = trans::make_select_column::<T>(input_domain, key)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let key = util::to_str(key)?;
+        let ast = Ast::new("make_select_column").type_args(&type_args).param("input_domain", input_domain.describe()).param("key", key);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast, input_domain, key))
+    })
+}
+
+/// Constructs a transformation that selects one column of a dataframe, under the symmetric distance,
+/// like the dataframes of `opendp_trans__make_filter_dataframe()`.
+///
+/// # Arguments
+/// * `type_args` - The type of the column, like `<f64>`.
+/// * `input_domain` - The dataframe domain of the input.
+/// * `key` - The name of the column.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_select_column_symmetric(type_args: *const c_char, input_domain: *const FfiObject, key: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, input_domain: &DataFrameDomain, key: &str) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Element + Clone + PartialEq {
+        let transformation = trans::make_select_column_symmetric::<T>(input_domain, key)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    let key = util::to_str(key);
-    let impute = util::to_bool(impute);
-    dispatch!(monomorphize, [(type_args.0[0], @primitives)], (key, impute))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let key = util::to_str(key)?;
+        let ast = Ast::new("make_select_column_symmetric").type_args(&type_args).param("input_domain", input_domain.describe()).param("key", key);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast, input_domain, key))
+    })
 }
 
+/// Constructs a transformation that selects one column of a dataframe, whose elements may be null,
+/// like the columns read by `opendp_trans__make_parse_jsonl()`.
+///
+/// # Arguments
+/// * `type_args` - The type of the non-null elements of the column, like `<f64>`.
+/// * `input_domain` - The dataframe domain of the input.
+/// * `key` - The name of the column.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_select_column(type_args: *const c_char, key: *const c_char) -> *mut FfiTransformation {
-    fn monomorphize<T>(key: &str) -> *mut FfiTransformation where
+pub extern "C" fn opendp_trans__make_select_column_option(type_args: *const c_char, input_domain: *const FfiObject, key: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, input_domain: &DataFrameDomain, key: &str) -> Result<*mut FfiTransformation, Error> where
         T: 'static + Element + Clone + PartialEq {
-        let transformation = trans::make_select_column::<T>(key);
-        FfiTransformation::new_from_types(transformation)
+        let transformation = trans::make_select_column_option::<T>(input_domain, key)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    let key = util::to_str(key);
-    dispatch!(monomorphize, [(type_args.0[0], @primitives)], (key))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let key = util::to_str(key)?;
+        let ast = Ast::new("make_select_column_option").type_args(&type_args).param("input_domain", input_domain.describe()).param("key", key);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast, input_domain, key))
+    })
+}
+
+fn to_predicate<T: DeserializeOwned>(predicate: *const c_char) -> Result<Predicate<T>, Error> {
+    serde_json::from_str(util::to_str(predicate)?).map_err(|e| Error::new(ErrorKind::FailedParse, format!("Bogus predicate: {}", e)))
 }
 
+/// Constructs a transformation that returns its input vector, converting the Hamming distance to the symmetric distance,
+/// so that it can be chained with transformations like `opendp_trans__make_filter()`.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_clamp(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> *mut FfiTransformation {
-    fn monomorphize<T>(lower: *const c_void, upper: *const c_void) -> *mut FfiTransformation where
-        T: 'static + Copy + PartialOrd {
-        let lower = util::as_ref(lower as *const T).clone();
-        let upper = util::as_ref(upper as *const T).clone();
+pub extern "C" fn opendp_trans__make_hamming_to_symmetric(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T: 'static + Clone>(ast: Ast) -> Result<*mut FfiTransformation, Error> {
+        let transformation = trans::make_hamming_to_symmetric(VectorDomain::<AllDomain<T>>::new_all());
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_hamming_to_symmetric").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast))
+    })
+}
+
+/// Constructs a transformation that returns its input vector of nullable elements, converting the Hamming distance to the symmetric distance,
+/// so that it can be chained with transformations like `opendp_trans__make_drop_null()`.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<Option<i32>>`, or `<f64>` for floats whose null is NaN.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_hamming_to_symmetric_nullable(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T: NullableCarrier + Clone>(ast: Ast) -> Result<*mut FfiTransformation, Error> {
+        let transformation = trans::make_hamming_to_symmetric(VectorDomain::new(T::Domain::new_all()));
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_hamming_to_symmetric_nullable").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @nullables)], (ast))
+    })
+}
+
+/// Constructs a transformation that returns its input dataframe, converting the Hamming distance to the symmetric distance,
+/// so that it can be chained with transformations like `opendp_trans__make_filter_dataframe()`.
+///
+/// # Arguments
+/// * `input_domain` - The dataframe domain of the input.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_hamming_to_symmetric_dataframe(input_domain: *const FfiObject) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let ast = Ast::new("make_hamming_to_symmetric_dataframe").param("input_domain", input_domain.describe());
+        Ok(FfiTransformation::new_from_ast(Some(ast), trans::make_hamming_to_symmetric(input_domain.clone())))
+    })
+}
+
+/// Constructs a transformation that keeps the elements of a vector that satisfy a predicate.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `predicate` - A [`Predicate`] serialized as JSON.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_filter(type_args: *const c_char, predicate: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, predicate: *const c_char) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Clone + PartialOrd + Serialize + DeserializeOwned {
+        let predicate = to_predicate(predicate)?;
+        let ast = ast.param("predicate", &predicate);
+        let transformation = trans::make_filter::<T>(predicate);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_filter").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast, predicate))
+    })
+}
+
+/// Constructs a transformation that keeps the rows of a dataframe whose column satisfies a predicate.
+///
+/// # Arguments
+/// * `type_args` - The type of the column, like `<f64>`.
+/// * `input_domain` - The dataframe domain of the input.
+/// * `key` - The name of the column.
+/// * `predicate` - A [`Predicate`] serialized as JSON.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_filter_dataframe(type_args: *const c_char, input_domain: *const FfiObject, key: *const c_char, predicate: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, input_domain: &DataFrameDomain, key: &str, predicate: *const c_char) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Element + Clone + PartialOrd + Serialize + DeserializeOwned {
+        let predicate = to_predicate(predicate)?;
+        let ast = ast.param("predicate", &predicate);
+        let transformation = trans::make_filter_dataframe::<T>(input_domain, key, predicate)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let key = util::to_str(key)?;
+        let ast = Ast::new("make_filter_dataframe").type_args(&type_args).param("input_domain", input_domain.describe()).param("key", key);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast, input_domain, key, predicate))
+    })
+}
+
+/// Constructs a transformation that returns its input dataframe, converting the Hamming distance to the user distance,
+/// so that it can be chained with `opendp_trans__make_bound_user_contributions()`.
+///
+/// # Arguments
+/// * `input_domain` - The dataframe domain of the input.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_hamming_to_user(input_domain: *const FfiObject) -> FfiResult<*mut FfiTransformation> {
+    util::ffi_result(|| {
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let ast = Ast::new("make_hamming_to_user").param("input_domain", input_domain.describe());
+        Ok(FfiTransformation::new_from_ast(Some(ast), trans::make_hamming_to_user(input_domain)))
+    })
+}
+
+/// Constructs a transformation that keeps at most `k` rows of a dataframe for each user.
+///
+/// # Arguments
+/// * `type_args` - The type of the id column, like `<String>`.
+/// * `input_domain` - The dataframe domain of the input.
+/// * `id_key` - The name of the id column.
+/// * `k` - The number of rows to keep for each user.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_bound_user_contributions(type_args: *const c_char, input_domain: *const FfiObject, id_key: *const c_char, k: c_uint) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<TK>(ast: Ast, input_domain: &DataFrameDomain, id_key: &str, k: u32) -> Result<*mut FfiTransformation, Error> where
+        TK: 'static + Element + Clone + Eq + Hash {
+        let transformation = trans::make_bound_user_contributions::<TK>(input_domain, id_key, k)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let id_key = util::to_str(id_key)?;
+        let ast = Ast::new("make_bound_user_contributions").type_args(&type_args).param("input_domain", input_domain.describe()).param("id_key", id_key).param("k", k);
+        dispatch!(monomorphize, [(type_args.0[0], [u32, u64, i32, i64, bool, String, u8])], (ast, input_domain, id_key, k))
+    })
+}
+
+/// Constructs a transformation that splits a dataframe into one partition for each of `keys`, by the value of a column.
+/// Rows whose value isn't one of `keys` are dropped.
+///
+/// # Arguments
+/// * `type_args` - The type of the column, like `<String>`.
+/// * `input_domain` - The dataframe domain of the input.
+/// * `key` - The name of the column.
+/// * `keys` - The values of the column to partition by, as a JSON array.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_partition_by(type_args: *const c_char, input_domain: *const FfiObject, key: *const c_char, keys: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<TK>(ast: Ast, input_domain: &DataFrameDomain, key: &str, keys: *const c_char) -> Result<*mut FfiTransformation, Error> where
+        TK: 'static + Element + Clone + Eq + Hash + Serialize + DeserializeOwned {
+        let keys: Vec<TK> = util::parse_list(keys, "keys")?;
+        let ast = ast.param("keys", &keys);
+        let transformation = trans::make_partition_by::<TK>(input_domain, key, keys)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let input_domain = to_dataframe_domain(input_domain)?;
+        let key = util::to_str(key)?;
+        let ast = Ast::new("make_partition_by").type_args(&type_args).param("input_domain", input_domain.describe()).param("key", key);
+        dispatch!(monomorphize, [(type_args.0[0], [u32, u64, i32, i64, bool, String, u8])], (ast, input_domain, key, keys))
+    })
+}
+
+/// Constructs a transformation that clamps the elements of a vector.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound.
+/// * `upper` - A pointer to the upper bound.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_clamp(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + Copy + PartialOrd {
+        let lower = util::as_ref(lower as *const T)?.clone();
+        let upper = util::as_ref(upper as *const T)?.clone();
+        let ast = ast.param("lower", lower).param("upper", upper);
         let transformation = trans::make_clamp::<T>(lower, upper);
-        FfiTransformation::new_from_types(transformation)
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_clamp").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper))
+    })
+}
+
+/// Constructs a transformation that clamps the elements of a vector, under the symmetric distance.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound.
+/// * `upper` - A pointer to the upper bound.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_clamp_symmetric(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + Copy + PartialOrd {
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let ast = ast.param("lower", lower).param("upper", upper);
+        let transformation = trans::make_clamp_symmetric::<T>(lower, upper);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_clamp_symmetric").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper))
+    })
+}
+
+/// Constructs a transformation that splits a vector into `k` random chunks, applies the function of a transformation
+/// to each chunk, and clamps the `k` results.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements of the input, and the type of the results, like `<f64, f64>`.
+/// * `transformation` - The transformation whose function is applied to each chunk, from a vector of the input elements to a result.
+/// * `k` - The number of chunks.
+/// * `lower` - A pointer to the lower bound of the results.
+/// * `upper` - A pointer to the upper bound of the results.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_sample_and_aggregate(type_args: *const c_char, transformation: *const FfiTransformation, k: c_uint, lower: *const c_void, upper: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<TI, T>(ast: Option<Ast>, transformation: &FfiTransformation, k: usize, lower: *const c_void, upper: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        TI: 'static + Clone, T: 'static + Serialize + Copy + PartialOrd {
+        let function = transformation.typed_function::<VectorDomain<AllDomain<TI>>, AllDomain<T>>()?;
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let ast = ast.map(|e| e.param("k", k).param("lower", lower).param("upper", upper));
+        let transformation = trans::make_sample_and_aggregate::<TI, T>(function, k, lower, upper)?;
+        Ok(FfiTransformation::new_from_ast(ast, transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 2)?;
+        let transformation = util::as_ref(transformation)?;
+        let ast = Ast::combine("make_sample_and_aggregate", &[&transformation.ast]).map(|e| e.type_args(&type_args));
+        dispatch!(monomorphize, [(type_args.0[0], @primitives), (type_args.0[1], @numbers)], (ast, transformation, k as usize, lower, upper))
+    })
+}
+
+/// Constructs a transformation that replaces each element of a vector that isn't one of the categories with a null value.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<String>`.
+/// * `categories` - The categories, as a JSON array.
+/// * `null` - A pointer to the value to replace other elements with.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_clamp_categorical(type_args: *const c_char, categories: *const c_char, null: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, categories: *const c_char, null: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + DeserializeOwned + Clone + Eq + Hash {
+        let categories = util::parse_list(categories, "categories")?;
+        let null = util::as_ref(null as *const T)?.clone();
+        let ast = ast.param("categories", &categories).param("null", &null);
+        let transformation = trans::make_clamp_categorical::<T>(categories, null);
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @numbers)], (lower, upper))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_clamp_categorical").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], [u32, u64, i32, i64, bool, String, u8])], (ast, categories, null))
+    })
 }
 
+/// Constructs a transformation that maps each element of a vector to the index of its bin.
+/// NaN lands in the unknown bin, one past the bin above the last edge.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `edges` - The sorted bin edges, as a JSON array.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_bounded_sum_l1(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> *mut FfiTransformation {
-    fn monomorphize<T>(lower: *const c_void, upper: *const c_void) -> *mut FfiTransformation where
-        T: 'static + Clone + PartialOrd + Sum {
-        let lower = util::as_ref(lower as *const T).clone();
-        let upper = util::as_ref(upper as *const T).clone();
+pub extern "C" fn opendp_trans__make_find_bin(type_args: *const c_char, edges: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, edges: *const c_char) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + DeserializeOwned + PartialOrd {
+        let edges = util::parse_list(edges, "edges")?;
+        let ast = ast.param("edges", &edges);
+        let transformation = trans::make_find_bin::<T>(edges)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_find_bin").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, edges))
+    })
+}
+
+/// Constructs a transformation that maps each element of a vector to the index of its category.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `categories` - The categories, as a JSON array.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_find(type_args: *const c_char, categories: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, categories: *const c_char) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + DeserializeOwned + Eq + Hash {
+        let categories = util::parse_list(categories, "categories")?;
+        let ast = ast.param("categories", &categories);
+        let transformation = trans::make_find::<T>(categories)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_find").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], [u32, u64, i32, i64, bool, String, u8])], (ast, categories))
+    })
+}
+
+/// Constructs a transformation that sums a vector, with L1 sensitivity.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound of the elements.
+/// * `upper` - A pointer to the upper bound of the elements.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_bounded_sum_l1(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + Clone + PartialOrd + Sum {
+        let lower = util::as_ref(lower as *const T)?.clone();
+        let upper = util::as_ref(upper as *const T)?.clone();
+        let ast = ast.param("lower", &lower).param("upper", &upper);
         let transformation = trans::make_bounded_sum_l1::<T>(lower, upper);
-        FfiTransformation::new_from_types(transformation)
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_bounded_sum_l1").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper))
+    })
+}
+
+/// Constructs a transformation that sums a vector under the symmetric distance, with L1 sensitivity.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound of the elements.
+/// * `upper` - A pointer to the upper bound of the elements.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_bounded_sum_symmetric(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + CheckedArithmetic + CastFrom<i32> + Sum {
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let ast = ast.param("lower", lower).param("upper", upper);
+        let transformation = trans::make_bounded_sum_symmetric::<T>(lower, upper)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @numbers)], (lower, upper))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_bounded_sum_symmetric").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper))
+    })
 }
 
+/// Constructs a transformation that sums a vector, with L2 sensitivity.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound of the elements.
+/// * `upper` - A pointer to the upper bound of the elements.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_bounded_sum_l2(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> *mut FfiTransformation {
-    fn monomorphize<T>(lower: *const c_void, upper: *const c_void) -> *mut FfiTransformation where
-        T: 'static + Clone + PartialOrd + Sum {
-        let lower = util::as_ref(lower as *const T).clone();
-        let upper = util::as_ref(upper as *const T).clone();
+pub extern "C" fn opendp_trans__make_bounded_sum_l2(type_args: *const c_char, lower: *const c_void, upper: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + Clone + PartialOrd + Sum {
+        let lower = util::as_ref(lower as *const T)?.clone();
+        let upper = util::as_ref(upper as *const T)?.clone();
+        let ast = ast.param("lower", &lower).param("upper", &upper);
         let transformation = trans::make_bounded_sum_l2::<T>(lower, upper);
-        FfiTransformation::new_from_types(transformation)
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @numbers)], (lower, upper))
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_bounded_sum_l2").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper))
+    })
 }
 
+/// Constructs a transformation that counts the elements of a vector, with L1 sensitivity.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_count_l1(type_args: *const c_char) -> *mut FfiTransformation {
-    fn monomorphize<T>() -> *mut FfiTransformation where T: 'static {
+pub extern "C" fn opendp_trans__make_count_l1(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast) -> Result<*mut FfiTransformation, Error> where T: 'static {
         let transformation = trans::make_count_l1::<T>();
-        FfiTransformation::new_from_types(transformation)
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
     }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @primitives)], ())
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_count_l1").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast))
+    })
 }
 
+/// Constructs a transformation that counts the elements of a vector, with L2 sensitivity.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
 #[no_mangle]
-pub extern "C" fn opendp_trans__make_count_l2(type_args: *const c_char) -> *mut FfiTransformation {
-    fn monomorphize<T>() -> *mut FfiTransformation where T: 'static {
+pub extern "C" fn opendp_trans__make_count_l2(type_args: *const c_char) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast) -> Result<*mut FfiTransformation, Error> where T: 'static {
         let transformation = trans::make_count_l2::<T>();
-        FfiTransformation::new_from_types(transformation)
-    }
-    let type_args = TypeArgs::expect(type_args, 1);
-    dispatch!(monomorphize, [(type_args.0[0], @primitives)], ())
-}
-
-#[no_mangle]
-pub extern "C" fn opendp_trans__bootstrap() -> *const c_char {
-    let spec =
-r#"{
-"functions": [
-    { "name": "make_identity", "ret": "void *" },
-    { "name": "make_split_lines", "ret": "void *" },
-    { "name": "make_parse_series", "args": [ ["const char *", "selector"], ["bool", "impute"] ], "ret": "void *" },
-    { "name": "make_split_records", "args": [ ["const char *", "separator"] ], "ret": "void *" },
-    { "name": "make_create_dataframe", "args": [ ["unsigned int", "col_count"] ], "ret": "void *" },
-    { "name": "make_split_dataframe", "args": [ ["const char *", "separator"], ["unsigned int", "col_count"] ], "ret": "void *" },
-    { "name": "make_parse_column", "args": [ ["const char *", "selector"], ["const char *", "key"], ["bool", "impute"] ], "ret": "void *" },
-    { "name": "make_select_column", "args": [ ["const char *", "selector"], ["const char *", "key"] ], "ret": "void *" },
-    { "name": "make_clamp", "args": [ ["const char *", "selector"], ["void *", "lower"], ["void *", "upper"] ], "ret": "void *" },
-    { "name": "make_bounded_sum_l1", "args": [ ["const char *", "selector"], ["void *", "lower"], ["void *", "upper"] ], "ret": "void *" },
-    { "name": "make_bounded_sum_l2", "args": [ ["const char *", "selector"], ["void *", "lower"], ["void *", "upper"] ], "ret": "void *" },
-    { "name": "make_count_l1", "args": [ ["const char *", "selector"] ], "ret": "void *" },
-    { "name": "make_count_l2", "args": [ ["const char *", "selector"] ], "ret": "void *" }
-]
-}"#;
-    util::bootstrap(spec)
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_count_l2").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @primitives)], (ast))
+    })
+}
+
+/// Constructs a transformation that multiplies a scalar by a constant.
+///
+/// # Arguments
+/// * `type_args` - The type of the scalar, like `<f64>`.
+/// * `c` - A pointer to the constant.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_lipschitz_mul(type_args: *const c_char, c: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, c: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + CheckedArithmetic {
+        let c = *util::as_ref(c as *const T)?;
+        let ast = ast.param("c", c);
+        let transformation = trans::make_lipschitz_mul::<T>(c)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_lipschitz_mul").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, c))
+    })
+}
+
+/// Constructs a transformation that adds a constant to a scalar.
+///
+/// # Arguments
+/// * `type_args` - The type of the scalar, like `<f64>`.
+/// * `shift` - A pointer to the constant.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_shift(type_args: *const c_char, shift: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, shift: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + CheckedArithmetic {
+        let shift = *util::as_ref(shift as *const T)?;
+        let ast = ast.param("shift", shift);
+        let transformation = trans::make_shift::<T>(shift)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_shift").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, shift))
+    })
+}
+
+/// Constructs a transformation that maps a scalar `x` to `a * x + b`.
+///
+/// # Arguments
+/// * `type_args` - The type of the scalar, like `<f64>`.
+/// * `a` - A pointer to the scale.
+/// * `b` - A pointer to the shift.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_affine(type_args: *const c_char, a: *const c_void, b: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, a: *const c_void, b: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + CheckedArithmetic {
+        let a = *util::as_ref(a as *const T)?;
+        let b = *util::as_ref(b as *const T)?;
+        let ast = ast.param("a", a).param("b", b);
+        let transformation = trans::make_affine::<T>(a, b)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_affine").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, a, b))
+    })
+}
+
+/// Constructs a transformation that multiplies each element of a bounded vector by a constant.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound of the elements.
+/// * `upper` - A pointer to the upper bound of the elements.
+/// * `c` - A pointer to the constant.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_lipschitz_mul_vec(type_args: *const c_char, lower: *const c_void, upper: *const c_void, c: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void, c: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + CheckedArithmetic {
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let c = *util::as_ref(c as *const T)?;
+        let ast = ast.param("lower", lower).param("upper", upper).param("c", c);
+        let transformation = trans::make_lipschitz_mul_vec::<T>(lower, upper, c)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_lipschitz_mul_vec").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper, c))
+    })
+}
+
+/// Constructs a transformation that adds a constant to each element of a bounded vector.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound of the elements.
+/// * `upper` - A pointer to the upper bound of the elements.
+/// * `shift` - A pointer to the constant.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_shift_vec(type_args: *const c_char, lower: *const c_void, upper: *const c_void, shift: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void, shift: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + CheckedArithmetic {
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let shift = *util::as_ref(shift as *const T)?;
+        let ast = ast.param("lower", lower).param("upper", upper).param("shift", shift);
+        let transformation = trans::make_shift_vec::<T>(lower, upper, shift)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_shift_vec").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper, shift))
+    })
+}
+
+/// Constructs a transformation that maps each element `x` of a bounded vector to `a * x + b`.
+///
+/// # Arguments
+/// * `type_args` - The type of the elements, like `<f64>`.
+/// * `lower` - A pointer to the lower bound of the elements.
+/// * `upper` - A pointer to the upper bound of the elements.
+/// * `a` - A pointer to the scale.
+/// * `b` - A pointer to the shift.
+#[no_mangle]
+pub extern "C" fn opendp_trans__make_affine_vec(type_args: *const c_char, lower: *const c_void, upper: *const c_void, a: *const c_void, b: *const c_void) -> FfiResult<*mut FfiTransformation> {
+    fn monomorphize<T>(ast: Ast, lower: *const c_void, upper: *const c_void, a: *const c_void, b: *const c_void) -> Result<*mut FfiTransformation, Error> where
+        T: 'static + Serialize + CheckedArithmetic {
+        let lower = *util::as_ref(lower as *const T)?;
+        let upper = *util::as_ref(upper as *const T)?;
+        let a = *util::as_ref(a as *const T)?;
+        let b = *util::as_ref(b as *const T)?;
+        let ast = ast.param("lower", lower).param("upper", upper).param("a", a).param("b", b);
+        let transformation = trans::make_affine_vec::<T>(lower, upper, a, b)?;
+        Ok(FfiTransformation::new_from_ast(Some(ast), transformation))
+    }
+    util::ffi_result(|| {
+        let type_args = TypeArgs::parse(type_args, 1)?;
+        let ast = Ast::new("make_affine_vec").type_args(&type_args);
+        dispatch!(monomorphize, [(type_args.0[0], @numbers)], (ast, lower, upper, a, b))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn opendp_trans__bootstrap() -> *mut c_char {
+    util::bootstrap(include_str!(concat!(env!("OUT_DIR"), "/trans.json")))
 }
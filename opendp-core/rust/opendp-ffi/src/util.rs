@@ -4,96 +4,433 @@ use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::ffi::CStr;
 use std::ffi::CString;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::os::raw::c_char;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+use std::sync::{Arc, RwLock};
+
+use opendp::data::Data;
+use serde::de::DeserializeOwned;
 
 #[derive(Debug)]
 pub struct TypeError;
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// The kind of an [`Error`], reported across FFI as the `variant` of an [`FfiError`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    /// A malformed argument at the FFI boundary, like a null pointer or a bad C string.
+    Ffi,
+    /// A type descriptor that couldn't be parsed.
+    TypeParse,
+    /// A type that the function isn't implemented for.
+    UnsupportedType,
+    /// An argument of a different type than the function expects.
+    TypeMismatch,
+    /// An argument that couldn't be parsed from its string form.
+    FailedParse,
+    FailedMembership,
+    InvalidDomain,
+    NotImplemented,
+    InvalidArgument,
+    /// A panic inside the library.
+    Panic,
+}
+
+impl ErrorKind {
+    /// The kind of an error from its `variant`, as reported across FFI.
+    fn from_variant(variant: &str) -> Option<ErrorKind> {
+        let kinds = [ErrorKind::Ffi, ErrorKind::TypeParse, ErrorKind::UnsupportedType, ErrorKind::TypeMismatch, ErrorKind::FailedParse, ErrorKind::FailedMembership, ErrorKind::InvalidDomain, ErrorKind::NotImplemented, ErrorKind::InvalidArgument, ErrorKind::Panic];
+        kinds.iter().copied().find(|kind| format!("{:?}", kind) == variant)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Error { kind, message: message.into() }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl From<opendp::error::Error> for Error {
+    fn from(error: opendp::error::Error) -> Self {
+        let kind = match error {
+            opendp::error::Error::FailedMembership => ErrorKind::FailedMembership,
+            opendp::error::Error::InvalidDomain(_) => ErrorKind::InvalidDomain,
+            opendp::error::Error::NotImplemented(_) => ErrorKind::NotImplemented,
+            opendp::error::Error::InvalidArgument(_) => ErrorKind::InvalidArgument,
+        };
+        Error::new(kind, error.to_string())
+    }
+}
+
+/// An error returned across FFI. Both strings are owned by the error, which must be freed with `opendp_core__error_free`.
+#[repr(C)]
+pub struct FfiError {
+    pub variant: *mut c_char,
+    pub message: *mut c_char,
+}
+
+impl From<Error> for FfiError {
+    fn from(error: Error) -> Self {
+        // Interior NULs would truncate the C string anyway, so drop them rather than fail.
+        let to_c_char_p = |s: String| into_c_char_p(s.replace('\0', ""));
+        FfiError { variant: to_c_char_p(format!("{:?}", error.kind)), message: to_c_char_p(error.message) }
+    }
+}
+
+/// The return value of every fallible FFI function: either the result, or an owned [`FfiError`].
+#[repr(C)]
+#[allow(dead_code)]  // The payloads are only read across FFI.
+pub enum FfiResult<T> {
+    Ok(T),
+    Err(*mut FfiError),
+}
+
+impl<T> FfiResult<T> {
+    /// Converts back to a `Result`, taking ownership of the error, for Rust code that calls the FFI functions.
+    pub fn into_result(self) -> Result<T, Error> {
+        match self {
+            FfiResult::Ok(value) => Ok(value),
+            FfiResult::Err(error) => {
+                let error = into_owned(error)?;
+                let variant = into_string(error.variant)?;
+                let message = into_string(error.message)?;
+                let kind = ErrorKind::from_variant(&variant).unwrap_or(ErrorKind::Ffi);
+                Err(Error::new(kind, message))
+            }
+        }
+    }
+}
+
+impl<T> From<Result<T, Error>> for FfiResult<T> {
+    fn from(result: Result<T, Error>) -> Self {
+        match result {
+            Ok(value) => FfiResult::Ok(value),
+            Err(error) => FfiResult::Err(into_raw(FfiError::from(error))),
+        }
+    }
+}
+
+/// Runs the body of an FFI function, converting both errors and panics into an [`FfiResult`],
+/// so that no panic unwinds across the C boundary.
+pub fn ffi_result<T>(body: impl FnOnce() -> Result<T, Error>) -> FfiResult<T> {
+    let result = panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        let message = payload.downcast_ref::<&str>().map(|e| e.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_owned());
+        Err(Error::new(ErrorKind::Panic, message))
+    });
+    result.into()
+}
+
+/// The unit value for FFI functions that return nothing on success.
+pub fn ffi_unit() -> *mut () {
+    ptr::null_mut()
+}
+
+type DropFn = Arc<dyn Fn(*mut ()) + Send + Sync>;
+type DebugFn = Arc<dyn Fn(*const ()) -> String + Send + Sync>;
+
+/// A type that crosses FFI, identified by its canonical descriptor, like `Vec<i32>` or `(Box<f64>, Box<u32>)`.
+///
+/// Types are interned in a registry, so that a descriptor parsed from a string finds the same type as one constructed in Rust.
+/// Types monomorphized in Rust are registered on first use by [`Type::new`], and pairs of boxed values, which are what
+/// compositions return, are assembled at runtime from their parts by [`Type::new_box_pair`].
+#[derive(Clone)]
 pub struct Type {
-    pub id: TypeId,
+    /// The `TypeId`, for types that were monomorphized in Rust.
+    pub id: Option<TypeId>,
     pub name: &'static str,
     pub descriptor: &'static str,
+    drop: DropFn,
+    debug: Option<DebugFn>,
 }
 
-impl Type {
-    pub fn new<T: 'static>() -> Type {
-        let descriptor = any::type_name::<T>();
-        Self::new_descriptor::<T>(descriptor)
+unsafe fn drop_box<T>(p: *mut ()) {
+    drop(Box::from_raw(p as *mut T))
+}
+
+fn debug_fn<T: 'static + fmt::Debug>() -> DebugFn {
+    Arc::new(|p| format!("{:?}", unsafe { &*(p as *const T) }))
+}
+
+// Types monomorphized in Rust are identified by their `TypeId`. Types assembled at runtime have none, and fall back to their
+// descriptor, so that a pair assembled from its parts matches the same pair monomorphized in Rust.
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.id, other.id) {
+            (Some(id), Some(other_id)) => id == other_id,
+            _ => self.descriptor == other.descriptor,
+        }
     }
+}
+impl Eq for Type {}
+// Equal types always have equal descriptors, since the registry gives each `TypeId` a distinct descriptor.
+impl Hash for Type {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.descriptor.hash(state)
+    }
+}
 
-    pub fn new_descriptor<T: 'static>(descriptor: &'static str) -> Type {
-        let id = TypeId::of::<T>();
-        let name = any::type_name::<T>();
-        Type { id, name, descriptor }
+impl fmt::Debug for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Type({})", self.descriptor)
+    }
+}
+
+impl Type {
+    pub fn new<T: 'static>() -> Type {
+        let registered = TYPE_REGISTRY.read().unwrap().by_id.get(&TypeId::of::<T>()).cloned();
+        registered.unwrap_or_else(|| TYPE_REGISTRY.write().unwrap().register_rust::<T>(None))
     }
 
-    // Hacky special entry point for composition.
+    /// The type of `(Box<T0>, Box<T1>)`, as returned by a composition. Both boxes are erased, so the pair can be dropped
+    /// and formatted from the parts, without monomorphizing the pair itself.
     pub fn new_box_pair(type0: &Type, type1: &Type) -> Type {
-        fn monomorphize<T0: 'static, T1: 'static>(type0: &Type, type1: &Type) -> Type {
-            let descriptor = format!("(Box<{}>, Box<{}>)", type0.descriptor, type1.descriptor);
-            // Hacky way to get &'static str from String.
-            let descriptor = Box::leak(descriptor.into_boxed_str());
-            Type::new_descriptor::<(Box<T0>, Box<T1>)>(descriptor)
+        let descriptor = format!("(Box<{}>, Box<{}>)", type0.descriptor, type1.descriptor);
+        if let Some(type_) = TYPE_REGISTRY.read().unwrap().by_descriptor.get(&descriptor) {
+            return type_.clone()
         }
-        dispatch!(
-            monomorphize,
-            // FIXME: The Box<f64> entries are here for demo use.
-            [(type0, [u32, u64, i32, i64, f32, f64, bool, String, u8, (Box<f64>, Box<f64>)]), (type1, [u32, u64, i32, i64, f32, f64, bool, String, u8, (Box<f64>, Box<f64>)])],
-            (type0, type1)
-        )
+        let descriptor: &'static str = Box::leak(descriptor.into_boxed_str());
+        let (drop0, drop1) = (type0.drop.clone(), type1.drop.clone());
+        let drop: DropFn = Arc::new(move |p| {
+            let (value0, value1) = *unsafe { Box::from_raw(p as *mut (Box<()>, Box<()>)) };
+            drop0(Box::into_raw(value0));
+            drop1(Box::into_raw(value1));
+        });
+        let debug = type0.debug.clone().zip(type1.debug.clone()).map(|(debug0, debug1)| -> DebugFn {
+            Arc::new(move |p| {
+                let (value0, value1) = unsafe { &*(p as *const (Box<()>, Box<()>)) };
+                format!("({}, {})", debug0(&**value0), debug1(&**value1))
+            })
+        });
+        let type_ = Type { id: None, name: descriptor, descriptor, drop, debug };
+        TYPE_REGISTRY.write().unwrap().register(type_)
     }
-}
 
-macro_rules! descriptor_types {
-    ($($type:ty),*) => { vec![$(Type::new_descriptor::<$type>(stringify!($type))),*] }
-}
-lazy_static! {
-    static ref DESCRIPTOR_TO_TYPE: HashMap<String, Type> = {
-        descriptor_types![
-            bool, char, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, String
-        ].into_iter().map(|e| (e.descriptor.to_owned(), e)).collect()
-    };
+    /// Drops a value of this type.
+    ///
+    /// # Safety
+    /// `value` must be the erased pointer of a `Box` of this type, which is consumed.
+    pub unsafe fn drop_value(&self, value: *mut ()) {
+        (self.drop)(value)
+    }
+
+    /// Formats a value of this type with `Debug`, if the type was registered with it.
+    ///
+    /// # Safety
+    /// `value` must point to a value of this type.
+    pub unsafe fn debug_value(&self, value: *const ()) -> Option<String> {
+        self.debug.as_ref().map(|debug| debug(value))
+    }
+
+    fn from_descriptor(descriptor: &TypeDescriptor) -> Result<Type, TypeError> {
+        if let Some(type_) = TYPE_REGISTRY.read().unwrap().by_descriptor.get(&descriptor.to_string()) {
+            return Ok(type_.clone())
+        }
+        // Pairs of boxes can be assembled from their parts, whether or not they were ever monomorphized.
+        let unbox = |descriptor: &TypeDescriptor| match descriptor {
+            TypeDescriptor::Named(name, args) if name == "Box" && args.len() == 1 => Self::from_descriptor(&args[0]),
+            _ => Err(TypeError),
+        };
+        match descriptor {
+            TypeDescriptor::Tuple(elements) if elements.len() == 2 => Ok(Type::new_box_pair(&unbox(&elements[0])?, &unbox(&elements[1])?)),
+            _ => Err(TypeError),
+        }
+    }
 }
 
 impl TryFrom<&str> for Type {
     type Error = TypeError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let type_ = DESCRIPTOR_TO_TYPE.get(value);
-        type_.map(|e| e.clone()).ok_or(TypeError)
+        Self::from_descriptor(&TypeDescriptor::parse(value)?)
+    }
+}
+
+/// The parsed form of a type descriptor, like `HashMap<String, Vec<(f64, f64)>>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeDescriptor {
+    /// A named type with its generic arguments. Only the last segment of a path is kept, so `alloc::vec::Vec<i32>` is `Vec<i32>`.
+    Named(String, Vec<TypeDescriptor>),
+    Tuple(Vec<TypeDescriptor>),
+}
+
+impl TypeDescriptor {
+    pub fn parse(descriptor: &str) -> Result<Self, TypeError> {
+        let mut parser = DescriptorParser { rest: descriptor };
+        let descriptor = parser.parse_type()?;
+        parser.finish()?;
+        Ok(descriptor)
+    }
+}
+
+impl fmt::Display for TypeDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn join(descriptors: &[TypeDescriptor]) -> String {
+            descriptors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", ")
+        }
+        match self {
+            TypeDescriptor::Named(name, args) if args.is_empty() => write!(f, "{}", name),
+            TypeDescriptor::Named(name, args) => write!(f, "{}<{}>", name, join(args)),
+            TypeDescriptor::Tuple(elements) if elements.len() == 1 => write!(f, "({},)", elements[0]),
+            TypeDescriptor::Tuple(elements) => write!(f, "({})", join(elements)),
+        }
+    }
+}
+
+struct DescriptorParser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> DescriptorParser<'a> {
+    fn eat(&mut self, token: char) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => { self.rest = rest; true },
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, token: char) -> Result<(), TypeError> {
+        if self.eat(token) { Ok(()) } else { Err(TypeError) }
+    }
+
+    /// Parses a comma-delimited list up to `close`, returning the elements and whether there was a trailing comma.
+    fn parse_list(&mut self, close: char) -> Result<(Vec<TypeDescriptor>, bool), TypeError> {
+        let mut elements = vec![];
+        let mut trailing_comma = false;
+        while !self.eat(close) {
+            elements.push(self.parse_type()?);
+            trailing_comma = self.eat(',');
+            if !trailing_comma {
+                self.expect(close)?;
+                break
+            }
+        }
+        Ok((elements, trailing_comma))
+    }
+
+    fn parse_type(&mut self) -> Result<TypeDescriptor, TypeError> {
+        if self.eat('(') {
+            let (mut elements, trailing_comma) = self.parse_list(')')?;
+            // Like Rust, `(T)` is just `T`, and a one-tuple needs a trailing comma.
+            return Ok(if elements.len() == 1 && !trailing_comma { elements.remove(0) } else { TypeDescriptor::Tuple(elements) })
+        }
+        self.rest = self.rest.trim_start();
+        let end = self.rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':')).unwrap_or(self.rest.len());
+        let (path, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        let is_ident = |s: &str| s.starts_with(|c: char| c.is_alphabetic() || c == '_') && !s.contains(':');
+        if !path.split("::").all(is_ident) {
+            return Err(TypeError)
+        }
+        let name = path.rsplit("::").next().unwrap().to_owned();
+        let args = if self.eat('<') {
+            let (args, _) = self.parse_list('>')?;
+            if args.is_empty() { return Err(TypeError) }
+            args
+        } else {
+            vec![]
+        };
+        Ok(TypeDescriptor::Named(name, args))
+    }
+
+    fn finish(&mut self) -> Result<(), TypeError> {
+        if self.rest.trim().is_empty() { Ok(()) } else { Err(TypeError) }
     }
 }
 
+#[derive(Default)]
+struct TypeRegistry {
+    by_id: HashMap<TypeId, Type>,
+    by_descriptor: HashMap<String, Type>,
+}
+
+impl TypeRegistry {
+    fn register_rust<T: 'static>(&mut self, debug: Option<DebugFn>) -> Type {
+        let id = TypeId::of::<T>();
+        if let Some(type_) = self.by_id.get(&id) {
+            return type_.clone()
+        }
+        let name = any::type_name::<T>();
+        let mut descriptor = TypeDescriptor::parse(name).map(|e| e.to_string()).unwrap_or_else(|_| name.to_owned());
+        // Types with the same name in different modules fall back to their full path.
+        if self.by_descriptor.get(&descriptor).is_some_and(|e| e.id.is_some_and(|e| e != id)) {
+            descriptor = name.to_owned();
+        }
+        let descriptor = Box::leak(descriptor.into_boxed_str());
+        let drop: DropFn = Arc::new(|p| unsafe { drop_box::<T>(p) });
+        let type_ = Type { id: Some(id), name, descriptor, drop, debug };
+        self.by_id.insert(id, type_.clone());
+        self.by_descriptor.entry(descriptor.to_owned()).or_insert_with(|| type_.clone());
+        type_
+    }
+
+    fn register(&mut self, type_: Type) -> Type {
+        self.by_descriptor.entry(type_.descriptor.to_owned()).or_insert(type_).clone()
+    }
+}
+
+macro_rules! register_debug {
+    ($registry:ident, [$($type:ty),*]) => { $($registry.register_rust::<$type>(Some(debug_fn::<$type>()));)* };
+}
+macro_rules! register_debug_with_compounds {
+    ($registry:ident, [$($type:ty),*]) => { register_debug!($registry, [$($type, Vec<$type>, Option<$type>, ($type, $type)),*]) };
+}
+lazy_static! {
+    static ref TYPE_REGISTRY: RwLock<TypeRegistry> = {
+        let mut registry = TypeRegistry::default();
+        register_debug_with_compounds!(registry, [bool, char, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, String]);
+        register_debug!(registry, [Data, Vec<Data>, Vec<Vec<String>>, HashMap<String, Data>]);
+        RwLock::new(registry)
+    };
+}
+
 #[derive(Debug, Eq, PartialEq, Hash)]
-pub struct TypeArgs(pub(crate) Vec<Type>);
+pub struct TypeArgs(pub Vec<Type>);
 
 impl TypeArgs {
-    pub fn expect(descriptor: *const c_char, count: usize) -> TypeArgs {
-        let descriptor = to_str(descriptor);
-        let type_args: TypeArgs = descriptor.try_into().expect("Bogus type args");
-        assert!(type_args.0.len() == count);
-        type_args
+    pub fn parse(descriptor: *const c_char, count: usize) -> Result<TypeArgs, Error> {
+        let descriptor = to_str(descriptor)?;
+        let type_args: TypeArgs = descriptor.try_into()
+            .map_err(|_| Error::new(ErrorKind::TypeParse, format!("Bogus type args {}", descriptor)))?;
+        if type_args.0.len() != count {
+            return Err(Error::new(ErrorKind::TypeParse, format!("Expected {} type args, got {}", count, descriptor)))
+        }
+        Ok(type_args)
     }
     // pub fn new(args: Vec<Type>) -> TypeArgs {
     //     TypeArgs(args)
     // }
-    // pub fn descriptor(&self) -> String {
-    //     let arg_descriptors: Vec<_> = self.0.iter().map(|e| e.descriptor).collect();
-    //     format!("<{}>", arg_descriptors.join(", "))
-    // }
+    pub fn descriptor(&self) -> String {
+        let arg_descriptors: Vec<_> = self.0.iter().map(|e| e.descriptor).collect();
+        format!("<{}>", arg_descriptors.join(", "))
+    }
 }
 
 impl TryFrom<&str> for TypeArgs {
     type Error = TypeError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.starts_with("<") && value.ends_with(">") {
-            let value = &value[1..value.len()-1];
-            let split = value.split(",");
-            let types: Result<Vec<_>, _> = split.into_iter().map(|e| e.trim().try_into()).collect();
-            Ok(TypeArgs(types?))
-        } else {
-            Err(TypeError)
-        }
+        let mut parser = DescriptorParser { rest: value };
+        parser.expect('<')?;
+        let (args, _) = parser.parse_list('>')?;
+        parser.finish()?;
+        let types: Result<Vec<_>, _> = args.iter().map(Type::from_descriptor).collect();
+        Ok(TypeArgs(types?))
     }
 }
 
@@ -111,14 +448,24 @@ pub fn into_box<T, U>(o: T) -> Box<U> {
 //     o as *mut T
 // }
 
-pub fn into_owned<T>(p: *mut T) -> T {
-    assert!(!p.is_null());
-    *unsafe { Box::<T>::from_raw(p) }
+fn null_pointer<T>() -> Result<T, Error> {
+    Err(Error::new(ErrorKind::Ffi, "Null pointer"))
+}
+
+// The pointer helpers below take pointers passed in by foreign callers, who are responsible for their validity.
+// They check for null but can't check anything else, so they allow `clippy::not_unsafe_ptr_arg_deref` rather than being `unsafe`,
+// which would only move the same unchecked assumption into every entry point.
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn into_owned<T>(p: *mut T) -> Result<T, Error> {
+    if p.is_null() { return null_pointer() }
+    Ok(*unsafe { Box::<T>::from_raw(p) })
 }
 
-pub fn as_ref<'a, T>(p: *const T) -> &'a T {
-    assert!(!p.is_null());
-    unsafe { &*p }
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn as_ref<'a, T>(p: *const T) -> Result<&'a T, Error> {
+    if p.is_null() { return null_pointer() }
+    Ok(unsafe { &*p })
 }
 
 // pub fn as_mut<'a, T>(ptr: *mut T) -> &'a mut T {
@@ -130,28 +477,36 @@ pub fn into_c_char_p(s: String) -> *mut c_char {
     CString::new(s).unwrap().into_raw()
 }
 
-// pub fn into_string(p: *mut c_char) -> String {
-//     assert!(!p.is_null());
-//     let s = unsafe { CString::from_raw(p) };
-//     s.into_string().expect("Bad C string")
-// }
+/// Takes back ownership of a string returned by [`into_c_char_p`].
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn into_string(p: *mut c_char) -> Result<String, Error> {
+    if p.is_null() { return null_pointer() }
+    let s = unsafe { CString::from_raw(p) };
+    s.into_string().map_err(|_| Error::new(ErrorKind::Ffi, "Bad C string"))
+}
 
-pub fn to_str<'a>(p: *const c_char) -> &'a str {
-    assert!(!p.is_null());
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn to_str<'a>(p: *const c_char) -> Result<&'a str, Error> {
+    if p.is_null() { return null_pointer() }
     let s = unsafe { CStr::from_ptr(p) };
-    s.to_str().expect("Bad C string")
+    s.to_str().map_err(|_| Error::new(ErrorKind::Ffi, "Bad C string"))
 }
 
-pub fn to_option_str<'a>(p: *const c_char) -> Option<&'a str> {
+pub fn to_option_str<'a>(p: *const c_char) -> Result<Option<&'a str>, Error> {
     if !p.is_null() {
-        Some(to_str(p))
+        to_str(p).map(Some)
     } else {
-        None
+        Ok(None)
     }
 }
 
-pub fn bootstrap(spec: &str) -> *const c_char {
-    // FIXME: Leaks string.
+/// Parses a list of values serialized as a JSON array, like the candidates or edges of a constructor.
+pub fn parse_list<T: DeserializeOwned>(p: *const c_char, name: &str) -> Result<Vec<T>, Error> {
+    serde_json::from_str(to_str(p)?).map_err(|e| Error::new(ErrorKind::FailedParse, format!("Bogus {}: {}", name, e)))
+}
+
+/// Returns the bootstrap metadata of a module, which the caller must free with `opendp_data__str_free()`.
+pub fn bootstrap(spec: &str) -> *mut c_char {
     into_c_char_p(spec.to_owned())
 }
 
@@ -162,9 +517,16 @@ pub fn to_bool(b: c_bool) -> bool {
     if b != 0 { true } else { false }
 }
 
+pub fn from_bool(b: bool) -> c_bool {
+    if b { 1 } else { 0 }
+}
+
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
     use super::*;
 
     #[test]
@@ -174,10 +536,108 @@ mod tests {
         assert_eq!(parsed, explicit);
     }
 
+    #[test]
+    fn test_ffi_result() {
+        let result = ffi_result(|| -> Result<i32, Error> { panic!("boom") });
+        match result {
+            FfiResult::Ok(_) => panic!("expected an error"),
+            FfiResult::Err(error) => {
+                let error_ref = as_ref(error).unwrap();
+                assert_eq!(to_str(error_ref.variant).unwrap(), "Panic");
+                assert_eq!(to_str(error_ref.message).unwrap(), "boom");
+                crate::core::opendp_core__error_free(error);
+            }
+        }
+        let type_args = CString::new("<bogus>").unwrap();
+        let error = TypeArgs::parse(type_args.as_ptr(), 1).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TypeParse);
+        let result: FfiResult<()> = Err(error.clone()).into();
+        assert_eq!(result.into_result(), Err(error));
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let list = CString::new(r#"["a,b", "c"]"#).unwrap();
+        assert_eq!(parse_list::<String>(list.as_ptr(), "keys"), Ok(vec!["a,b".to_owned(), "c".to_owned()]));
+        assert_eq!(parse_list::<i32>(list.as_ptr(), "edges").unwrap_err().kind, ErrorKind::FailedParse);
+        let list = CString::new("a,b").unwrap();
+        assert_eq!(parse_list::<String>(list.as_ptr(), "keys").unwrap_err().kind, ErrorKind::FailedParse);
+    }
+
+    #[test]
+    fn test_type_descriptor() {
+        let parsed: Type = "String".try_into().unwrap();
+        let explicit = Type::new::<String>();
+        assert_eq!(parsed, explicit);
+    }
+
     #[test]
     fn test_type_args() {
         let parsed: TypeArgs = "<i32, f32>".try_into().unwrap();
         let explicit = TypeArgs(vec![Type::new::<i32>(), Type::new::<f32>()]);
         assert_eq!(parsed, explicit);
+        let parsed: TypeArgs = "<Vec<i32>, HashMap<String, Data>>".try_into().unwrap();
+        let explicit = TypeArgs(vec![Type::new::<Vec<i32>>(), Type::new::<HashMap<String, Data>>()]);
+        assert_eq!(parsed, explicit);
+        let parsed: TypeArgs = "<(f64, f64), Option<u8>>".try_into().unwrap();
+        let explicit = TypeArgs(vec![Type::new::<(f64, f64)>(), Type::new::<Option<u8>>()]);
+        assert_eq!(parsed, explicit);
+        assert_eq!(parsed.descriptor(), "<(f64, f64), Option<u8>>");
+        assert!(TypeArgs::try_from("<i32, Vec<i32>").is_err());
+    }
+
+    #[test]
+    fn test_type_descriptor_parse() {
+        let parsed = TypeDescriptor::parse(" std::collections::HashMap< alloc::string::String,Vec<(f64, (u8,))> >").unwrap();
+        assert_eq!(parsed.to_string(), "HashMap<String, Vec<(f64, (u8,))>>");
+        assert_eq!(TypeDescriptor::parse("(i32)").unwrap().to_string(), "i32");
+        assert_eq!(TypeDescriptor::parse("()").unwrap(), TypeDescriptor::Tuple(vec![]));
+        for bogus in ["", "Vec<>", "Vec<i32", "i32>", "(i32, f64", "a:b", "3d", "i32 f64"].iter() {
+            assert!(TypeDescriptor::parse(bogus).is_err(), "{:?} should not parse", bogus);
+        }
+    }
+
+    #[test]
+    fn test_type_registry() {
+        assert_eq!(Type::new::<HashMap<String, Data>>().descriptor, "HashMap<String, Data>");
+        assert_eq!(Type::new::<Vec<(f64, Option<i32>)>>().descriptor, "Vec<(f64, Option<i32>)>");
+        // A type registered in Rust can then be parsed.
+        assert!(Type::try_from("Vec<(u16, u16, u16)>").is_err());
+        let explicit = Type::new::<Vec<(u16, u16, u16)>>();
+        assert_eq!(Type::try_from("Vec<(u16, u16, u16)>").unwrap(), explicit);
+    }
+
+    #[test]
+    fn test_type_eq() {
+        let explicit = Type::new::<i32>();
+        let other_id = Type { id: Some(TypeId::of::<u32>()), ..explicit.clone() };
+        assert_ne!(explicit, other_id);
+        let assembled = Type { id: None, ..explicit.clone() };
+        assert_eq!(explicit, assembled);
+    }
+
+    #[test]
+    fn test_type_box_pair() {
+        struct DropFlag(Rc<Cell<bool>>);
+        impl Drop for DropFlag {
+            fn drop(&mut self) { self.0.set(true) }
+        }
+
+        let parsed = Type::try_from("(Box<i32>, Box<(Box<f64>, Box<String>)>)").unwrap();
+        let inner = Type::new_box_pair(&Type::new::<f64>(), &Type::new::<String>());
+        assert_eq!(parsed, Type::new_box_pair(&Type::new::<i32>(), &inner));
+        assert_eq!(parsed, Type::new::<(Box<i32>, Box<(Box<f64>, Box<String>)>)>());
+
+        let value = (Box::new(1_i32), Box::new((Box::new(2.0_f64), Box::new("a".to_owned()))));
+        let value = into_raw(value) as *mut ();
+        assert_eq!(unsafe { parsed.debug_value(value) }, Some(r#"(1, (2.0, "a"))"#.to_owned()));
+        unsafe { parsed.drop_value(value) };
+
+        let dropped = Rc::new(Cell::new(false));
+        let pair = Type::new_box_pair(&Type::new::<DropFlag>(), &Type::new::<i32>());
+        let value: (Box<DropFlag>, Box<i32>) = (Box::new(DropFlag(dropped.clone())), Box::new(1));
+        assert_eq!(unsafe { pair.debug_value(&value as *const _ as *const ()) }, None);
+        unsafe { pair.drop_value(into_raw(value) as *mut ()) };
+        assert!(dropped.get());
     }
 }
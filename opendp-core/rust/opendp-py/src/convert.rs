@@ -0,0 +1,95 @@
+use std::convert::TryFrom;
+use std::os::raw::c_void;
+
+use opendp_ffi::core::FfiObject;
+use opendp_ffi::data as ffi_data;
+use opendp_ffi::util::{Type, TypeDescriptor};
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use pyo3::IntoPyObjectExt;
+
+/// An owned object, converted from or to a Python value.
+pub struct Object(pub *mut FfiObject);
+
+impl Object {
+    /// Converts a Python value to an object of the given type.
+    pub fn from_py(value: &Bound<'_, PyAny>, type_: &Type) -> PyResult<Self> {
+        // Numeric vectors are copied straight from the buffer, if there is one, and otherwise extracted from the sequence.
+        macro_rules! from_py {
+            ([$($scalar:ty),*], [$($numeric:ty),*], [$($vector:ty),*]) => {
+                $(if type_ == &Type::new::<$scalar>() {
+                    return Ok(Object(FfiObject::new(value.extract::<$scalar>()?)))
+                })*
+                $(if type_ == &Type::new::<Vec<$numeric>>() {
+                    return Ok(Object(FfiObject::new(extract_buffer::<$numeric>(value)?.map_or_else(|| value.extract::<Vec<$numeric>>(), Ok)?)))
+                })*
+                $(if type_ == &Type::new::<$vector>() {
+                    return Ok(Object(FfiObject::new(value.extract::<$vector>()?)))
+                })*
+            };
+        }
+        from_py!([i32, i64, u32, u64, f32, f64, bool, String], [i32, i64, u32, u64, f32, f64], [Vec<bool>, Vec<String>]);
+        Err(PyTypeError::new_err(format!("Unsupported type {}", type_.descriptor)))
+    }
+
+    /// A pointer to the value, for passing to an FFI constructor in place of a `*const c_void`.
+    pub fn as_ptr(&self) -> *const c_void {
+        unsafe { (*self.0).as_erased() as *const () as *const c_void }
+    }
+
+    /// Converts the object to a Python value.
+    pub fn to_py(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let object = unsafe { &*self.0 };
+        unsafe { value_to_py(py, &object.type_, object.as_erased()) }
+    }
+}
+
+impl Drop for Object {
+    fn drop(&mut self) {
+        ffi_data::opendp_data__data_free(self.0);
+    }
+}
+
+/// Copies a one-dimensional buffer of `T`, or returns `None` if `value` doesn't support the buffer protocol with that item type.
+fn extract_buffer<T: pyo3::buffer::Element + Copy>(value: &Bound<'_, PyAny>) -> PyResult<Option<Vec<T>>> {
+    let buffer = match PyBuffer::<T>::get(value) {
+        Ok(buffer) => buffer,
+        Err(_) => return Ok(None),
+    };
+    if buffer.dimensions() != 1 {
+        return Err(PyTypeError::new_err(format!("Expected a one-dimensional buffer, got {} dimensions", buffer.dimensions())))
+    }
+    buffer.to_vec(value.py()).map(Some)
+}
+
+/// Converts a value of the given type to a Python value.
+///
+/// # Safety
+/// `value` must point to a value of type `type_`.
+unsafe fn value_to_py(py: Python<'_>, type_: &Type, value: *const ()) -> PyResult<Py<PyAny>> {
+    macro_rules! to_py {
+        ($($type:ty),*) => {
+            $(if type_ == &Type::new::<$type>() {
+                return (*(value as *const $type)).clone().into_py_any(py)
+            })*
+        };
+    }
+    to_py!(i32, i64, u32, u64, f32, f64, bool, String);
+    to_py!(Vec<i32>, Vec<i64>, Vec<u32>, Vec<u64>, Vec<f32>, Vec<f64>, Vec<bool>, Vec<String>, Vec<Vec<String>>);
+    // The pairs returned by compositions are converted to tuples, from their parts.
+    if let Ok(TypeDescriptor::Tuple(elements)) = TypeDescriptor::parse(type_.descriptor) {
+        let unbox = |descriptor: &TypeDescriptor| match descriptor {
+            TypeDescriptor::Named(name, args) if name == "Box" && args.len() == 1 => Type::try_from(args[0].to_string().as_str()).ok(),
+            _ => None,
+        };
+        if let [Some(type0), Some(type1)] = elements.iter().map(unbox).collect::<Vec<_>>().as_slice() {
+            let (value0, value1) = &*(value as *const (Box<()>, Box<()>));
+            let element0 = value_to_py(py, type0, &**value0)?;
+            let element1 = value_to_py(py, type1, &**value1)?;
+            return PyTuple::new(py, [element0, element1])?.into_py_any(py)
+        }
+    }
+    Err(PyTypeError::new_err(format!("Unsupported type {}", type_.descriptor)))
+}
@@ -0,0 +1,222 @@
+use std::ffi::CString;
+
+use opendp_ffi::core::{self as ffi_core, FfiMeasurement, FfiObject, FfiTransformation};
+use opendp_ffi::data as ffi_data;
+use opendp_ffi::util::{self, FfiResult, Type, TypeArgs};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+use crate::convert::Object;
+
+create_exception!(opendp_py, OpenDPError, PyException, "An error returned by OpenDP.");
+
+/// Converts an error from the FFI layer into a Python exception.
+pub fn to_py_err(error: util::Error) -> PyErr {
+    OpenDPError::new_err(error.to_string())
+}
+
+/// Unwraps the result of an FFI function, raising its error as an [`OpenDPError`].
+pub fn unwrap<T>(result: FfiResult<T>) -> PyResult<T> {
+    match result {
+        FfiResult::Ok(ok) => Ok(ok),
+        FfiResult::Err(err) => {
+            let message = {
+                let err = util::as_ref(err).map_err(to_py_err)?;
+                let variant = util::to_str(err.variant).map_err(to_py_err)?;
+                let message = util::to_str(err.message).map_err(to_py_err)?;
+                format!("{}: {}", variant, message)
+            };
+            ffi_core::opendp_core__error_free(err);
+            Err(OpenDPError::new_err(message))
+        }
+    }
+}
+
+pub fn to_c_string(s: &str) -> PyResult<CString> {
+    CString::new(s).map_err(|_| PyValueError::new_err("String contains a null byte"))
+}
+
+/// Parses type arguments like `"<f64>"`, returning them along with the C string to pass to the FFI constructor.
+pub fn parse_type_args(type_args: &str, count: usize) -> PyResult<(CString, Vec<Type>)> {
+    let type_args = to_c_string(type_args)?;
+    let types = TypeArgs::parse(type_args.as_ptr(), count).map_err(to_py_err)?;
+    Ok((type_args, types.0))
+}
+
+/// Serializes the elements of a Python sequence as a JSON array, for the FFI constructors that take lists.
+pub fn list_to_json(values: &Bound<'_, PyAny>) -> PyResult<CString> {
+    let elements = values.try_iter()?.collect::<PyResult<Vec<_>>>()?;
+    py_to_json(PyList::new(values.py(), elements)?.as_any())
+}
+
+/// Parses a JSON string returned by the FFI layer into Python objects, taking ownership of the string.
+fn json_to_py(py: Python<'_>, json: *mut std::os::raw::c_char) -> PyResult<Py<PyAny>> {
+    let json = util::into_string(json).map_err(to_py_err)?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Serializes Python objects as JSON, for the FFI functions that take it.
+fn py_to_json(value: &Bound<'_, PyAny>) -> PyResult<CString> {
+    let json: String = value.py().import("json")?.call_method1("dumps", (value,))?.extract()?;
+    to_c_string(&json)
+}
+
+/// The domain of the input or output of a transformation, as used by the dataframe constructors.
+#[pyclass(unsendable, module = "opendp_py")]
+pub struct Domain(pub *mut FfiObject);
+
+impl Drop for Domain {
+    fn drop(&mut self) {
+        ffi_data::opendp_data__data_free(self.0);
+    }
+}
+
+#[pyclass(unsendable, module = "opendp_py")]
+pub struct Transformation(pub *mut FfiTransformation);
+
+impl Transformation {
+    pub fn new(result: FfiResult<*mut FfiTransformation>) -> PyResult<Self> {
+        unwrap(result).map(Transformation)
+    }
+
+    fn as_ffi(&self) -> &FfiTransformation {
+        unsafe { &*self.0 }
+    }
+}
+
+impl Drop for Transformation {
+    fn drop(&mut self) {
+        ffi_core::opendp_core__transformation_free(self.0);
+    }
+}
+
+#[pymethods]
+impl Transformation {
+    /// Applies the transformation to an argument of its input carrier type.
+    fn invoke(&self, py: Python<'_>, arg: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let arg = Object::from_py(arg, &self.as_ffi().input_glue.domain_carrier)?;
+        let res = Object(unwrap(ffi_core::opendp_core__transformation_invoke(self.0, arg.0))?);
+        res.to_py(py)
+    }
+
+    fn __call__(&self, py: Python<'_>, arg: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        self.invoke(py, arg)
+    }
+
+    /// Checks whether the transformation is `d_out`-stable for inputs that are `d_in`-close.
+    fn check(&self, d_in: &Bound<'_, PyAny>, d_out: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let d_in = Object::from_py(d_in, &self.as_ffi().input_glue.metric_distance)?;
+        let d_out = Object::from_py(d_out, &self.as_ffi().output_glue.metric_distance)?;
+        unwrap(ffi_core::opendp_core__transformation_check(self.0, d_in.0, d_out.0)).map(util::to_bool)
+    }
+
+    /// Returns a description of the domains and metrics of the transformation.
+    fn describe(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        json_to_py(py, unwrap(ffi_core::opendp_core__transformation_describe(self.0))?)
+    }
+
+    /// Returns the AST of the constructors and combinators that made the transformation.
+    fn to_ast(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        json_to_py(py, unwrap(ffi_core::opendp_core__transformation_to_ast(self.0))?)
+    }
+
+    /// Rebuilds a transformation from its AST, as returned by `to_ast()`.
+    #[staticmethod]
+    fn from_ast(ast: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let ast = py_to_json(ast)?;
+        Transformation::new(ffi_core::opendp_core__transformation_from_ast(ast.as_ptr()))
+    }
+
+    #[getter]
+    fn output_domain(&self) -> PyResult<Domain> {
+        unwrap(ffi_core::opendp_core__transformation_output_domain(self.0)).map(Domain)
+    }
+
+    /// Chains a transformation or measurement after this one.
+    fn __rshift__(&self, py: Python<'_>, other: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        if let Ok(transformation1) = other.extract::<PyRef<'_, Transformation>>() {
+            Ok(make_chain_tt(&transformation1, self)?.into_pyobject(py)?.into_any().unbind())
+        } else if let Ok(measurement1) = other.extract::<PyRef<'_, Measurement>>() {
+            Ok(make_chain_mt(&measurement1, self)?.into_pyobject(py)?.into_any().unbind())
+        } else {
+            Ok(py.NotImplemented())
+        }
+    }
+}
+
+#[pyclass(unsendable, module = "opendp_py")]
+pub struct Measurement(pub *mut FfiMeasurement);
+
+impl Measurement {
+    pub fn new(result: FfiResult<*mut FfiMeasurement>) -> PyResult<Self> {
+        unwrap(result).map(Measurement)
+    }
+
+    fn as_ffi(&self) -> &FfiMeasurement {
+        unsafe { &*self.0 }
+    }
+}
+
+impl Drop for Measurement {
+    fn drop(&mut self) {
+        ffi_core::opendp_core__measurement_free(self.0);
+    }
+}
+
+#[pymethods]
+impl Measurement {
+    /// Applies the measurement to an argument of its input carrier type.
+    fn invoke(&self, py: Python<'_>, arg: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let arg = Object::from_py(arg, &self.as_ffi().input_glue.domain_carrier)?;
+        let res = Object(unwrap(ffi_core::opendp_core__measurement_invoke(self.0, arg.0))?);
+        res.to_py(py)
+    }
+
+    fn __call__(&self, py: Python<'_>, arg: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        self.invoke(py, arg)
+    }
+
+    /// Checks whether the measurement is `d_out`-private for inputs that are `d_in`-close.
+    fn check(&self, d_in: &Bound<'_, PyAny>, d_out: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let d_in = Object::from_py(d_in, &self.as_ffi().input_glue.metric_distance)?;
+        let d_out = Object::from_py(d_out, &self.as_ffi().output_glue.measure_distance)?;
+        unwrap(ffi_core::opendp_core__measurement_check(self.0, d_in.0, d_out.0)).map(util::to_bool)
+    }
+
+    /// Returns a description of the domains, metric and measure of the measurement.
+    fn describe(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        json_to_py(py, unwrap(ffi_core::opendp_core__measurement_describe(self.0))?)
+    }
+
+    /// Returns the AST of the constructors and combinators that made the measurement.
+    fn to_ast(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        json_to_py(py, unwrap(ffi_core::opendp_core__measurement_to_ast(self.0))?)
+    }
+
+    /// Rebuilds a measurement from its AST, as returned by `to_ast()`.
+    #[staticmethod]
+    fn from_ast(ast: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let ast = py_to_json(ast)?;
+        Measurement::new(ffi_core::opendp_core__measurement_from_ast(ast.as_ptr()))
+    }
+}
+
+/// Chains two transformations, applying `transformation0` first.
+#[pyfunction]
+pub fn make_chain_tt(transformation1: &Transformation, transformation0: &Transformation) -> PyResult<Transformation> {
+    Transformation::new(ffi_core::opendp_core__make_chain_tt(transformation1.0, transformation0.0))
+}
+
+/// Chains a measurement after a transformation.
+#[pyfunction]
+pub fn make_chain_mt(measurement1: &Measurement, transformation0: &Transformation) -> PyResult<Measurement> {
+    Measurement::new(ffi_core::opendp_core__make_chain_mt(measurement1.0, transformation0.0))
+}
+
+/// Composes two measurements on the same input, returning a pair of their outputs.
+#[pyfunction]
+pub fn make_composition(measurement0: &Measurement, measurement1: &Measurement) -> PyResult<Measurement> {
+    Measurement::new(ffi_core::opendp_core__make_composition(measurement0.0, measurement1.0))
+}
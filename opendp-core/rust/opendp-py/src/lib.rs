@@ -0,0 +1,60 @@
+//! Native Python bindings for OpenDP.
+//!
+//! # Overview
+//!
+//! This crate is an alternative to the ctypes layer in `opendp.py`. Rather than marshalling `void *` by hand, it wraps the
+//! erased types of [`opendp_ffi`] in Python classes, and converts arguments and results to and from native Python values.
+//!
+//! ```python
+//! import opendp_py as odp
+//!
+//! clamp = odp.trans.make_clamp("<f64>", 0.0, 10.0)
+//! bounded_sum = odp.trans.make_bounded_sum_l1("<f64>", 0.0, 10.0)
+//! base_laplace = odp.meas.make_base_laplace("<f64>", 1.0)
+//! noisy_sum = clamp >> bounded_sum >> base_laplace
+//! noisy_sum([1.0, 2.0, 3.0])
+//! ```
+//!
+//! Constructors take the same arguments as the FFI functions they call, with the type arguments as a string, and with values in place
+//! of pointers. Chaining with `>>` applies the left operand first, so `t0 >> t1` is `make_chain_tt(t1, t0)`, and `t >> m` is
+//! `make_chain_mt(m, t)`. Errors from Rust are raised as `OpenDPError`.
+//!
+//! Arguments of vector types accept any sequence, and any one-dimensional object supporting the buffer protocol (like a numpy
+//! array), which is copied without going through Python objects.
+//!
+//! # Building
+//!
+//! The crate isn't part of the Rust workspace, because it needs a Python toolchain. To build it into the current virtualenv and run
+//! the tests:
+//! ```text
+//! cd opendp-core/rust/opendp-py
+//! maturin develop
+//! pytest tests
+//! ```
+
+use pyo3::prelude::*;
+
+mod convert;
+mod core;
+mod meas;
+mod trans;
+
+#[pymodule]
+fn opendp_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("OpenDPError", m.py().get_type::<core::OpenDPError>())?;
+    m.add_class::<core::Domain>()?;
+    m.add_class::<core::Measurement>()?;
+    m.add_class::<core::Transformation>()?;
+    m.add_function(wrap_pyfunction!(core::make_chain_mt, m)?)?;
+    m.add_function(wrap_pyfunction!(core::make_chain_tt, m)?)?;
+    m.add_function(wrap_pyfunction!(core::make_composition, m)?)?;
+
+    let trans = PyModule::new(m.py(), "trans")?;
+    trans::register(&trans)?;
+    m.add_submodule(&trans)?;
+
+    let meas = PyModule::new(m.py(), "meas")?;
+    meas::register(&meas)?;
+    m.add_submodule(&meas)?;
+    Ok(())
+}
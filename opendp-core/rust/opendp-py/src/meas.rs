@@ -0,0 +1,43 @@
+use opendp_ffi::meas as ffi_meas;
+use pyo3::prelude::*;
+
+use crate::convert::Object;
+use crate::core::{list_to_json, parse_type_args, Measurement};
+
+/// Constructs a measurement that adds Laplace noise to a scalar.
+#[pyfunction]
+fn make_base_laplace(type_args: &str, sigma: f64) -> PyResult<Measurement> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Measurement::new(ffi_meas::opendp_meas__make_base_laplace(type_args.as_ptr(), sigma))
+}
+
+/// Constructs a measurement that adds Laplace noise to each element of a vector.
+#[pyfunction]
+fn make_base_laplace_vec(type_args: &str, sigma: f64) -> PyResult<Measurement> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Measurement::new(ffi_meas::opendp_meas__make_base_laplace_vec(type_args.as_ptr(), sigma))
+}
+
+/// Constructs a measurement that adds Gaussian noise to a scalar.
+#[pyfunction]
+fn make_base_gaussian(type_args: &str, sigma: f64) -> PyResult<Measurement> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Measurement::new(ffi_meas::opendp_meas__make_base_gaussian(type_args.as_ptr(), sigma))
+}
+
+/// Constructs a measurement that releases a quantile with the exponential mechanism.
+#[pyfunction]
+fn make_private_quantile(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>, candidates: &Bound<'_, PyAny>, alpha: f64, scale: f64) -> PyResult<Measurement> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?);
+    let candidates = list_to_json(candidates)?;
+    Measurement::new(ffi_meas::opendp_meas__make_private_quantile(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr(), candidates.as_ptr(), alpha, scale))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(make_base_laplace, m)?)?;
+    m.add_function(wrap_pyfunction!(make_base_laplace_vec, m)?)?;
+    m.add_function(wrap_pyfunction!(make_base_gaussian, m)?)?;
+    m.add_function(wrap_pyfunction!(make_private_quantile, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,344 @@
+use std::os::raw::c_char;
+use std::ptr;
+
+use opendp_ffi::trans as ffi_trans;
+use opendp_ffi::util;
+use pyo3::prelude::*;
+
+use crate::convert::Object;
+use crate::core::{list_to_json, parse_type_args, to_c_string, Domain, Transformation};
+
+/// A C string for an optional argument, which is passed as null when missing.
+fn option_as_ptr(s: &Option<std::ffi::CString>) -> *const c_char {
+    s.as_ref().map_or(ptr::null(), |s| s.as_ptr())
+}
+
+fn to_option_c_string(s: Option<&str>) -> PyResult<Option<std::ffi::CString>> {
+    s.map(to_c_string).transpose()
+}
+
+/// Constructs a transformation that returns its input.
+#[pyfunction]
+fn make_identity(type_args: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Transformation::new(ffi_trans::opendp_trans__make_identity(type_args.as_ptr()))
+}
+
+/// Constructs a transformation that splits a string into lines.
+#[pyfunction]
+fn make_split_lines() -> PyResult<Transformation> {
+    Transformation::new(ffi_trans::opendp_trans__make_split_lines())
+}
+
+/// Constructs a transformation that parses a vector of strings.
+#[pyfunction]
+#[pyo3(signature = (type_args, impute=false))]
+fn make_parse_series(type_args: &str, impute: bool) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Transformation::new(ffi_trans::opendp_trans__make_parse_series(type_args.as_ptr(), util::from_bool(impute)))
+}
+
+/// Constructs a transformation that casts the elements of a vector, replacing failed casts with a default value.
+#[pyfunction]
+fn make_cast_default(type_args: &str, default: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 2)?;
+    let default = Object::from_py(default, &types[1])?;
+    Transformation::new(ffi_trans::opendp_trans__make_cast_default(type_args.as_ptr(), default.as_ptr()))
+}
+
+/// Constructs a transformation that casts the elements of a vector, replacing failed casts with the type's inherent null value.
+#[pyfunction]
+fn make_cast_inherent(type_args: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 2)?;
+    Transformation::new(ffi_trans::opendp_trans__make_cast_inherent(type_args.as_ptr()))
+}
+
+/// Constructs a transformation that compares each element of a vector to a value.
+#[pyfunction]
+fn make_is_equal(type_args: &str, value: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let value = Object::from_py(value, &types[0])?;
+    Transformation::new(ffi_trans::opendp_trans__make_is_equal(type_args.as_ptr(), value.as_ptr()))
+}
+
+/// Constructs a transformation that splits each line into fields.
+#[pyfunction]
+#[pyo3(signature = (separator=None))]
+fn make_split_records(separator: Option<&str>) -> PyResult<Transformation> {
+    let separator = to_option_c_string(separator)?;
+    Transformation::new(ffi_trans::opendp_trans__make_split_records(option_as_ptr(&separator)))
+}
+
+/// Constructs a transformation that turns records into a dataframe with numbered columns.
+#[pyfunction]
+fn make_create_dataframe(col_count: u32) -> PyResult<Transformation> {
+    Transformation::new(ffi_trans::opendp_trans__make_create_dataframe(col_count))
+}
+
+/// Constructs a transformation that splits a string into a dataframe with numbered columns.
+#[pyfunction]
+#[pyo3(signature = (separator, col_count))]
+fn make_split_dataframe(separator: Option<&str>, col_count: u32) -> PyResult<Transformation> {
+    let separator = to_option_c_string(separator)?;
+    Transformation::new(ffi_trans::opendp_trans__make_split_dataframe(option_as_ptr(&separator), col_count))
+}
+
+/// Constructs a transformation that parses CSV into a dataframe of string columns.
+#[pyfunction]
+#[pyo3(signature = (keys, separator=None, quote=None, escape=None, has_header=false))]
+fn make_split_csv(keys: &Bound<'_, PyAny>, separator: Option<&str>, quote: Option<&str>, escape: Option<&str>, has_header: bool) -> PyResult<Transformation> {
+    let keys = list_to_json(keys)?;
+    let (separator, quote, escape) = (to_option_c_string(separator)?, to_option_c_string(quote)?, to_option_c_string(escape)?);
+    Transformation::new(ffi_trans::opendp_trans__make_split_csv(keys.as_ptr(), option_as_ptr(&separator), option_as_ptr(&quote), option_as_ptr(&escape), util::from_bool(has_header)))
+}
+
+/// Constructs a transformation that parses JSON lines into a dataframe.
+#[pyfunction]
+fn make_parse_jsonl(type_args: &str, keys: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let keys = list_to_json(keys)?;
+    let type_args = to_c_string(type_args)?;
+    Transformation::new(ffi_trans::opendp_trans__make_parse_jsonl(type_args.as_ptr(), keys.as_ptr()))
+}
+
+/// Constructs a transformation that parses one column of a dataframe.
+#[pyfunction]
+#[pyo3(signature = (type_args, input_domain, key, impute=false))]
+fn make_parse_column(type_args: &str, input_domain: &Domain, key: &str, impute: bool) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let key = to_c_string(key)?;
+    Transformation::new(ffi_trans::opendp_trans__make_parse_column(type_args.as_ptr(), input_domain.0, key.as_ptr(), util::from_bool(impute)))
+}
+
+/// Constructs a transformation that selects one column of a dataframe.
+#[pyfunction]
+fn make_select_column(type_args: &str, input_domain: &Domain, key: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let key = to_c_string(key)?;
+    Transformation::new(ffi_trans::opendp_trans__make_select_column(type_args.as_ptr(), input_domain.0, key.as_ptr()))
+}
+
+/// Constructs a transformation that selects one column of a dataframe, with symmetric distance.
+#[pyfunction]
+fn make_select_column_symmetric(type_args: &str, input_domain: &Domain, key: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let key = to_c_string(key)?;
+    Transformation::new(ffi_trans::opendp_trans__make_select_column_symmetric(type_args.as_ptr(), input_domain.0, key.as_ptr()))
+}
+
+/// Constructs a transformation that selects one column of a dataframe, whose elements may be null.
+#[pyfunction]
+fn make_select_column_option(type_args: &str, input_domain: &Domain, key: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let key = to_c_string(key)?;
+    Transformation::new(ffi_trans::opendp_trans__make_select_column_option(type_args.as_ptr(), input_domain.0, key.as_ptr()))
+}
+
+/// Constructs a transformation that keeps the elements of a vector that satisfy a predicate, given as JSON.
+#[pyfunction]
+fn make_filter(type_args: &str, predicate: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let predicate = to_c_string(predicate)?;
+    Transformation::new(ffi_trans::opendp_trans__make_filter(type_args.as_ptr(), predicate.as_ptr()))
+}
+
+/// Constructs a transformation that converts Hamming distance on a vector to symmetric distance.
+#[pyfunction]
+fn make_hamming_to_symmetric(type_args: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Transformation::new(ffi_trans::opendp_trans__make_hamming_to_symmetric(type_args.as_ptr()))
+}
+
+/// Constructs a transformation that converts Hamming distance on a vector of nullable elements to symmetric distance.
+#[pyfunction]
+fn make_hamming_to_symmetric_nullable(type_args: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Transformation::new(ffi_trans::opendp_trans__make_hamming_to_symmetric_nullable(type_args.as_ptr()))
+}
+
+/// Constructs a transformation that converts Hamming distance on a dataframe to symmetric distance.
+#[pyfunction]
+fn make_hamming_to_symmetric_dataframe(input_domain: &Domain) -> PyResult<Transformation> {
+    Transformation::new(ffi_trans::opendp_trans__make_hamming_to_symmetric_dataframe(input_domain.0))
+}
+
+/// Constructs a transformation that keeps the rows of a dataframe whose column satisfies a predicate, given as JSON.
+#[pyfunction]
+fn make_filter_dataframe(type_args: &str, input_domain: &Domain, key: &str, predicate: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let (key, predicate) = (to_c_string(key)?, to_c_string(predicate)?);
+    Transformation::new(ffi_trans::opendp_trans__make_filter_dataframe(type_args.as_ptr(), input_domain.0, key.as_ptr(), predicate.as_ptr()))
+}
+
+/// Constructs a transformation that converts Hamming distance on a dataframe to user distance.
+#[pyfunction]
+fn make_hamming_to_user(input_domain: &Domain) -> PyResult<Transformation> {
+    Transformation::new(ffi_trans::opendp_trans__make_hamming_to_user(input_domain.0))
+}
+
+/// Constructs a transformation that keeps at most `k` rows of a dataframe for each user.
+#[pyfunction]
+fn make_bound_user_contributions(type_args: &str, input_domain: &Domain, id_key: &str, k: u32) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let id_key = to_c_string(id_key)?;
+    Transformation::new(ffi_trans::opendp_trans__make_bound_user_contributions(type_args.as_ptr(), input_domain.0, id_key.as_ptr(), k))
+}
+
+/// Constructs a transformation that clamps the elements of a vector.
+#[pyfunction]
+fn make_clamp(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_clamp(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr()))
+}
+
+/// Constructs a transformation that clamps the elements of a vector, with symmetric distance.
+#[pyfunction]
+fn make_clamp_symmetric(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_clamp_symmetric(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr()))
+}
+
+/// Constructs a transformation that maps each element of a vector to the index of its bin.
+#[pyfunction]
+fn make_find_bin(type_args: &str, edges: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let edges = list_to_json(edges)?;
+    Transformation::new(ffi_trans::opendp_trans__make_find_bin(type_args.as_ptr(), edges.as_ptr()))
+}
+
+/// Constructs a transformation that maps each element of a vector to the index of its category.
+#[pyfunction]
+fn make_find(type_args: &str, categories: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    let categories = list_to_json(categories)?;
+    Transformation::new(ffi_trans::opendp_trans__make_find(type_args.as_ptr(), categories.as_ptr()))
+}
+
+/// Constructs a transformation that sums a vector, with L1 sensitivity.
+#[pyfunction]
+fn make_bounded_sum_l1(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_bounded_sum_l1(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr()))
+}
+
+/// Constructs a transformation that sums a vector with symmetric distance, with L1 sensitivity.
+#[pyfunction]
+fn make_bounded_sum_symmetric(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_bounded_sum_symmetric(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr()))
+}
+
+/// Constructs a transformation that sums a vector, with L2 sensitivity.
+#[pyfunction]
+fn make_bounded_sum_l2(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_bounded_sum_l2(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr()))
+}
+
+/// Constructs a transformation that counts the elements of a vector, with L1 sensitivity.
+#[pyfunction]
+fn make_count_l1(type_args: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Transformation::new(ffi_trans::opendp_trans__make_count_l1(type_args.as_ptr()))
+}
+
+/// Constructs a transformation that counts the elements of a vector, with L2 sensitivity.
+#[pyfunction]
+fn make_count_l2(type_args: &str) -> PyResult<Transformation> {
+    let (type_args, _) = parse_type_args(type_args, 1)?;
+    Transformation::new(ffi_trans::opendp_trans__make_count_l2(type_args.as_ptr()))
+}
+
+/// Constructs a transformation that multiplies a scalar by a constant.
+#[pyfunction]
+fn make_lipschitz_mul(type_args: &str, c: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let c = Object::from_py(c, &types[0])?;
+    Transformation::new(ffi_trans::opendp_trans__make_lipschitz_mul(type_args.as_ptr(), c.as_ptr()))
+}
+
+/// Constructs a transformation that adds a constant to a scalar.
+#[pyfunction]
+fn make_shift(type_args: &str, shift: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let shift = Object::from_py(shift, &types[0])?;
+    Transformation::new(ffi_trans::opendp_trans__make_shift(type_args.as_ptr(), shift.as_ptr()))
+}
+
+/// Constructs a transformation that maps a scalar `x` to `a * x + b`.
+#[pyfunction]
+fn make_affine(type_args: &str, a: &Bound<'_, PyAny>, b: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (a, b) = (Object::from_py(a, &types[0])?, Object::from_py(b, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_affine(type_args.as_ptr(), a.as_ptr(), b.as_ptr()))
+}
+
+/// Constructs a transformation that multiplies each element of a bounded vector by a constant.
+#[pyfunction]
+fn make_lipschitz_mul_vec(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>, c: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper, c) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?, Object::from_py(c, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_lipschitz_mul_vec(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr(), c.as_ptr()))
+}
+
+/// Constructs a transformation that adds a constant to each element of a bounded vector.
+#[pyfunction]
+fn make_shift_vec(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>, shift: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper, shift) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?, Object::from_py(shift, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_shift_vec(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr(), shift.as_ptr()))
+}
+
+/// Constructs a transformation that maps each element `x` of a bounded vector to `a * x + b`.
+#[pyfunction]
+fn make_affine_vec(type_args: &str, lower: &Bound<'_, PyAny>, upper: &Bound<'_, PyAny>, a: &Bound<'_, PyAny>, b: &Bound<'_, PyAny>) -> PyResult<Transformation> {
+    let (type_args, types) = parse_type_args(type_args, 1)?;
+    let (lower, upper) = (Object::from_py(lower, &types[0])?, Object::from_py(upper, &types[0])?);
+    let (a, b) = (Object::from_py(a, &types[0])?, Object::from_py(b, &types[0])?);
+    Transformation::new(ffi_trans::opendp_trans__make_affine_vec(type_args.as_ptr(), lower.as_ptr(), upper.as_ptr(), a.as_ptr(), b.as_ptr()))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(make_identity, m)?)?;
+    m.add_function(wrap_pyfunction!(make_split_lines, m)?)?;
+    m.add_function(wrap_pyfunction!(make_parse_series, m)?)?;
+    m.add_function(wrap_pyfunction!(make_cast_default, m)?)?;
+    m.add_function(wrap_pyfunction!(make_cast_inherent, m)?)?;
+    m.add_function(wrap_pyfunction!(make_is_equal, m)?)?;
+    m.add_function(wrap_pyfunction!(make_split_records, m)?)?;
+    m.add_function(wrap_pyfunction!(make_create_dataframe, m)?)?;
+    m.add_function(wrap_pyfunction!(make_split_dataframe, m)?)?;
+    m.add_function(wrap_pyfunction!(make_split_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(make_parse_jsonl, m)?)?;
+    m.add_function(wrap_pyfunction!(make_parse_column, m)?)?;
+    m.add_function(wrap_pyfunction!(make_select_column, m)?)?;
+    m.add_function(wrap_pyfunction!(make_select_column_symmetric, m)?)?;
+    m.add_function(wrap_pyfunction!(make_select_column_option, m)?)?;
+    m.add_function(wrap_pyfunction!(make_filter, m)?)?;
+    m.add_function(wrap_pyfunction!(make_hamming_to_symmetric, m)?)?;
+    m.add_function(wrap_pyfunction!(make_hamming_to_symmetric_nullable, m)?)?;
+    m.add_function(wrap_pyfunction!(make_hamming_to_symmetric_dataframe, m)?)?;
+    m.add_function(wrap_pyfunction!(make_filter_dataframe, m)?)?;
+    m.add_function(wrap_pyfunction!(make_hamming_to_user, m)?)?;
+    m.add_function(wrap_pyfunction!(make_bound_user_contributions, m)?)?;
+    m.add_function(wrap_pyfunction!(make_clamp, m)?)?;
+    m.add_function(wrap_pyfunction!(make_clamp_symmetric, m)?)?;
+    m.add_function(wrap_pyfunction!(make_find_bin, m)?)?;
+    m.add_function(wrap_pyfunction!(make_find, m)?)?;
+    m.add_function(wrap_pyfunction!(make_bounded_sum_l1, m)?)?;
+    m.add_function(wrap_pyfunction!(make_bounded_sum_symmetric, m)?)?;
+    m.add_function(wrap_pyfunction!(make_bounded_sum_l2, m)?)?;
+    m.add_function(wrap_pyfunction!(make_count_l1, m)?)?;
+    m.add_function(wrap_pyfunction!(make_count_l2, m)?)?;
+    m.add_function(wrap_pyfunction!(make_lipschitz_mul, m)?)?;
+    m.add_function(wrap_pyfunction!(make_shift, m)?)?;
+    m.add_function(wrap_pyfunction!(make_affine, m)?)?;
+    m.add_function(wrap_pyfunction!(make_lipschitz_mul_vec, m)?)?;
+    m.add_function(wrap_pyfunction!(make_shift_vec, m)?)?;
+    m.add_function(wrap_pyfunction!(make_affine_vec, m)?)?;
+    Ok(())
+}
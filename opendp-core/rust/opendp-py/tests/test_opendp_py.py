@@ -0,0 +1,90 @@
+import array
+
+import pytest
+
+import opendp_py as odp
+
+
+def test_identity():
+    identity = odp.trans.make_identity("<String>")
+    assert identity("hello, world!") == "hello, world!"
+
+
+def test_chain_operators():
+    clamp = odp.trans.make_clamp("<f64>", 0.0, 10.0)
+    bounded_sum = odp.trans.make_bounded_sum_l1("<f64>", 0.0, 10.0)
+    base_laplace = odp.meas.make_base_laplace("<f64>", 1.0)
+    sum_ = clamp >> bounded_sum
+    assert isinstance(sum_, odp.Transformation)
+    assert sum_([1.0, 2.0, 30.0]) == 13.0
+    noisy_sum = sum_ >> base_laplace
+    assert isinstance(noisy_sum, odp.Measurement)
+    assert isinstance(noisy_sum([1.0, 2.0, 3.0]), float)
+    with pytest.raises(TypeError):
+        base_laplace >> clamp
+
+
+def test_dataframe():
+    split_dataframe = odp.trans.make_split_dataframe(",", 3)
+    parse_column = odp.trans.make_parse_column("<i32>", split_dataframe.output_domain, "1", True)
+    parse_dataframe = split_dataframe >> parse_column
+    select = odp.trans.make_select_column("<i32>", parse_dataframe.output_domain, "1")
+    count = odp.trans.make_count_l1("<i32>")
+    assert (parse_dataframe >> select >> count)("ant, 1, 1.1\nbat, 2, 2.2\ncat, 3, 3.3") == 3
+
+
+def test_composition():
+    base_laplace = odp.meas.make_base_laplace("<f64>", 1.0)
+    composition = odp.make_composition(base_laplace, base_laplace)
+    res = composition(1.0)
+    assert isinstance(res, tuple) and len(res) == 2
+
+
+def test_buffer_input():
+    count = odp.trans.make_count_l1("<f64>")
+    assert count(array.array("d", [1.0, 2.0, 3.0])) == 3
+    # Buffers of a different item type are extracted element by element.
+    assert count(array.array("i", [1, 2])) == 2
+
+
+def test_numpy_input():
+    np = pytest.importorskip("numpy")
+    bounded_sum = odp.trans.make_bounded_sum_l1("<f64>", 0.0, 10.0)
+    assert bounded_sum(np.array([1.0, 2.0, 3.0])) == 6.0
+    assert bounded_sum(np.arange(4.0)[::2]) == 2.0
+    with pytest.raises(TypeError):
+        bounded_sum(np.ones((2, 2)))
+
+
+def test_check_and_describe():
+    base_laplace = odp.meas.make_base_laplace("<f64>", 2.0)
+    assert base_laplace.check(1.0, 0.5)
+    assert not base_laplace.check(1.0, 0.25)
+    count = odp.trans.make_count_l1("<i32>")
+    assert count.check(2, 2)
+    assert count.describe()["output_metric"] == {"type": "L1Sensitivity", "distance": "i32"}
+
+
+def test_ast():
+    clamp = odp.trans.make_clamp("<f64>", 0.0, 10.0)
+    bounded_sum = odp.trans.make_bounded_sum_l1("<f64>", 0.0, 10.0)
+    sum_ast = (clamp >> bounded_sum).to_ast()
+    assert sum_ast["children"][1] == {"constructor": "make_clamp", "type_args": "<f64>", "params": {"lower": 0.0, "upper": 10.0}}
+    assert odp.Transformation.from_ast(sum_ast)([1.0, 2.0, 30.0]) == 13.0
+
+    noisy_sum = clamp >> bounded_sum >> odp.meas.make_base_laplace("<f64>", 1.0)
+    assert odp.Measurement.from_ast(noisy_sum.to_ast()).to_ast() == noisy_sum.to_ast()
+    with pytest.raises(odp.OpenDPError, match="TypeMismatch"):
+        odp.Transformation.from_ast(noisy_sum.to_ast())
+
+
+def test_errors():
+    with pytest.raises(odp.OpenDPError, match="TypeParse"):
+        odp.trans.make_identity("<i32, i32>")
+    with pytest.raises(odp.OpenDPError, match="UnsupportedType"):
+        odp.trans.make_clamp("<String>", "a", "b")
+    with pytest.raises(TypeError):
+        odp.trans.make_identity("<i32>")("not an int")
+    # Panics in the library are caught at the FFI boundary and raised too.
+    with pytest.raises(odp.OpenDPError, match="Panic"):
+        (odp.trans.make_split_lines() >> odp.trans.make_parse_series("<i32>"))("1\nx")
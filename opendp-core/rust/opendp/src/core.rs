@@ -10,7 +10,14 @@
 
 use std::rc::Rc;
 
-use crate::dom::{BoxDomain, PairDomain};
+use rand::Rng;
+use rand::seq::index;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::dist::{AmplifiableMeasure, HammingDistance, SymmetricDistance};
+use crate::dom::{BoxDomain, PairDomain, VectorDomain};
+use crate::error::Error;
 
 /// A set which constrains the input or output of a [`Function`].
 ///
@@ -20,6 +27,37 @@ pub trait Domain: Clone + PartialEq {
     type Carrier;
     /// Predicate to test an element for membership in the domain.
     fn member(&self, val: &Self::Carrier) -> bool;
+    /// Returns a description of the domain as JSON, so that front-ends can display and validate pipelines.
+    /// Domains with parameters (like bounds) or inner domains should override this to include them.
+    fn describe(&self) -> Value {
+        json!({ "type": base_type_name::<Self>(), "carrier": type_name::<Self::Carrier>() })
+    }
+}
+
+/// The name of a type without module paths, like `Vec<Data>` rather than `alloc::vec::Vec<opendp::data::Data>`.
+pub(crate) fn type_name<T: ?Sized>() -> String {
+    let mut name = String::new();
+    let mut segment_start = 0;
+    let mut chars = std::any::type_name::<T>().chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            // Drop the path segment preceding the `::`.
+            chars.next();
+            name.truncate(segment_start);
+        } else {
+            name.push(c);
+            if !(c.is_alphanumeric() || c == '_') {
+                segment_start = name.len();
+            }
+        }
+    }
+    name
+}
+
+/// The name of a type without module paths or generic arguments, like `VectorDomain`.
+pub(crate) fn base_type_name<T: ?Sized>() -> String {
+    let name = type_name::<T>();
+    name.split('<').next().unwrap().to_owned()
 }
 
 /// A mathematical function which maps values from an input [`Domain`] to an output [`Domain`].
@@ -49,11 +87,20 @@ impl<ID: Domain, OD: Domain> Function<ID, OD> {
 
 impl<ID: 'static + Domain, OD: 'static + Domain> Function<ID, OD> {
     pub fn make_chain<XD: 'static + Domain>(function1: &Function<XD, OD>, function0: &Function<ID, XD>) -> Function<ID, OD> {
+        Self::make_chain_glue(function1, function0, &new_drop())
+    }
+
+    /// Like [`Function::make_chain`], but drops the intermediate result with `carrier_drop`, so that it can be freed
+    /// when `XD` is erased.
+    pub fn make_chain_glue<XD: 'static + Domain>(function1: &Function<XD, OD>, function0: &Function<ID, XD>, carrier_drop: &Rc<dyn Fn(Box<XD::Carrier>)>) -> Function<ID, OD> {
         let function0 = function0.function.clone();
         let function1 = function1.function.clone();
+        let carrier_drop = carrier_drop.clone();
         let function = move |arg: &ID::Carrier| {
             let res0 = function0(arg);
-            function1(&res0)
+            let res1 = function1(&res0);
+            carrier_drop(res0);
+            res1
         };
         let function = Rc::new(function);
         Function { function }
@@ -77,27 +124,50 @@ impl<ID: 'static + Domain, ODA: 'static + Domain, ODB: 'static + Domain> Functio
 /// A representation of the distance between two elements in a set.
 pub trait Metric: Clone {
     type Distance;
+    /// Returns a description of the metric as JSON, including the type of its distances.
+    fn describe(&self) -> Value {
+        json!({ "type": base_type_name::<Self>(), "distance": type_name::<Self::Distance>() })
+    }
 }
 
 /// A representation of the distance between two distributions.
 pub trait Measure: Clone {
     type Distance;
+    /// Returns a description of the measure as JSON, including the type of its distances.
+    fn describe(&self) -> Value {
+        json!({ "type": base_type_name::<Self>(), "distance": type_name::<Self::Distance>() })
+    }
 }
 
+/// A relation between distances, which fails if it can't be evaluated.
+pub type Relation<DI, DO> = Rc<dyn Fn(&DI, &DO) -> Result<bool, Error>>;
+
 /// A boolean relation evaluating the privacy of a [`Measurement`].
 ///
 /// A `PrivacyRelation` is implemented as a function that takes an input [`Metric::Distance`] and output [`Measure::Distance`],
-/// and returns a boolean indicating if the relation holds.
-#[derive(Clone)]
+/// and returns a boolean indicating if the relation holds, or an error if it can't be evaluated.
 pub struct PrivacyRelation<IM: Metric, OM: Measure> {
-    pub relation: Rc<dyn Fn(&IM::Distance, &OM::Distance) -> bool>
+    pub relation: Relation<IM::Distance, OM::Distance>
+}
+// Not derived, as that would require IM: Clone and OM: Clone.
+impl<IM: Metric, OM: Measure> Clone for PrivacyRelation<IM, OM> {
+    fn clone(&self) -> Self {
+        PrivacyRelation { relation: self.relation.clone() }
+    }
 }
 impl<IM: Metric, OM: Measure> PrivacyRelation<IM, OM> {
     pub fn new(relation: impl Fn(&IM::Distance, &OM::Distance) -> bool + 'static) -> Self {
+        Self::new_fallible(move |d_in, d_out| Ok(relation(d_in, d_out)))
+    }
+    pub fn new_fallible(relation: impl Fn(&IM::Distance, &OM::Distance) -> Result<bool, Error> + 'static) -> Self {
         let relation = Rc::new(relation);
         PrivacyRelation { relation }
     }
-    pub fn eval(&self, input_distance: &IM::Distance, output_distance: &OM::Distance) -> bool {
+    /// A relation that always fails with [`Error::NotImplemented`], for combinators that can't yet relate distances.
+    pub fn new_not_implemented(name: &'static str) -> Self {
+        Self::new_fallible(move |_d_in, _d_out| Err(Error::NotImplemented(format!("privacy relation of {}", name))))
+    }
+    pub fn eval(&self, input_distance: &IM::Distance, output_distance: &OM::Distance) -> Result<bool, Error> {
         (self.relation)(input_distance, output_distance)
     }
 }
@@ -105,17 +175,29 @@ impl<IM: Metric, OM: Measure> PrivacyRelation<IM, OM> {
 /// A boolean relation evaluating the stability of a [`Transformation`].
 ///
 /// A `StabilityRelation` is implemented as a function that takes an input and output [`Metric::Distance`],
-/// and returns a boolean indicating if the relation holds.
-#[derive(Clone)]
+/// and returns a boolean indicating if the relation holds, or an error if it can't be evaluated.
 pub struct StabilityRelation<IM: Metric, OM: Metric> {
-    pub relation: Rc<dyn Fn(&IM::Distance, &OM::Distance) -> bool>
+    pub relation: Relation<IM::Distance, OM::Distance>
+}
+// Not derived, as that would require IM: Clone and OM: Clone.
+impl<IM: Metric, OM: Metric> Clone for StabilityRelation<IM, OM> {
+    fn clone(&self) -> Self {
+        StabilityRelation { relation: self.relation.clone() }
+    }
 }
 impl<IM: Metric, OM: Metric> StabilityRelation<IM, OM> {
     pub fn new(relation: impl Fn(&IM::Distance, &OM::Distance) -> bool + 'static) -> Self {
+        Self::new_fallible(move |d_in, d_out| Ok(relation(d_in, d_out)))
+    }
+    pub fn new_fallible(relation: impl Fn(&IM::Distance, &OM::Distance) -> Result<bool, Error> + 'static) -> Self {
         let relation = Rc::new(relation);
         StabilityRelation { relation }
     }
-    pub fn eval(&self, input_distance: &IM::Distance, output_distance: &OM::Distance) -> bool {
+    /// A relation that always fails with [`Error::NotImplemented`], for combinators that can't yet relate distances.
+    pub fn new_not_implemented(name: &'static str) -> Self {
+        Self::new_fallible(move |_d_in, _d_out| Err(Error::NotImplemented(format!("stability relation of {}", name))))
+    }
+    pub fn eval(&self, input_distance: &IM::Distance, output_distance: &OM::Distance) -> Result<bool, Error> {
         (self.relation)(input_distance, output_distance)
     }
 }
@@ -148,6 +230,14 @@ impl<ID: Domain, OD: Domain, IM: Metric, OM: Measure> Measurement<ID, OD, IM, OM
         let privacy_relation = PrivacyRelation::new(privacy_relation);
         Measurement { input_domain, output_domain, function, input_metric, output_measure, privacy_relation }
     }
+
+    /// Evaluates the function on `arg`, after checking that `arg` is a member of the input domain.
+    pub fn invoke(&self, arg: &ID::Carrier) -> Result<OD::Carrier, Error> {
+        if !self.input_domain.member(arg) {
+            return Err(Error::FailedMembership);
+        }
+        Ok(self.function.eval(arg))
+    }
 }
 
 /// A data transformation with certain stability characteristics.
@@ -177,49 +267,114 @@ impl<ID: Domain, OD: Domain, IM: Metric, OM: Metric> Transformation<ID, OD, IM,
         let stability_relation = StabilityRelation::new(stability_relation);
         Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation }
     }
+
+    /// Evaluates the function on `arg`, after checking that `arg` is a member of the input domain.
+    pub fn invoke(&self, arg: &ID::Carrier) -> Result<OD::Carrier, Error> {
+        if !self.input_domain.member(arg) {
+            return Err(Error::FailedMembership);
+        }
+        Ok(self.function.eval(arg))
+    }
 }
 
 
 // GLUE FOR FFI USE OF COMBINATORS
-fn new_clone<T: Clone>() -> Rc<dyn Fn(&Box<T>) -> Box<T>> {
-    let clone = |t: &Box<T>| t.clone();
+fn new_clone<T: Clone>() -> Rc<dyn Fn(&T) -> Box<T>> {
+    let clone = |t: &T| Box::new(t.clone());
     Rc::new(clone)
 }
 
-fn new_domain_glue<D: Domain>() -> (Rc<dyn Fn(&Box<D>, &Box<D>) -> bool>, Rc<dyn Fn(&Box<D>) -> Box<D>>) {
-    let eq = |d0: &Box<D>, d1: &Box<D>| d0 == d1;
+fn new_drop<T>() -> Rc<dyn Fn(Box<T>)> {
+    let drop = |t: Box<T>| drop(t);
+    Rc::new(drop)
+}
+
+fn new_domain_glue<D: Domain>() -> (Rc<dyn Fn(&D, &D) -> bool>, Rc<dyn Fn(&D) -> Box<D>>, Rc<dyn Fn(Box<D>)>) {
+    let eq = |d0: &D, d1: &D| d0 == d1;
     let eq = Rc::new(eq);
     let clone = new_clone();
-    (eq, clone)
+    let drop = new_drop();
+    (eq, clone, drop)
 }
 
 /// Public only for access from FFI.
 #[derive(Clone)]
 pub struct MeasureGlue<D: Domain, M: Measure> {
-    pub domain_eq: Rc<dyn Fn(&Box<D>, &Box<D>) -> bool>,
-    pub domain_clone: Rc<dyn Fn(&Box<D>) -> Box<D>>,
-    pub measure_clone: Rc<dyn Fn(&Box<M>) -> Box<M>>,
+    pub domain_eq: Rc<dyn Fn(&D, &D) -> bool>,
+    pub domain_clone: Rc<dyn Fn(&D) -> Box<D>>,
+    pub domain_drop: Rc<dyn Fn(Box<D>)>,
+    pub domain_describe: Rc<dyn Fn(&D) -> Value>,
+    pub measure_clone: Rc<dyn Fn(&M) -> Box<M>>,
+    pub measure_describe: Rc<dyn Fn(&M) -> Value>,
 }
 impl<D: 'static + Domain, M: 'static + Measure> MeasureGlue<D, M> {
     pub fn new() -> Self {
-        let (domain_eq, domain_clone) = new_domain_glue();
+        let (domain_eq, domain_clone, domain_drop) = new_domain_glue();
+        let domain_describe = Rc::new(|d: &D| d.describe());
         let measure_clone = new_clone();
-        MeasureGlue { domain_eq, domain_clone, measure_clone }
+        let measure_describe = Rc::new(|m: &M| m.describe());
+        MeasureGlue { domain_eq, domain_clone, domain_drop, domain_describe, measure_clone, measure_describe }
+    }
+}
+impl<D0: 'static + Domain, D1: 'static + Domain, M: 'static + Measure> MeasureGlue<PairDomain<BoxDomain<D0>, BoxDomain<D1>>, M> {
+    /// Constructs the glue for the output of a composition, from the glue of its parts.
+    pub fn new_pair(glue0: &MeasureGlue<D0, M>, glue1: &MeasureGlue<D1, M>) -> Self {
+        let (eq0, eq1) = (glue0.domain_eq.clone(), glue1.domain_eq.clone());
+        let domain_eq = Rc::new(move |d0: &PairDomain<BoxDomain<D0>, BoxDomain<D1>>, d1: &PairDomain<BoxDomain<D0>, BoxDomain<D1>>| {
+            eq0(&d0.0.element_domain, &d1.0.element_domain) && eq1(&d0.1.element_domain, &d1.1.element_domain)
+        });
+        let (clone0, clone1) = (glue0.domain_clone.clone(), glue1.domain_clone.clone());
+        let domain_clone = Rc::new(move |d: &PairDomain<BoxDomain<D0>, BoxDomain<D1>>| {
+            Box::new(PairDomain::new(BoxDomain::new(clone0(&d.0.element_domain)), BoxDomain::new(clone1(&d.1.element_domain))))
+        });
+        let (drop0, drop1) = (glue0.domain_drop.clone(), glue1.domain_drop.clone());
+        let domain_drop = Rc::new(move |d: Box<PairDomain<BoxDomain<D0>, BoxDomain<D1>>>| {
+            let PairDomain(d0, d1) = *d;
+            drop0(d0.element_domain);
+            drop1(d1.element_domain);
+        });
+        let (describe0, describe1) = (glue0.domain_describe.clone(), glue1.domain_describe.clone());
+        let domain_describe = Rc::new(move |d: &PairDomain<BoxDomain<D0>, BoxDomain<D1>>| {
+            json!({ "type": "PairDomain", "element_domains": [describe0(&d.0.element_domain), describe1(&d.1.element_domain)] })
+        });
+        let measure_clone = glue0.measure_clone.clone();
+        let measure_describe = glue0.measure_describe.clone();
+        MeasureGlue { domain_eq, domain_clone, domain_drop, domain_describe, measure_clone, measure_describe }
     }
 }
 
 /// Public only for access from FFI.
-#[derive(Clone)]
 pub struct MetricGlue<D: Domain, M: Metric> {
-    pub domain_eq: Rc<dyn Fn(&Box<D>, &Box<D>) -> bool>,
-    pub domain_clone: Rc<dyn Fn(&Box<D>) -> Box<D>>,
-    pub metric_clone: Rc<dyn Fn(&Box<M>) -> Box<M>>,
+    pub domain_eq: Rc<dyn Fn(&D, &D) -> bool>,
+    pub domain_clone: Rc<dyn Fn(&D) -> Box<D>>,
+    pub domain_drop: Rc<dyn Fn(Box<D>)>,
+    pub domain_describe: Rc<dyn Fn(&D) -> Value>,
+    pub carrier_drop: Rc<dyn Fn(Box<D::Carrier>)>,
+    pub metric_clone: Rc<dyn Fn(&M) -> Box<M>>,
+    pub metric_describe: Rc<dyn Fn(&M) -> Value>,
+}
+// Not derived, as that would require D::Carrier: Clone.
+impl<D: Domain, M: Metric> Clone for MetricGlue<D, M> {
+    fn clone(&self) -> Self {
+        MetricGlue {
+            domain_eq: self.domain_eq.clone(),
+            domain_clone: self.domain_clone.clone(),
+            domain_drop: self.domain_drop.clone(),
+            domain_describe: self.domain_describe.clone(),
+            carrier_drop: self.carrier_drop.clone(),
+            metric_clone: self.metric_clone.clone(),
+            metric_describe: self.metric_describe.clone(),
+        }
+    }
 }
 impl<D: 'static + Domain, M: 'static + Metric> MetricGlue<D, M> {
     pub fn new() -> Self {
-        let (domain_eq, domain_clone) = new_domain_glue();
+        let (domain_eq, domain_clone, domain_drop) = new_domain_glue();
+        let domain_describe = Rc::new(|d: &D| d.describe());
+        let carrier_drop = new_drop();
         let metric_clone = new_clone();
-        MetricGlue { domain_eq, domain_clone, metric_clone }
+        let metric_describe = Rc::new(|m: &M| m.describe());
+        MetricGlue { domain_eq, domain_clone, domain_drop, domain_describe, carrier_drop, metric_clone, metric_describe }
     }
 }
 
@@ -238,11 +393,11 @@ pub fn make_chain_mt_glue<ID, XD, OD, IM, XM, OM>(measurement1: &Measurement<XD,
     assert!((x_glue.domain_eq)(&transformation0.output_domain, &measurement1.input_domain));
     let input_domain = (input_glue.domain_clone)(&transformation0.input_domain);
     let output_domain = (output_glue.domain_clone)(&measurement1.output_domain);
-    let function = Function::make_chain(&measurement1.function, &transformation0.function);
+    let function = Function::make_chain_glue(&measurement1.function, &transformation0.function, &x_glue.carrier_drop);
     let input_metric = (input_glue.metric_clone)(&transformation0.input_metric);
     let output_measure = (output_glue.measure_clone)(&measurement1.output_measure);
-    // TODO: PrivacyRelation for make_chain_mt
-    let privacy_relation = PrivacyRelation::new(|_i, _o| false);
+    // TODO: PrivacyRelation for make_chain_mt, which needs the intermediate distance.
+    let privacy_relation = PrivacyRelation::new_not_implemented("make_chain_mt");
     Measurement { input_domain, output_domain, function, input_metric, output_measure, privacy_relation }
 }
 
@@ -259,11 +414,11 @@ pub fn make_chain_tt_glue<ID, XD, OD, IM, XM, OM>(transformation1: &Transformati
     assert!((x_glue.domain_eq)(&transformation0.output_domain, &transformation1.input_domain));
     let input_domain = (input_glue.domain_clone)(&transformation0.input_domain);
     let output_domain = (output_glue.domain_clone)(&transformation1.output_domain);
-    let function = Function::make_chain(&transformation1.function, &transformation0.function);
+    let function = Function::make_chain_glue(&transformation1.function, &transformation0.function, &x_glue.carrier_drop);
     let input_metric = (input_glue.metric_clone)(&transformation0.input_metric);
     let output_metric = (output_glue.metric_clone)(&transformation1.output_metric);
-    // TODO: StabilityRelation for make_chain_tt
-    let stability_relation = StabilityRelation::new(|_i, _o| false);
+    // TODO: StabilityRelation for make_chain_tt, which needs the intermediate distance.
+    let stability_relation = StabilityRelation::new_not_implemented("make_chain_tt");
     Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation }
 }
 
@@ -290,17 +445,117 @@ pub fn make_composition_glue<ID, OD0, OD1, IM, OM>(measurement0: &Measurement<ID
     let input_metric = (input_glue.metric_clone)(&measurement0.input_metric);
     // TODO: Figure out output_measure for composition.
     let output_measure = (output_glue0.measure_clone)(&measurement0.output_measure);
-    // TODO: PrivacyRelation for make_composition
-    let privacy_relation = PrivacyRelation::new(|_i, _o| false);
+    // TODO: PrivacyRelation for make_composition, which needs a split of the output distance between the measurements.
+    let privacy_relation = PrivacyRelation::new_not_implemented("make_composition");
     Measurement { input_domain, output_domain, function, input_metric, output_measure, privacy_relation }
 }
 
+/// Constructs a [`Measurement`] that applies `measurement` to each partition of its input.
+///
+/// The input is a vector of disjoint partitions (e.g., from [`make_partition_by`](crate::trans::make_partition_by)),
+/// with the input distance summed over all partitions. Since each individual lands in only one partition,
+/// the privacy loss is charged once rather than once per partition: the relation is that of `measurement`.
+/// This assumes the privacy loss of `measurement` grows at least linearly in the input distance,
+/// as it does for the measurements in [`meas`](crate::meas).
+pub fn make_parallel_composition<ID, OD, IM, OM>(measurement: &Measurement<ID, OD, IM, OM>) -> Measurement<VectorDomain<ID>, VectorDomain<OD>, IM, OM> where
+    ID: 'static + Domain, OD: 'static + Domain, IM: 'static + Metric, OM: 'static + Measure {
+    let input_domain = Box::new(VectorDomain::new(measurement.input_domain.as_ref().clone()));
+    let output_domain = Box::new(VectorDomain::new(measurement.output_domain.as_ref().clone()));
+    let function = measurement.function.function.clone();
+    let function = Function::new(move |arg: &Vec<ID::Carrier>| -> Vec<OD::Carrier> {
+        arg.iter().map(|e| *function(e)).collect()
+    });
+    let input_metric = measurement.input_metric.clone();
+    let output_measure = measurement.output_measure.clone();
+    let privacy_relation = measurement.privacy_relation.clone();
+    Measurement { input_domain, output_domain, function, input_metric, output_measure, privacy_relation }
+}
+
+/// A scheme for drawing a subsample, for use with [`make_subsample`].
+///
+/// The metric `M` is the input metric under which the amplification theorem for the scheme holds.
+pub trait Sampler<M: Metric>: 'static {
+    /// The probability that any given record is included in the subsample.
+    fn rate(&self) -> f64;
+    fn sample<T: Clone>(&self, arg: &[T]) -> Vec<T>;
+}
+
+/// Includes each record independently with probability `rate`. Amplification holds under [`SymmetricDistance`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PoissonSampler {
+    pub rate: f64,
+}
+impl Sampler<SymmetricDistance> for PoissonSampler {
+    fn rate(&self) -> f64 { self.rate }
+    fn sample<T: Clone>(&self, arg: &[T]) -> Vec<T> {
+        let mut rng = rand::thread_rng();
+        arg.iter().filter(|_| rng.gen_bool(self.rate)).cloned().collect()
+    }
+}
+
+/// Draws `sample_size` records without replacement from a dataset of `population_size` records.
+/// Amplification holds under [`HammingDistance`].
+///
+/// A dataset of any other length is sampled at the same proportion, rounded down, so the sampling rate never exceeds
+/// [`rate`](Sampler::rate). Neighboring datasets under [`HammingDistance`] have the same length, so they are sampled alike.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FixedSizeSampler {
+    pub population_size: usize,
+    pub sample_size: usize,
+}
+impl Sampler<HammingDistance> for FixedSizeSampler {
+    fn rate(&self) -> f64 { self.sample_size as f64 / self.population_size as f64 }
+    fn sample<T: Clone>(&self, arg: &[T]) -> Vec<T> {
+        let sample_size = (self.sample_size as u128 * arg.len() as u128 / self.population_size as u128) as usize;
+        let mut indices = index::sample(&mut rand::thread_rng(), arg.len(), sample_size).into_vec();
+        indices.sort_unstable();
+        indices.into_iter().map(|i| arg[i].clone()).collect()
+    }
+}
+
+/// Constructs a [`Measurement`] that runs `measurement` on a subsample of its input drawn by `sampler`.
+///
+/// The privacy relation is tightened by amplification by subsampling. For `d_in > 1`,
+/// the sampling rate is conservatively taken to be `d_in` times the rate of `sampler`.
+///
+/// Fails unless the rate of `sampler` is in `(0, 1]`.
+pub fn make_subsample<D, OD, IM, OM, S>(measurement: &Measurement<VectorDomain<D>, OD, IM, OM>, sampler: S) -> Result<Measurement<VectorDomain<D>, OD, IM, OM>, Error> where
+    D: 'static + Domain, D::Carrier: Clone, OD: 'static + Domain, IM: 'static + Metric<Distance=i32>, OM: 'static + AmplifiableMeasure, S: Sampler<IM> {
+    let output_glue = MeasureGlue::<OD, OM>::new();
+    make_subsample_glue(measurement, sampler, &output_glue)
+}
+
+/// Like [`make_subsample`], but clones the output domain and measure with `output_glue`, so that `OD` may be erased.
+pub fn make_subsample_glue<D, OD, IM, OM, S>(measurement: &Measurement<VectorDomain<D>, OD, IM, OM>, sampler: S, output_glue: &MeasureGlue<OD, OM>) -> Result<Measurement<VectorDomain<D>, OD, IM, OM>, Error> where
+    D: 'static + Domain, D::Carrier: Clone, OD: 'static + Domain, IM: 'static + Metric<Distance=i32>, OM: 'static + AmplifiableMeasure, S: Sampler<IM> {
+    let rate = sampler.rate();
+    if !(rate > 0.0 && rate <= 1.0) {
+        return Err(Error::InvalidArgument(format!("sampling rate must be in (0, 1], got {}", rate)))
+    }
+    let input_domain = measurement.input_domain.clone();
+    let output_domain = (output_glue.domain_clone)(&measurement.output_domain);
+    // The result is passed on still boxed, so that it needn't be unboxed as OD::Carrier.
+    let function = measurement.function.function.clone();
+    let function: Rc<dyn Fn(&Vec<D::Carrier>) -> Box<OD::Carrier>> = Rc::new(move |arg: &Vec<D::Carrier>| function(&sampler.sample(arg)));
+    let function = Function { function };
+    let input_metric = measurement.input_metric.clone();
+    let output_measure = (output_glue.measure_clone)(&measurement.output_measure);
+    let privacy_relation = measurement.privacy_relation.clone();
+    let privacy_relation = PrivacyRelation::new_fallible(move |d_in: &i32, d_out: &OM::Distance| {
+        let rate = (rate * *d_in as f64).min(1.0);
+        privacy_relation.eval(d_in, &OM::deamplify(d_out, rate))
+    });
+    Ok(Measurement { input_domain, output_domain, function, input_metric, output_measure, privacy_relation })
+}
+
 
 // UNIT TESTS
 #[cfg(test)]
 mod tests {
+    use std::ops::Bound;
+
     use crate::dist::{L1Sensitivity, MaxDivergence};
-    use crate::dom::AllDomain;
+    use crate::dom::{AllDomain, IntervalDomain};
 
     use super::*;
 
@@ -318,6 +573,33 @@ mod tests {
         assert_eq!(ret, 99);
     }
 
+    #[test]
+    fn test_invoke() {
+        let input_domain = IntervalDomain::new(Bound::Included(0), Bound::Excluded(10));
+        let output_domain = AllDomain::<i32>::new();
+        let function = |arg: &i32| arg * 2;
+        let input_metric = L1Sensitivity::<i32>::new();
+        let output_metric = L1Sensitivity::<i32>::new();
+        let stability_relation = |d_in: &i32, d_out: &i32| *d_out >= *d_in * 2;
+        let double = Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation);
+        assert_eq!(double.invoke(&4), Ok(8));
+        assert_eq!(double.invoke(&10), Err(Error::FailedMembership));
+        assert_eq!(double.invoke(&-1), Err(Error::FailedMembership));
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(type_name::<Vec<Option<String>>>(), "Vec<Option<String>>");
+        assert_eq!(base_type_name::<VectorDomain<AllDomain<f64>>>(), "VectorDomain");
+        let domain = VectorDomain::new(IntervalDomain::new(Bound::Included(0), Bound::Excluded(10)));
+        assert_eq!(domain.describe(), json!({
+            "type": "VectorDomain", "carrier": "Vec<i32>",
+            "element_domain": { "type": "IntervalDomain", "carrier": "i32", "lower": { "included": 0 }, "upper": { "excluded": 10 } },
+        }));
+        assert_eq!(L1Sensitivity::<f64>::new().describe(), json!({ "type": "L1Sensitivity", "distance": "f64" }));
+        assert_eq!(MaxDivergence::new().describe(), json!({ "type": "MaxDivergence", "distance": "f64" }));
+    }
+
     #[test]
     fn test_make_chain_mt() {
         let input_domain0 = AllDomain::<u8>::new();
@@ -338,6 +620,7 @@ mod tests {
         let arg = 99_u8;
         let ret = chain.function.eval(&arg);
         assert_eq!(ret, 101.0);
+        assert!(matches!(chain.privacy_relation.eval(&1, &1.0), Err(Error::NotImplemented(_))));
     }
 
     #[test]
@@ -362,6 +645,48 @@ mod tests {
         assert_eq!(ret, 101.0);
     }
 
+    #[test]
+    fn test_make_parallel_composition() {
+        let input_domain = AllDomain::<i32>::new();
+        let output_domain = AllDomain::<i32>::new();
+        let function = |arg: &i32| arg + 1;
+        let input_metric = L1Sensitivity::<i32>::new();
+        let output_measure = MaxDivergence::new();
+        let privacy_relation = |d_in: &i32, d_out: &f64| *d_out >= *d_in as f64;
+        let measurement = Measurement::new(input_domain, output_domain, function, input_metric, output_measure, privacy_relation);
+        let parallel = make_parallel_composition(&measurement);
+        let arg = vec![1, 2, 3];
+        let ret = parallel.function.eval(&arg);
+        assert_eq!(ret, vec![2, 3, 4]);
+        assert!(parallel.privacy_relation.eval(&1, &1.0).unwrap());
+        assert!(!parallel.privacy_relation.eval(&2, &1.0).unwrap());
+    }
+
+    #[test]
+    fn test_make_subsample() {
+        let input_domain = VectorDomain::new_all();
+        let output_domain = AllDomain::<usize>::new();
+        let function = |arg: &Vec<i32>| arg.len();
+        let input_metric = HammingDistance::new();
+        let output_measure = MaxDivergence::new();
+        let privacy_relation = |d_in: &i32, d_out: &f64| *d_out >= *d_in as f64;
+        let measurement = Measurement::new(input_domain, output_domain, function, input_metric, output_measure, privacy_relation);
+        let subsample = make_subsample(&measurement, FixedSizeSampler { population_size: 10, sample_size: 5 }).unwrap();
+        let arg: Vec<i32> = (0..10).collect();
+        assert_eq!(subsample.function.eval(&arg), 5);
+        // ln(1 + 0.5 (e^1 - 1)) ~= 0.620
+        assert!(subsample.privacy_relation.eval(&1, &0.621).unwrap());
+        assert!(!subsample.privacy_relation.eval(&1, &0.619).unwrap());
+        assert!(subsample.privacy_relation.eval(&2, &2.0).unwrap());
+        // Inputs of other lengths are sampled at no more than the same rate.
+        assert_eq!(subsample.function.eval(&(0..5).collect()), 2);
+        assert_eq!(subsample.function.eval(&(0..21).collect()), 10);
+        assert_eq!(subsample.function.eval(&vec![]), 0);
+        assert!(make_subsample(&measurement, FixedSizeSampler { population_size: 10, sample_size: 11 }).is_err());
+        assert!(make_subsample(&measurement, FixedSizeSampler { population_size: 10, sample_size: 0 }).is_err());
+        assert!(make_subsample(&measurement, FixedSizeSampler { population_size: 0, sample_size: 0 }).is_err());
+    }
+
     #[test]
     fn test_make_composition() {
         let input_domain0 = AllDomain::<i32>::new();
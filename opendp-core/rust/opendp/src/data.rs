@@ -24,6 +24,7 @@ impl Element for bool {}
 impl Element for String {}
 impl Element for u8 {}
 impl Element for Data {}
+impl<T: Element> Element for Option<T> {}
 
 pub trait Form: Debug {
     // Not sure if we need into_any() (which consumes the Form), keeping it for now.
@@ -90,7 +91,10 @@ impl Data {
         Data { form: Box::new(form) }
     }
     pub fn as_form<T: 'static + Form>(&self) -> &T {
-        self.form.as_any().downcast_ref::<T>().expect("Wrong form")
+        self.try_as_form().expect("Wrong form")
+    }
+    pub fn try_as_form<T: 'static + Form>(&self) -> Option<&T> {
+        self.form.as_any().downcast_ref::<T>()
     }
     pub fn into_form<T: 'static + Form>(self) -> T {
         let any= self.form.into_any();
@@ -23,6 +23,26 @@ impl SmoothedMaxDivergence {
     pub fn new() -> Self { SmoothedMaxDivergence }
 }
 
+/// A [`Measure`] whose privacy loss is amplified by subsampling.
+pub trait AmplifiableMeasure: Measure {
+    /// Returns the largest privacy loss of a measurement such that, when run on a subsample drawn at `rate`,
+    /// its privacy loss is at most `d_out`. This inverts the amplification theorem.
+    fn deamplify(d_out: &Self::Distance, rate: f64) -> Self::Distance;
+}
+impl AmplifiableMeasure for MaxDivergence {
+    fn deamplify(d_out: &f64, rate: f64) -> f64 {
+        // ε' = ln(1 + q (e^ε - 1))
+        (d_out.exp_m1() / rate).ln_1p()
+    }
+}
+impl AmplifiableMeasure for SmoothedMaxDivergence {
+    fn deamplify(d_out: &(f64, f64), rate: f64) -> (f64, f64) {
+        // ε' = ln(1 + q (e^ε - 1)), δ' = q δ
+        let (eps, delta) = *d_out;
+        ((eps.exp_m1() / rate).ln_1p(), delta / rate)
+    }
+}
+
 /// Metrics
 #[derive(Clone)]
 pub struct SymmetricDistance;
@@ -33,6 +53,16 @@ impl SymmetricDistance {
     pub fn new() -> Self { SymmetricDistance }
 }
 
+/// The number of individuals whose records differ between two datasets, where one individual may contribute many records.
+#[derive(Clone, Default)]
+pub struct UserDistance;
+impl Metric for UserDistance {
+    type Distance = i32;
+}
+impl UserDistance {
+    pub fn new() -> Self { UserDistance }
+}
+
 #[derive(Clone)]
 pub struct HammingDistance;
 impl Metric for HammingDistance {
@@ -4,12 +4,22 @@
 //! Most of the implementaitons are generic, with the type parameter setting the underlying [`Domain::Carrier`]
 //! type.
 
-use std::collections::HashMap;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::ops::Bound;
 
-use crate::core::Domain;
-use crate::data::{Data, Form};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::core::{base_type_name, type_name, Domain};
+use crate::data::{Data, Element, Form};
+
+/// Describes a Domain that wraps a single element Domain, as in [`Domain::describe`].
+fn describe_with_element<D: Domain>(element_domain: Value) -> Value {
+    json!({ "type": base_type_name::<D>(), "carrier": type_name::<D::Carrier>(), "element_domain": element_domain })
+}
 
 /// A Domain that contains all members of the carrier type.
 pub struct AllDomain<T> {
@@ -37,7 +47,7 @@ impl<T> Domain for AllDomain<T> {
 /// A Domain that carries an underlying Domain in a Box.
 #[derive(Clone, PartialEq)]
 pub struct BoxDomain<D: Domain> {
-    element_domain: Box<D>
+    pub element_domain: Box<D>
 }
 impl<D: Domain> BoxDomain<D> {
     pub fn new(element_domain: Box<D>) -> Self {
@@ -49,6 +59,9 @@ impl<D: Domain> Domain for BoxDomain<D> {
     fn member(&self, val: &Self::Carrier) -> bool {
         self.element_domain.member(val)
     }
+    fn describe(&self) -> Value {
+        describe_with_element::<Self>(self.element_domain.describe())
+    }
 }
 
 
@@ -69,6 +82,9 @@ impl<D: Domain> Domain for DataDomain<D> where
         let val = val.as_form();
         self.form_domain.member(val)
     }
+    fn describe(&self) -> Value {
+        json!({ "type": base_type_name::<Self>(), "carrier": type_name::<Self::Carrier>(), "form_domain": self.form_domain.describe() })
+    }
 }
 
 
@@ -83,7 +99,7 @@ impl<T> IntervalDomain<T> {
         IntervalDomain { lower, upper }
     }
 }
-impl<T: Clone + PartialOrd> Domain for IntervalDomain<T> {
+impl<T: Clone + PartialOrd + Serialize> Domain for IntervalDomain<T> {
     type Carrier = T;
     fn member(&self, val: &Self::Carrier) -> bool {
         let lower_ok = match &self.lower {
@@ -97,6 +113,117 @@ impl<T: Clone + PartialOrd> Domain for IntervalDomain<T> {
             Bound::Unbounded => { true }
         }
     }
+    fn describe(&self) -> Value {
+        let describe_bound = |bound: &Bound<T>| match bound {
+            Bound::Included(bound) => json!({ "included": bound }),
+            Bound::Excluded(bound) => json!({ "excluded": bound }),
+            Bound::Unbounded => Value::Null,
+        };
+        json!({ "type": base_type_name::<Self>(), "carrier": type_name::<T>(), "lower": describe_bound(&self.lower), "upper": describe_bound(&self.upper) })
+    }
+}
+
+
+/// A Domain that contains only the values in an explicit set of categories.
+#[derive(Clone, PartialEq)]
+pub struct CategoricalDomain<T: Eq + Hash> {
+    pub categories: HashSet<T>,
+}
+impl<T: Eq + Hash> CategoricalDomain<T> {
+    pub fn new(categories: HashSet<T>) -> Self {
+        CategoricalDomain { categories }
+    }
+}
+impl<T: Clone + Eq + Hash + Serialize> Domain for CategoricalDomain<T> {
+    type Carrier = T;
+    fn member(&self, val: &Self::Carrier) -> bool {
+        self.categories.contains(val)
+    }
+    fn describe(&self) -> Value {
+        json!({ "type": base_type_name::<Self>(), "carrier": type_name::<T>(), "categories": self.categories })
+    }
+}
+
+
+/// A Domain that adds an explicit null value to an underlying Domain, using `Option`.
+#[derive(Clone, PartialEq)]
+pub struct OptionNullDomain<D: Domain> {
+    pub element_domain: D,
+}
+impl<D: Domain> OptionNullDomain<D> {
+    pub fn new(element_domain: D) -> Self {
+        OptionNullDomain { element_domain }
+    }
+}
+impl<D: Domain> Domain for OptionNullDomain<D> {
+    type Carrier = Option<D::Carrier>;
+    fn member(&self, val: &Self::Carrier) -> bool {
+        val.as_ref().is_none_or(|v| self.element_domain.member(v))
+    }
+    fn describe(&self) -> Value {
+        describe_with_element::<Self>(self.element_domain.describe())
+    }
+}
+
+
+/// A type with a value of its own that represents null (e.g., NaN for floats).
+pub trait InherentNull {
+    const NULL: Self;
+    fn is_null(&self) -> bool;
+}
+impl InherentNull for f32 {
+    const NULL: Self = f32::NAN;
+    fn is_null(&self) -> bool { self.is_nan() }
+}
+impl InherentNull for f64 {
+    const NULL: Self = f64::NAN;
+    fn is_null(&self) -> bool { self.is_nan() }
+}
+
+/// A Domain that admits the inherent null value of its carrier type, in addition to the members of an underlying Domain.
+#[derive(Clone, PartialEq)]
+pub struct InherentNullDomain<D: Domain> where
+    D::Carrier: InherentNull {
+    pub element_domain: D,
+}
+impl<D: Domain> InherentNullDomain<D> where
+    D::Carrier: InherentNull {
+    pub fn new(element_domain: D) -> Self {
+        InherentNullDomain { element_domain }
+    }
+}
+impl<D: Domain> Domain for InherentNullDomain<D> where
+    D::Carrier: InherentNull {
+    type Carrier = D::Carrier;
+    fn member(&self, val: &Self::Carrier) -> bool {
+        val.is_null() || self.element_domain.member(val)
+    }
+    fn describe(&self) -> Value {
+        describe_with_element::<Self>(self.element_domain.describe())
+    }
+}
+
+
+/// A Domain whose members may be null, and which can be stripped of its nulls.
+///
+/// `NonNull` is the carrier type that remains once nulls have been removed or imputed.
+pub trait NullableDomain: Domain {
+    type NonNull;
+    fn new_all() -> Self;
+    /// Returns the value of `val`, or `None` if it is null.
+    fn non_null(val: &Self::Carrier) -> Option<&Self::NonNull>;
+}
+impl<T> NullableDomain for OptionNullDomain<AllDomain<T>> {
+    type NonNull = T;
+    fn new_all() -> Self { Self::new(AllDomain::new()) }
+    fn non_null(val: &Self::Carrier) -> Option<&Self::NonNull> { val.as_ref() }
+}
+impl<T: InherentNull> NullableDomain for InherentNullDomain<AllDomain<T>> {
+    type NonNull = T;
+    fn new_all() -> Self { Self::new(AllDomain::new()) }
+    fn non_null(val: &Self::Carrier) -> Option<&Self::NonNull> {
+        if val.is_null() { None } else { Some(val) }
+    }
 }
 
 
@@ -113,6 +240,9 @@ impl<D0: Domain, D1: Domain> Domain for PairDomain<D0, D1> {
     fn member(&self, val: &Self::Carrier) -> bool {
         self.0.member(&val.0) && self.1.member(&val.1)
     }
+    fn describe(&self) -> Value {
+        json!({ "type": base_type_name::<Self>(), "carrier": type_name::<Self::Carrier>(), "element_domains": [self.0.describe(), self.1.describe()] })
+    }
 }
 
 
@@ -136,6 +266,9 @@ impl<D: Domain> Domain for MapDomain<D> {
     fn member(&self, val: &Self::Carrier) -> bool {
         val.iter().all(|e| self.element_domain.member(e.1))
     }
+    fn describe(&self) -> Value {
+        describe_with_element::<Self>(self.element_domain.describe())
+    }
 }
 
 
@@ -159,4 +292,92 @@ impl<D: Domain> Domain for VectorDomain<D> {
     fn member(&self, val: &Self::Carrier) -> bool {
         val.iter().all(|e| self.element_domain.member(e))
     }
+    fn describe(&self) -> Value {
+        describe_with_element::<Self>(self.element_domain.describe())
+    }
+}
+
+
+/// The Domain of a single dataframe column, with its concrete type erased.
+///
+/// This is implemented for every [`VectorDomain`] whose elements can be wrapped in [`Data`].
+pub trait ColumnDomain {
+    fn as_any(&self) -> &dyn Any;
+    fn box_clone(&self) -> Box<dyn ColumnDomain>;
+    fn eq(&self, other: &dyn Any) -> bool;
+    /// Predicate to test the contents of a [`Data`] for membership in the domain.
+    fn member_data(&self, val: &Data) -> bool;
+    /// Constructs a new column from the rows of `val` at `indices`, in order.
+    fn select_rows(&self, val: &Data, indices: &[usize]) -> Data;
+    /// Returns a description of the column's domain as JSON, as in [`Domain::describe`].
+    fn describe(&self) -> Value;
+}
+impl<D> ColumnDomain for VectorDomain<D> where
+    D: 'static + Domain, D::Carrier: 'static + Element + Clone + PartialEq {
+    fn as_any(&self) -> &dyn Any { self }
+    fn box_clone(&self) -> Box<dyn ColumnDomain> { Box::new(self.clone()) }
+    fn eq(&self, other: &dyn Any) -> bool { other.downcast_ref::<Self>().is_some_and(|o| o == self) }
+    fn member_data(&self, val: &Data) -> bool {
+        val.try_as_form().is_some_and(|v| self.member(v))
+    }
+    fn select_rows(&self, val: &Data, indices: &[usize]) -> Data {
+        let val: &Vec<D::Carrier> = val.as_form();
+        let rows: Vec<D::Carrier> = indices.iter().map(|i| val[*i].clone()).collect();
+        Data::new(rows)
+    }
+    fn describe(&self) -> Value {
+        Domain::describe(self)
+    }
+}
+
+/// A Domain that contains dataframes with a fixed set of named columns, each with its own Domain.
+pub struct DataFrameDomain {
+    pub columns: HashMap<String, Box<dyn ColumnDomain>>,
+}
+impl DataFrameDomain {
+    pub fn new() -> Self {
+        DataFrameDomain { columns: HashMap::new() }
+    }
+    /// Adds a column to the schema, replacing any existing column with the same key.
+    pub fn insert_column<D: ColumnDomain + 'static>(&mut self, key: &str, column_domain: D) {
+        self.columns.insert(key.to_owned(), Box::new(column_domain));
+    }
+    /// Looks up the Domain of a column, returning `None` if the column is missing or has a different Domain type.
+    pub fn column<D: 'static>(&self, key: &str) -> Option<&D> {
+        self.columns.get(key).and_then(|e| e.as_any().downcast_ref())
+    }
+    /// Constructs a new dataframe from the rows of `val` at `indices`, in order.
+    pub fn select_rows(&self, val: &HashMap<String, Data>, indices: &[usize]) -> HashMap<String, Data> {
+        self.columns.iter().map(|(k, v)| (k.clone(), v.select_rows(&val[k], indices))).collect()
+    }
+}
+impl Default for DataFrameDomain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Clone for DataFrameDomain {
+    fn clone(&self) -> Self {
+        let columns = self.columns.iter().map(|(k, v)| (k.clone(), v.box_clone())).collect();
+        DataFrameDomain { columns }
+    }
+}
+impl PartialEq for DataFrameDomain {
+    fn eq(&self, other: &Self) -> bool {
+        self.columns.len() == other.columns.len() && self.columns.iter().all(|(k, v)| {
+            other.columns.get(k).is_some_and(|o| v.eq(o.as_any()))
+        })
+    }
+}
+impl Domain for DataFrameDomain {
+    type Carrier = HashMap<String, Data>;
+    fn member(&self, val: &Self::Carrier) -> bool {
+        val.len() == self.columns.len() && self.columns.iter().all(|(k, v)| {
+            val.get(k).is_some_and(|e| v.member_data(e))
+        })
+    }
+    fn describe(&self) -> Value {
+        let columns: serde_json::Map<String, Value> = self.columns.iter().map(|(k, v)| (k.clone(), v.describe())).collect();
+        json!({ "type": base_type_name::<Self>(), "carrier": type_name::<Self::Carrier>(), "columns": columns })
+    }
 }
@@ -0,0 +1,29 @@
+//! Errors returned by OpenDP.
+
+use std::fmt;
+
+/// The ways an OpenDP operation can fail.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// An argument was not a member of the input [`Domain`](crate::core::Domain) it was checked against.
+    FailedMembership,
+    /// A constructor was given an input [`Domain`](crate::core::Domain) it can't operate on.
+    InvalidDomain(String),
+    /// An operation that isn't implemented yet, like the relation of some combinators.
+    NotImplemented(String),
+    /// A constructor or relation was given an argument outside the range it can handle.
+    InvalidArgument(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::FailedMembership => write!(f, "Argument is not a member of the input domain"),
+            Error::InvalidDomain(message) => write!(f, "Invalid domain: {}", message),
+            Error::NotImplemented(message) => write!(f, "Not implemented: {}", message),
+            Error::InvalidArgument(message) => write!(f, "Invalid argument: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
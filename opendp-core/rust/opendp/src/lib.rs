@@ -116,5 +116,6 @@ pub mod core;
 pub mod data;
 pub mod dist;
 pub mod dom;
+pub mod error;
 pub mod meas;
 pub mod trans;
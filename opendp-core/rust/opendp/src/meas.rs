@@ -3,11 +3,19 @@
 //! The different [`Measurement`] implementations in this module are accessed by calling the appropriate constructor function.
 //! Constructors are named in the form `make_xxx()`, where `xxx` indicates what the resulting `Measurement` does.
 
+use std::ops::Bound;
+
 use rand::Rng;
+use serde::Serialize;
+
+use crate::core::{Function, Measurement};
+use crate::dist::{HammingDistance, L2Sensitivity, L1Sensitivity, MaxDivergence, SmoothedMaxDivergence};
+use crate::dom::{AllDomain, IntervalDomain, VectorDomain};
+use crate::error::Error;
+use crate::trans::make_sample_and_aggregate;
 
-use crate::core::Measurement;
-use crate::dist::{L2Sensitivity, L1Sensitivity, MaxDivergence, SmoothedMaxDivergence};
-use crate::dom::{AllDomain, VectorDomain};
+/// A [`Measurement`] that is pure-DP under the Hamming distance, like those of [`make_private_quantile`] and [`make_sample_and_aggregate_median`].
+pub type HammingMeasurement<DI, DO> = Measurement<DI, DO, HammingDistance, MaxDivergence>;
 
 fn laplace(sigma: f64) -> f64 {
     let mut rng = rand::thread_rng();
@@ -180,6 +188,93 @@ pub fn make_base_gaussian<T>(sigma: f64) -> Measurement<AllDomain<T>, AllDomain<
     Measurement::new(input_domain, output_domain, function, input_metric, output_measure, privacy_relation)
 }
 
+/// Samples an index with probability proportional to `exp(scores[i] / scale)`, by the Gumbel-max trick.
+fn exponential_mechanism(scores: &[f64], scale: f64) -> usize {
+    let mut rng = rand::thread_rng();
+    let noisy = scores.iter().map(|score| {
+        let u: f64 = rng.gen_range(0.0, 1.0);
+        score / scale - (-u.ln()).ln()
+    });
+    noisy.enumerate()
+        .fold((0, f64::NEG_INFINITY), |best, (i, e)| if e > best.1 { (i, e) } else { best })
+        .0
+}
+
+/// Constructs a [`Measurement`] that releases the `alpha`-quantile of data in `[lower, upper]`, chosen from the public `candidates`.
+///
+/// Each candidate `c` is scored by `-|(1 - alpha) * #{x < c} - alpha * #{x > c}|`, and one is selected by the exponential mechanism.
+/// Changing one record moves the score by at most 1, so the privacy loss is `2 * d_in / scale`.
+/// Use `alpha = 0.5` for the median.
+///
+/// Fails if `candidates` is empty or has an entry outside `[lower, upper]`, or if `alpha` is outside `[0, 1]`.
+pub fn make_private_quantile<T>(lower: T, upper: T, candidates: Vec<T>, alpha: f64, scale: f64) -> Result<HammingMeasurement<VectorDomain<IntervalDomain<T>>, AllDomain<T>>, Error> where
+    T: 'static + Serialize + Clone + PartialOrd {
+    if candidates.is_empty() {
+        return Err(Error::InvalidArgument("candidates must not be empty".to_owned()))
+    }
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err(Error::InvalidArgument(format!("alpha must be in [0, 1], got {}", alpha)))
+    }
+    if !candidates.iter().all(|c| lower <= *c && *c <= upper) {
+        return Err(Error::InvalidArgument("candidates must be within [lower, upper]".to_owned()))
+    }
+    let input_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(lower), Bound::Included(upper)));
+    let output_domain = AllDomain::new();
+    let function = move |arg: &Vec<T>| -> T {
+        let scores: Vec<f64> = candidates.iter().map(|c| {
+            let below = arg.iter().filter(|x| *x < c).count() as f64;
+            let above = arg.iter().filter(|x| *x > c).count() as f64;
+            -((1.0 - alpha) * below - alpha * above).abs()
+        }).collect();
+        candidates[exponential_mechanism(&scores, scale)].clone()
+    };
+    let input_metric = HammingDistance::new();
+    let output_measure = MaxDivergence::new();
+    let privacy_relation = move |d_in: &i32, d_out: &f64| *d_out >= 2.0 * *d_in as f64 / scale;
+    Ok(Measurement::new(input_domain, output_domain, function, input_metric, output_measure, privacy_relation))
+}
+
+/// Constructs a [`Measurement`] that releases the private median of `function` over `k` random chunks of the input,
+/// by [`make_sample_and_aggregate`] followed by [`make_private_quantile`] over `candidates`.
+///
+/// Fails if `k` is zero, or for the same `candidates` as [`make_private_quantile`].
+pub fn make_sample_and_aggregate_median<TI, T>(function: Function<VectorDomain<AllDomain<TI>>, AllDomain<T>>, k: usize, lower: T, upper: T, candidates: Vec<T>, scale: f64) -> Result<HammingMeasurement<VectorDomain<AllDomain<TI>>, AllDomain<T>>, Error> where
+    TI: 'static + Clone, T: 'static + Serialize + Copy + PartialOrd {
+    let transformation = make_sample_and_aggregate(function, k, lower, upper)?;
+    let quantile = make_private_quantile(lower, upper, candidates, 0.5, scale)?;
+    let function = Function::make_chain(&quantile.function, &transformation.function);
+    let input_domain = Box::new(VectorDomain::new_all());
+    let output_domain = Box::new(AllDomain::new());
+    let input_metric = Box::new(HammingDistance::new());
+    let output_measure = Box::new(MaxDivergence::new());
+    // make_sample_and_aggregate is 1-stable, so the privacy loss is that of the quantile.
+    let privacy_relation = quantile.privacy_relation;
+    Ok(Measurement { input_domain, output_domain, function, input_metric, output_measure, privacy_relation })
+}
+
+/// Constructs a [`Measurement`] that releases the mean of `function` over `k` random chunks of the input, with Laplace noise.
+///
+/// Changing one record moves the mean of the `k` clamped results by at most `(upper - lower) / k`.
+/// The mean is released as an `f64`, so `T` must convert to `f64` without loss.
+///
+/// Fails if `k` is zero.
+pub fn make_sample_and_aggregate_mean<TI, T>(function: Function<VectorDomain<AllDomain<TI>>, AllDomain<T>>, k: usize, lower: T, upper: T, sigma: f64) -> Result<HammingMeasurement<VectorDomain<AllDomain<TI>>, AllDomain<f64>>, Error> where
+    TI: 'static + Clone, T: 'static + Serialize + Copy + PartialOrd + Into<f64> {
+    let transformation = make_sample_and_aggregate(function, k, lower, upper)?;
+    let sensitivity = (upper.into() - lower.into()) / k as f64;
+    let input_domain = VectorDomain::new_all();
+    let output_domain = AllDomain::new();
+    let function = move |arg: &Vec<TI>| -> f64 {
+        let results = transformation.function.eval(arg);
+        let mean = results.into_iter().map(Into::into).sum::<f64>() / k as f64;
+        mean + laplace(sigma)
+    };
+    let input_metric = HammingDistance::new();
+    let output_measure = MaxDivergence::new();
+    let privacy_relation = move |d_in: &i32, d_out: &f64| *d_out >= *d_in as f64 * sensitivity / sigma;
+    Ok(Measurement::new(input_domain, output_domain, function, input_metric, output_measure, privacy_relation))
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -193,4 +288,39 @@ mod tests {
         // TODO: Test for base_laplace
     }
 
+    #[test]
+    fn test_make_private_quantile() {
+        let measurement = make_private_quantile(0, 100, vec![0, 25, 50, 75, 100], 0.5, 0.1).unwrap();
+        let arg: Vec<i32> = (0..=100).collect();
+        let ret = measurement.function.eval(&arg);
+        assert_eq!(ret, 50);
+        assert!(measurement.privacy_relation.eval(&1, &20.0).unwrap());
+        assert!(!measurement.privacy_relation.eval(&1, &19.0).unwrap());
+        assert!(make_private_quantile(0, 100, vec![], 0.5, 0.1).is_err());
+        assert!(make_private_quantile(0, 100, vec![50], 1.5, 0.1).is_err());
+        assert!(make_private_quantile(0.0, 100.0, vec![50.0], f64::NAN, 0.1).is_err());
+        assert!(make_private_quantile(0, 100, vec![50, 101], 0.5, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_make_sample_and_aggregate() {
+        let function = Function::new(|arg: &Vec<f64>| arg.iter().sum::<f64>() / arg.len() as f64);
+        let median = make_sample_and_aggregate_median(function.clone(), 5, 0.0, 10.0, vec![0.0, 5.0, 10.0], 0.01).unwrap();
+        assert!(make_sample_and_aggregate_median(function.clone(), 5, 0.0, 10.0, vec![11.0], 0.01).is_err());
+        assert!(make_sample_and_aggregate_median(function.clone(), 0, 0.0, 10.0, vec![5.0], 0.01).is_err());
+        assert!(make_sample_and_aggregate_mean(function.clone(), 0, 0.0, 10.0, 1.0).is_err());
+        let mean = make_sample_and_aggregate_mean(function, 5, 0.0, 10.0, 1.0).unwrap();
+        let arg = vec![5.0; 100];
+        assert_eq!(median.function.eval(&arg), 5.0);
+        let _ret = mean.function.eval(&arg);
+        assert!(mean.privacy_relation.eval(&1, &2.0).unwrap());
+        assert!(!mean.privacy_relation.eval(&1, &1.0).unwrap());
+
+        // The mean of integer results isn't truncated to an integer.
+        let function = Function::new(|arg: &Vec<i32>| arg.len() as i32);
+        let mean = make_sample_and_aggregate_mean(function, 2, 0, 10, 1e-9).unwrap();
+        let ret = mean.function.eval(&vec![0; 7]);
+        assert!((ret - 3.5).abs() < 1e-6);
+    }
+
 }
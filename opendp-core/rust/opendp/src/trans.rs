@@ -3,17 +3,43 @@
 //! The different [`Transformation`] implementations in this module are accessed by calling the appropriate constructor function.
 //! Constructors are named in the form `make_xxx()`, where `xxx` indicates what the resulting `Transformation` does.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use std::fmt::Debug;
+use std::hash::Hash;
 use std::iter;
 use std::iter::Sum;
-use std::ops::Bound;
+use std::ops::{Bound, Neg};
 use std::str::FromStr;
 
-use crate::core::{Domain, Transformation};
+use rand::distributions::uniform::SampleUniform;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Domain, Function, StabilityRelation, Transformation};
+use crate::error::Error;
 use crate::data::{Data, Element};
-use crate::dist::{HammingDistance, L1Sensitivity, L2Sensitivity};
-use crate::dom::{AllDomain, IntervalDomain, MapDomain, VectorDomain};
+use crate::dist::{HammingDistance, L1Sensitivity, L2Sensitivity, SymmetricDistance, UserDistance};
+use crate::dom::{AllDomain, CategoricalDomain, InherentNull, InherentNullDomain, IntervalDomain, ColumnDomain, DataFrameDomain, NullableDomain, OptionNullDomain, VectorDomain};
+
+/// The Domain of vectors whose elements may be null, with nulls represented by `None`.
+pub type OptionVectorDomain<T> = VectorDomain<OptionNullDomain<AllDomain<T>>>;
+
+/// The Domain of vectors whose elements may be null, with nulls represented by the inherent null of `T` (e.g., NaN).
+pub type InherentVectorDomain<T> = VectorDomain<InherentNullDomain<AllDomain<T>>>;
+
+/// A [`Transformation`] between vector domains that is stable under the Hamming distance, like that of [`make_sample_and_aggregate`].
+pub type HammingVectorTransformation<DI, DO> = Transformation<VectorDomain<DI>, VectorDomain<DO>, HammingDistance, HammingDistance>;
+
+/// A [`Transformation`] on aggregates that is stable under the L1 sensitivity, like those of [`make_lipschitz_mul`] and [`make_affine`].
+pub type L1AggregateTransformation<T> = Transformation<AllDomain<T>, AllDomain<T>, L1Sensitivity<T>, L1Sensitivity<T>>;
+
+/// A [`Transformation`] that is stable under the symmetric distance, like those of [`make_filter`] and [`make_clamp_symmetric`].
+pub type SymmetricTransformation<DI, DO> = Transformation<DI, DO, SymmetricDistance, SymmetricDistance>;
+
+/// A [`Transformation`] from bounded vectors under the symmetric distance to their sum under the L1 sensitivity, like that of [`make_bounded_sum_symmetric`].
+pub type SymmetricSumTransformation<T> = Transformation<VectorDomain<IntervalDomain<T>>, AllDomain<T>, SymmetricDistance, L1Sensitivity<T>>;
 
 /// Utility to create a new [`Transformation`] with the given parameters, and 1-stability.
 fn new_1_stable_transformation<ID: Domain, OD: Domain>(input_domain: ID, output_domain: OD, function: impl Fn(&ID::Carrier) -> OD::Carrier + 'static) -> Transformation<ID, OD, HammingDistance, HammingDistance> {
@@ -23,6 +49,15 @@ fn new_1_stable_transformation<ID: Domain, OD: Domain>(input_domain: ID, output_
     Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation)
 }
 
+/// Utility to reinterpret `transformation`, which must map each record independently, as 1-stable under [`SymmetricDistance`].
+/// Adding or removing a record then adds or removes just the one record it maps to.
+fn row_by_row_symmetric<ID: Domain, OD: Domain>(transformation: Transformation<ID, OD, HammingDistance, HammingDistance>) -> SymmetricTransformation<ID, OD> {
+    let Transformation { input_domain, output_domain, function, stability_relation, .. } = transformation;
+    let (input_metric, output_metric) = (Box::new(SymmetricDistance::new()), Box::new(SymmetricDistance::new()));
+    let stability_relation = StabilityRelation { relation: stability_relation.relation };
+    Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation }
+}
+
 /// Constructs a [`Transformation`] of the identity function.
 pub fn make_identity<T: Clone>() -> Transformation<AllDomain<T>, AllDomain<T>, HammingDistance, HammingDistance> {
     let input_domain = AllDomain::<T>::new();
@@ -77,6 +112,163 @@ pub fn make_parse_series<T>(impute: bool) -> Transformation<VectorDomain<AllDoma
     new_1_stable_transformation(input_domain, output_domain, function)
 }
 
+/// Constructs a [`Transformation`] that parses a `Vec<String>` into a `Vec<Option<T>>`, with `None` for values that fail to parse.
+pub fn make_parse_series_option<T>() -> Transformation<VectorDomain<AllDomain<String>>, OptionVectorDomain<T>, HammingDistance, HammingDistance> where
+    T: FromStr {
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new(OptionNullDomain::new(AllDomain::new()));
+    let function = |arg: &Vec<String>| -> Vec<Option<T>> {
+        arg.iter().map(|e| e.parse().ok()).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Constructs a [`Transformation`] that parses a `Vec<String>` into a `Vec<T>`, with the inherent null of `T` (e.g., NaN)
+/// for values that fail to parse.
+pub fn make_parse_series_inherent<T>() -> Transformation<VectorDomain<AllDomain<String>>, InherentVectorDomain<T>, HammingDistance, HammingDistance> where
+    T: FromStr + InherentNull {
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new(InherentNullDomain::new(AllDomain::new()));
+    let function = |arg: &Vec<String>| -> Vec<T> {
+        arg.iter().map(|e| e.parse().unwrap_or(T::NULL)).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Constructs a [`Transformation`] that replaces each null with `constant`.
+pub fn make_impute_constant<D>(constant: D::NonNull) -> Transformation<VectorDomain<D>, VectorDomain<AllDomain<D::NonNull>>, HammingDistance, HammingDistance> where
+    D: NullableDomain, D::NonNull: 'static + Clone {
+    let input_domain = VectorDomain::new(D::new_all());
+    let output_domain = VectorDomain::new_all();
+    let function = move |arg: &Vec<D::Carrier>| -> Vec<D::NonNull> {
+        arg.iter().map(|e| D::non_null(e).unwrap_or(&constant).clone()).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// A floating-point type, for constructors like [`make_impute_uniform_float`] that only apply to floats.
+pub trait Float: 'static + CheckedArithmetic + Neg<Output=Self> + SampleUniform + InherentNull {}
+impl Float for f32 {}
+impl Float for f64 {}
+
+/// Constructs a [`Transformation`] that replaces each null with a value drawn uniformly from `[lower, upper)`.
+///
+/// Fails unless `lower < upper`, and `upper - lower` is finite.
+pub fn make_impute_uniform_float<D>(lower: D::NonNull, upper: D::NonNull) -> Result<Transformation<VectorDomain<D>, VectorDomain<AllDomain<D::NonNull>>, HammingDistance, HammingDistance>, Error> where
+    D: NullableDomain, D::NonNull: Float {
+    if !(lower < upper && upper.checked_add(-lower).is_some()) {
+        return Err(Error::InvalidArgument("lower must be less than upper, with a finite range between them".to_owned()))
+    }
+    let input_domain = VectorDomain::new(D::new_all());
+    let output_domain = VectorDomain::new_all();
+    let function = move |arg: &Vec<D::Carrier>| -> Vec<D::NonNull> {
+        let mut rng = rand::thread_rng();
+        arg.iter().map(|e| D::non_null(e).copied().unwrap_or_else(|| rng.gen_range(lower, upper))).collect()
+    };
+    Ok(new_1_stable_transformation(input_domain, output_domain, function))
+}
+
+/// Constructs a [`Transformation`] that removes all nulls.
+///
+/// Dropping records changes the length of the data, so this is 1-stable under [`SymmetricDistance`] rather than [`HammingDistance`].
+pub fn make_drop_null<D>() -> Transformation<VectorDomain<D>, VectorDomain<AllDomain<D::NonNull>>, SymmetricDistance, SymmetricDistance> where
+    D: NullableDomain, D::NonNull: 'static + Clone {
+    let input_domain = VectorDomain::new(D::new_all());
+    let output_domain = VectorDomain::new_all();
+    let function = |arg: &Vec<D::Carrier>| -> Vec<D::NonNull> {
+        arg.iter().filter_map(|e| D::non_null(e).cloned()).collect()
+    };
+    let input_metric = SymmetricDistance::new();
+    let output_metric = SymmetricDistance::new();
+    let stability_relation = |d_in: &i32, d_out: &i32| *d_out >= *d_in;
+    Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation)
+}
+
+/// Fallible conversion from `TI`, used by the cast transformations.
+///
+/// Conversions fail when the value can't be represented in the output type: integers out of range,
+/// non-finite floats to integers, or strings that don't parse. Floats are truncated toward zero when cast to integers.
+pub trait CastFrom<TI>: Sized {
+    fn cast_from(value: &TI) -> Option<Self>;
+}
+
+macro_rules! impl_cast_from {
+    ([$($ti:ty),*] => $tos:tt, |$v:ident| $e:expr) => {
+        $(impl_cast_from!(@one $ti => $tos, |$v| $e);)*
+    };
+    (@one $ti:ty => [$($to:ty),*], |$v:ident| $e:expr) => {
+        $(impl CastFrom<$ti> for $to {
+            // The same-type and widening integer casts go through TryFrom too, to share one macro.
+            #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+            fn cast_from($v: &$ti) -> Option<Self> { $e }
+        })*
+    };
+}
+impl_cast_from!([u8, u32, u64, i32, i64] => [u8, u32, u64, i32, i64], |v| Self::try_from(*v).ok());
+impl_cast_from!([u8, u32, u64, i32, i64, f32, f64] => [f32, f64], |v| Some(*v as Self));
+impl_cast_from!([f32, f64] => [u8, u32, u64, i32, i64], |v| if v.is_finite() { Self::try_from(v.trunc() as i128).ok() } else { None });
+impl_cast_from!([bool] => [u8, u32, u64, i32, i64, f32, f64], |v| Some(if *v { 1 as Self } else { 0 as Self }));
+impl_cast_from!([u8, u32, u64, i32, i64] => [bool], |v| Some(*v != 0));
+impl_cast_from!([f32, f64] => [bool], |v| if v.is_nan() { None } else { Some(*v != 0.0) });
+impl_cast_from!([bool] => [bool], |v| Some(*v));
+impl_cast_from!([u8, u32, u64, i32, i64, f32, f64, bool] => [String], |v| Some(v.to_string()));
+impl_cast_from!([String] => [u8, u32, u64, i32, i64, f32, f64, bool, String], |v| v.parse().ok());
+
+/// Constructs a [`Transformation`] that casts each element from `TI` to `TO`, with `None` for elements that fail to cast.
+pub fn make_cast<TI, TO>() -> Transformation<VectorDomain<AllDomain<TI>>, OptionVectorDomain<TO>, HammingDistance, HammingDistance> where
+    TO: CastFrom<TI> {
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new(OptionNullDomain::new(AllDomain::new()));
+    let function = |arg: &Vec<TI>| -> Vec<Option<TO>> {
+        arg.iter().map(TO::cast_from).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Constructs a [`Transformation`] that casts each element from `TI` to `TO`, with `default` for elements that fail to cast.
+pub fn make_cast_default<TI, TO>(default: TO) -> Transformation<VectorDomain<AllDomain<TI>>, VectorDomain<AllDomain<TO>>, HammingDistance, HammingDistance> where
+    TO: 'static + CastFrom<TI> + Clone {
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new_all();
+    let function = move |arg: &Vec<TI>| -> Vec<TO> {
+        arg.iter().map(|e| TO::cast_from(e).unwrap_or_else(|| default.clone())).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Constructs a [`Transformation`] that casts each element from `TI` to `TO`, with the inherent null of `TO` (e.g., NaN)
+/// for elements that fail to cast.
+pub fn make_cast_inherent<TI, TO>() -> Transformation<VectorDomain<AllDomain<TI>>, InherentVectorDomain<TO>, HammingDistance, HammingDistance> where
+    TO: CastFrom<TI> + InherentNull {
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new(InherentNullDomain::new(AllDomain::new()));
+    let function = |arg: &Vec<TI>| -> Vec<TO> {
+        arg.iter().map(|e| TO::cast_from(e).unwrap_or(TO::NULL)).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Constructs a [`Transformation`] that tests each element for equality with `value`.
+pub fn make_is_equal<TI>(value: TI) -> Transformation<VectorDomain<AllDomain<TI>>, VectorDomain<AllDomain<bool>>, HammingDistance, HammingDistance> where
+    TI: 'static + PartialEq {
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new_all();
+    let function = move |arg: &Vec<TI>| -> Vec<bool> {
+        arg.iter().map(|e| *e == value).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Constructs a [`Transformation`] that tests each element for null.
+pub fn make_is_null<D: NullableDomain>() -> Transformation<VectorDomain<D>, VectorDomain<AllDomain<bool>>, HammingDistance, HammingDistance> {
+    let input_domain = VectorDomain::new(D::new_all());
+    let output_domain = VectorDomain::new_all();
+    let function = |arg: &Vec<D::Carrier>| -> Vec<bool> {
+        arg.iter().map(|e| D::non_null(e).is_none()).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
 fn split_records<'a>(separator: &str, lines: &Vec<&'a str>) -> Vec<Vec<&'a str>> {
     fn split<'a>(line: &'a str, separator: &str) -> Vec<&'a str> {
         line.split(separator).into_iter().map(|e| e.trim()).collect()
@@ -111,6 +303,9 @@ fn conform_records<'a>(len: usize, records: &Vec<Vec<&'a str>>) -> Vec<Vec<&'a s
 
 pub type DataFrame = HashMap<String, Data>;
 
+/// A [`Transformation`] on dataframes that is 1-stable under the Hamming distance, like those of [`make_parse_column`] and [`make_select_column`].
+pub type DataFrameTransformation<OD> = Transformation<DataFrameDomain, OD, HammingDistance, HammingDistance>;
+
 fn create_dataframe(col_count: usize, records: &Vec<Vec<&str>>) -> DataFrame {
     let records = conform_records(col_count, &records);
     let mut cols = vec![Vec::new(); col_count];
@@ -122,13 +317,24 @@ fn create_dataframe(col_count: usize, records: &Vec<Vec<&str>>) -> DataFrame {
     cols.into_iter().enumerate().map(|(k, v)| (k.to_string(), Data::new(vec_str_to_string(v)))).collect()
 }
 
-pub fn create_dataframe_domain() -> MapDomain<AllDomain<Data>> {
-    MapDomain::new(AllDomain::new())
+/// Constructs a [`DataFrameDomain`] with `col_count` string columns, keyed `"0"`, `"1"`, ...
+pub fn create_dataframe_domain(col_count: usize) -> DataFrameDomain {
+    let keys: Vec<_> = (0..col_count).map(|e| e.to_string()).collect();
+    create_dataframe_domain_keys(&keys)
 }
 
-pub fn make_create_dataframe(col_count: usize) -> Transformation<VectorDomain<VectorDomain<AllDomain<String>>>, MapDomain<AllDomain<Data>>, HammingDistance, HammingDistance> {
+/// Constructs a [`DataFrameDomain`] with a string column for each of `keys`.
+pub fn create_dataframe_domain_keys(keys: &[String]) -> DataFrameDomain {
+    let mut domain = DataFrameDomain::new();
+    for key in keys {
+        domain.insert_column(key, VectorDomain::<AllDomain<String>>::new_all());
+    }
+    domain
+}
+
+pub fn make_create_dataframe(col_count: usize) -> Transformation<VectorDomain<VectorDomain<AllDomain<String>>>, DataFrameDomain, HammingDistance, HammingDistance> {
     let input_domain = VectorDomain::new(VectorDomain::new_all());
-    let output_domain = create_dataframe_domain();
+    let output_domain = create_dataframe_domain(col_count);
     let function = move |arg: &Vec<Vec<String>>| -> DataFrame {
         let arg = arg.into_iter().map(|e| vec_string_to_str(e)).collect();
         create_dataframe(col_count, &arg)
@@ -143,16 +349,303 @@ fn split_dataframe<'a>(separator: &str, col_count: usize, s: &str) -> DataFrame
     create_dataframe(col_count, &records)
 }
 
-pub fn make_split_dataframe(separator: Option<&str>, col_count: usize) -> Transformation<AllDomain<String>, MapDomain<AllDomain<Data>>, HammingDistance, HammingDistance> {
+pub fn make_split_dataframe(separator: Option<&str>, col_count: usize) -> Transformation<AllDomain<String>, DataFrameDomain, HammingDistance, HammingDistance> {
     let separator = separator.unwrap_or(",").to_owned();
     let input_domain = AllDomain::new();
-    let output_domain = create_dataframe_domain();
+    let output_domain = create_dataframe_domain(col_count);
     let function = move |arg: &String| -> DataFrame {
         split_dataframe(&separator, col_count, &arg)
     };
     new_1_stable_transformation(input_domain, output_domain, function)
 }
 
+/// Options for parsing CSV data with [`make_split_csv`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvOptions {
+    /// Character separating the fields of a record.
+    pub separator: char,
+    /// Character used to quote fields containing separators, quotes or line breaks.
+    pub quote: char,
+    /// Character used to escape a quote inside a quoted field. If `None`, quotes are escaped by doubling them, as in RFC 4180.
+    pub escape: Option<char>,
+    /// Whether the first record is a header naming the columns.
+    pub has_header: bool,
+}
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { separator: ',', quote: '"', escape: None, has_header: true }
+    }
+}
+
+fn split_csv(s: &str, options: &CsvOptions) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if Some(c) == options.escape && chars.peek().is_some() {
+                field.extend(chars.next());
+            } else if c == options.quote {
+                if options.escape.is_none() && chars.peek() == Some(&options.quote) {
+                    field.push(options.quote);
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == options.quote {
+            in_quotes = true;
+        } else if c == options.separator {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            record.push(std::mem::take(&mut field));
+            records.push(std::mem::take(&mut record));
+        } else {
+            field.push(c);
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+fn csv_dataframe(keys: &[String], options: &CsvOptions, s: &str) -> Result<DataFrame, Error> {
+    let mut records = split_csv(s, options);
+    let indices: Vec<usize> = if options.has_header && !records.is_empty() {
+        let header = records.remove(0);
+        keys.iter().map(|k| header.iter().position(|h| h == k)
+            .ok_or_else(|| Error::InvalidArgument(format!("CSV header is missing column {}", k)))).collect::<Result<_, _>>()?
+    } else {
+        (0..keys.len()).collect()
+    };
+    Ok(keys.iter().zip(indices).map(|(key, index)| {
+        let col: Vec<String> = records.iter().map(|record| record.get(index).cloned().unwrap_or_default()).collect();
+        (key.clone(), Data::new(col))
+    }).collect())
+}
+
+/// Constructs a [`Transformation`] that parses a CSV `String` (as described in RFC 4180) into a dataframe with string columns `keys`.
+///
+/// If `options.has_header` is set, columns are matched to `keys` by the names in the header, and the function panics
+/// if any of `keys` is missing from the header. Otherwise columns are assigned to `keys` by position.
+/// Fields missing from the end of a short record are read as empty strings.
+/// Each record after the header becomes one row, so the transformation is 1-stable.
+pub fn make_split_csv(keys: Vec<String>, options: CsvOptions) -> Transformation<AllDomain<String>, DataFrameDomain, HammingDistance, HammingDistance> {
+    let input_domain = AllDomain::new();
+    let output_domain = create_dataframe_domain_keys(&keys);
+    let function = move |arg: &String| -> DataFrame {
+        csv_dataframe(&keys, &options, arg).unwrap_or_else(|e| panic!("{}", e))
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// A primitive type that can be read from the structured input formats.
+pub trait InputElement: 'static + Element + Clone + PartialEq {
+    /// Converts a JSON value, returning `None` if it isn't a valid `Self`.
+    fn from_json(value: &serde_json::Value) -> Option<Self>;
+    /// Converts an Arrow array, with nulls and values that can't be cast read as `None`.
+    #[cfg(feature = "arrow")]
+    fn from_arrow(array: &arrow_array::ArrayRef) -> Result<Vec<Option<Self>>, arrow_schema::ArrowError>;
+}
+
+macro_rules! impl_input_element_int {
+    ($($type:ty => $arrow_type:ident),*) => {$(
+        impl InputElement for $type {
+            fn from_json(value: &serde_json::Value) -> Option<Self> {
+                value.as_i64().and_then(|v| <$type>::try_from(v).ok())
+                    .or_else(|| value.as_u64().and_then(|v| <$type>::try_from(v).ok()))
+            }
+            #[cfg(feature = "arrow")]
+            fn from_arrow(array: &arrow_array::ArrayRef) -> Result<Vec<Option<Self>>, arrow_schema::ArrowError> {
+                use arrow_array::cast::AsArray;
+                use arrow_array::types::ArrowPrimitiveType;
+                let array = arrow_cast::cast(array, &arrow_array::types::$arrow_type::DATA_TYPE)?;
+                Ok(array.as_primitive::<arrow_array::types::$arrow_type>().iter().collect())
+            }
+        }
+    )*}
+}
+impl_input_element_int!(u8 => UInt8Type, u32 => UInt32Type, u64 => UInt64Type, i32 => Int32Type, i64 => Int64Type);
+
+macro_rules! impl_input_element_float {
+    ($($type:ty => $arrow_type:ident),*) => {$(
+        impl InputElement for $type {
+            fn from_json(value: &serde_json::Value) -> Option<Self> {
+                value.as_f64().map(|v| v as $type)
+            }
+            #[cfg(feature = "arrow")]
+            fn from_arrow(array: &arrow_array::ArrayRef) -> Result<Vec<Option<Self>>, arrow_schema::ArrowError> {
+                use arrow_array::cast::AsArray;
+                use arrow_array::types::ArrowPrimitiveType;
+                let array = arrow_cast::cast(array, &arrow_array::types::$arrow_type::DATA_TYPE)?;
+                Ok(array.as_primitive::<arrow_array::types::$arrow_type>().iter().collect())
+            }
+        }
+    )*}
+}
+impl_input_element_float!(f32 => Float32Type, f64 => Float64Type);
+
+impl InputElement for bool {
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        value.as_bool()
+    }
+    #[cfg(feature = "arrow")]
+    fn from_arrow(array: &arrow_array::ArrayRef) -> Result<Vec<Option<Self>>, arrow_schema::ArrowError> {
+        use arrow_array::cast::AsArray;
+        let array = arrow_cast::cast(array, &arrow_schema::DataType::Boolean)?;
+        Ok(array.as_boolean().iter().collect())
+    }
+}
+
+impl InputElement for String {
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        value.as_str().map(|v| v.to_owned())
+    }
+    #[cfg(feature = "arrow")]
+    fn from_arrow(array: &arrow_array::ArrayRef) -> Result<Vec<Option<Self>>, arrow_schema::ArrowError> {
+        use arrow_array::cast::AsArray;
+        let array = arrow_cast::cast(array, &arrow_schema::DataType::Utf8)?;
+        Ok(array.as_string::<i32>().iter().map(|e| e.map(|e| e.to_owned())).collect())
+    }
+}
+
+/// A typed column to be read by an input transformation, such as [`make_parse_jsonl`].
+///
+/// The column is read into a `Vec<Option<T>>`, with `None` for values that are null, missing or not a valid `T`,
+/// so its Domain is an [`OptionVectorDomain`]. Use [`make_select_column_option`] to get it out of the dataframe,
+/// and then [`make_impute_constant`] or [`make_drop_null`] to handle the nulls.
+pub struct InputColumn {
+    pub key: String,
+    domain: Box<dyn ColumnDomain>,
+    read_json: fn(&[serde_json::Value], &str) -> Data,
+    #[cfg(feature = "arrow")]
+    read_arrow: fn(&[arrow_array::RecordBatch], &str) -> Data,
+}
+impl InputColumn {
+    pub fn new<T: InputElement>(key: &str) -> Self {
+        fn read_json<T: InputElement>(records: &[serde_json::Value], key: &str) -> Data {
+            let col: Vec<Option<T>> = records.iter().map(|e| e.get(key).and_then(T::from_json)).collect();
+            Data::new(col)
+        }
+        #[cfg(feature = "arrow")]
+        fn read_arrow<T: InputElement>(batches: &[arrow_array::RecordBatch], key: &str) -> Data {
+            let mut col: Vec<Option<T>> = Vec::new();
+            for batch in batches {
+                // A column that is missing, or whose type can't be cast to T, is all nulls.
+                match batch.column_by_name(key).and_then(|e| T::from_arrow(e).ok()) {
+                    Some(values) => col.extend(values),
+                    None => col.extend(iter::repeat_n(None, batch.num_rows())),
+                }
+            }
+            Data::new(col)
+        }
+        InputColumn {
+            key: key.to_owned(),
+            domain: Box::new(OptionVectorDomain::<T>::new(OptionNullDomain::new(AllDomain::new()))),
+            read_json: read_json::<T>,
+            #[cfg(feature = "arrow")]
+            read_arrow: read_arrow::<T>,
+        }
+    }
+}
+impl Clone for InputColumn {
+    fn clone(&self) -> Self {
+        InputColumn {
+            key: self.key.clone(),
+            domain: self.domain.box_clone(),
+            read_json: self.read_json,
+            #[cfg(feature = "arrow")]
+            read_arrow: self.read_arrow,
+        }
+    }
+}
+
+fn input_dataframe_domain(columns: &[InputColumn]) -> DataFrameDomain {
+    let columns = columns.iter().map(|e| (e.key.clone(), e.domain.box_clone())).collect();
+    DataFrameDomain { columns }
+}
+
+/// Constructs a [`Transformation`] that parses a `String` of JSON Lines into a dataframe with the given `columns`.
+///
+/// Each line becomes one row, so the transformation is 1-stable. Fields that are null, missing or have the wrong type
+/// are read as `None`, as are all the fields of lines that aren't JSON objects.
+pub fn make_parse_jsonl(columns: Vec<InputColumn>) -> Transformation<AllDomain<String>, DataFrameDomain, HammingDistance, HammingDistance> {
+    let input_domain = AllDomain::new();
+    let output_domain = input_dataframe_domain(&columns);
+    let function = move |arg: &String| -> DataFrame {
+        let records: Vec<serde_json::Value> = split_lines(arg).into_iter().map(|e| serde_json::from_str(e).unwrap_or(serde_json::Value::Null)).collect();
+        columns.iter().map(|e| (e.key.clone(), (e.read_json)(&records, &e.key))).collect()
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+#[cfg(feature = "arrow")]
+fn arrow_dataframe(columns: &[InputColumn], batches: &[arrow_array::RecordBatch]) -> DataFrame {
+    columns.iter().map(|e| (e.key.clone(), (e.read_arrow)(batches, &e.key))).collect()
+}
+
+/// Reads the record batches of Arrow IPC data, failing if any of it can't be decoded.
+#[cfg(feature = "arrow")]
+fn read_arrow_ipc(bytes: &[u8]) -> Result<Vec<arrow_array::RecordBatch>, arrow_schema::ArrowError> {
+    use std::io::Cursor;
+    use arrow_ipc::reader::{FileReader, StreamReader};
+    // The IPC file format starts with a magic string, otherwise assume the streaming format.
+    if bytes.starts_with(b"ARROW1") {
+        FileReader::try_new(Cursor::new(bytes), None)?.collect()
+    } else {
+        StreamReader::try_new(Cursor::new(bytes), None)?.collect()
+    }
+}
+
+/// Constructs a [`Transformation`] that reads Apache Arrow IPC data (file or stream format) into a dataframe with the given `columns`.
+///
+/// Each row becomes one row, so the transformation is 1-stable. Nulls, and values in columns that are missing or can't be cast
+/// to the column type, are read as `None`. The function panics on data that isn't valid Arrow IPC, including truncated data,
+/// rather than release a dataframe with rows silently dropped.
+#[cfg(feature = "arrow")]
+pub fn make_read_arrow_ipc(columns: Vec<InputColumn>) -> Transformation<AllDomain<Vec<u8>>, DataFrameDomain, HammingDistance, HammingDistance> {
+    let input_domain = AllDomain::new();
+    let output_domain = input_dataframe_domain(&columns);
+    let function = move |arg: &Vec<u8>| -> DataFrame {
+        let batches = read_arrow_ipc(arg).unwrap_or_else(|e| panic!("Invalid Arrow IPC data: {}", e));
+        arrow_dataframe(&columns, &batches)
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Reads the record batches of a Parquet file, failing if any of it can't be decoded.
+#[cfg(feature = "parquet")]
+fn read_parquet(bytes: &[u8]) -> Result<Vec<arrow_array::RecordBatch>, arrow_schema::ArrowError> {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    let bytes = bytes::Bytes::copy_from_slice(bytes);
+    ParquetRecordBatchReaderBuilder::try_new(bytes).and_then(|e| e.build())?.collect()
+}
+
+/// Constructs a [`Transformation`] that reads an Apache Parquet file into a dataframe with the given `columns`.
+///
+/// Each row becomes one row, so the transformation is 1-stable. Nulls, and values in columns that are missing or can't be cast
+/// to the column type, are read as `None`. The function panics on data that isn't a valid Parquet file, including a truncated
+/// file, rather than release a dataframe with rows silently dropped.
+#[cfg(feature = "parquet")]
+pub fn make_read_parquet(columns: Vec<InputColumn>) -> Transformation<AllDomain<Vec<u8>>, DataFrameDomain, HammingDistance, HammingDistance> {
+    let input_domain = AllDomain::new();
+    let output_domain = input_dataframe_domain(&columns);
+    let function = move |arg: &Vec<u8>| -> DataFrame {
+        let batches = read_parquet(arg).unwrap_or_else(|e| panic!("Invalid Parquet file: {}", e));
+        arrow_dataframe(&columns, &batches)
+    };
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
 fn replace_col(key: &str, df: &DataFrame, col: &Data) -> DataFrame {
     let mut df = df.clone();
     *df.get_mut(key).unwrap() = col.clone();
@@ -168,28 +661,256 @@ fn parse_column<T>(key: &str, impute: bool, df: &DataFrame) -> DataFrame where
     replace_col(key, &df, &col.into())
 }
 
-pub fn make_parse_column<T>(key: &str, impute: bool) -> Transformation<MapDomain<AllDomain<Data>>, MapDomain<AllDomain<Data>>, HammingDistance, HammingDistance> where
+/// Checks that `input_domain` has a column `key` with the Domain `D`.
+fn check_column<D: 'static>(input_domain: &DataFrameDomain, key: &str) -> Result<(), Error> {
+    if !input_domain.columns.contains_key(key) {
+        return Err(Error::InvalidDomain(format!("Missing dataframe column {}", key)));
+    }
+    if input_domain.column::<D>(key).is_none() {
+        return Err(Error::InvalidDomain(format!("Dataframe column {} has the wrong type", key)));
+    }
+    Ok(())
+}
+
+/// Constructs a [`Transformation`] that parses the string column `key` of a dataframe into a column of `T`.
+///
+/// The output domain is `input_domain`, with the column `key` updated to contain `T`.
+pub fn make_parse_column<T>(input_domain: &DataFrameDomain, key: &str, impute: bool) -> Result<DataFrameTransformation<DataFrameDomain>, Error> where
     T: 'static + Element + Clone + PartialEq + FromStr + Default, T::Err: Debug {
+    check_column::<VectorDomain<AllDomain<String>>>(input_domain, key)?;
     let key = key.to_owned();
-    let input_domain = create_dataframe_domain();
-    let output_domain = create_dataframe_domain();
+    let input_domain = input_domain.clone();
+    let mut output_domain = input_domain.clone();
+    output_domain.insert_column(&key, VectorDomain::<AllDomain<T>>::new_all());
     let function = move |arg: &DataFrame| -> DataFrame {
         parse_column::<T>(&key, impute, arg)
     };
-    new_1_stable_transformation(input_domain, output_domain, function)
+    Ok(new_1_stable_transformation(input_domain, output_domain, function))
 }
 
-pub fn make_select_column<T>(key: &str) -> Transformation<MapDomain<AllDomain<Data>>, VectorDomain<AllDomain<T>>, HammingDistance, HammingDistance> where
-    T: 'static + Element + Clone + PartialEq {
+fn select_column<D>(input_domain: &DataFrameDomain, key: &str, output_domain: VectorDomain<D>) -> Result<DataFrameTransformation<VectorDomain<D>>, Error> where
+    D: 'static + Domain, D::Carrier: 'static + Element + Clone + PartialEq {
+    check_column::<VectorDomain<D>>(input_domain, key)?;
     let key = key.to_owned();
-    let input_domain = create_dataframe_domain();
-    let output_domain = VectorDomain::new_all();
-    let function = move |arg: &DataFrame| -> Vec<T> {
+    let input_domain = input_domain.clone();
+    let function = move |arg: &DataFrame| -> Vec<D::Carrier> {
         let ret = arg.get(&key).expect("Missing dataframe column");
-        let ret: &Vec<T> = ret.as_form();
+        let ret: &Vec<D::Carrier> = ret.as_form();
         ret.clone()
     };
-    new_1_stable_transformation(input_domain, output_domain, function)
+    Ok(new_1_stable_transformation(input_domain, output_domain, function))
+}
+
+/// Constructs a [`Transformation`] that extracts the column `key` of a dataframe, which must contain `T`.
+pub fn make_select_column<T>(input_domain: &DataFrameDomain, key: &str) -> Result<DataFrameTransformation<VectorDomain<AllDomain<T>>>, Error> where
+    T: 'static + Element + Clone + PartialEq {
+    select_column(input_domain, key, VectorDomain::new_all())
+}
+
+/// Like [`make_select_column`], but 1-stable under [`SymmetricDistance`], for dataframes like those of [`make_filter_dataframe`].
+pub fn make_select_column_symmetric<T>(input_domain: &DataFrameDomain, key: &str) -> Result<SymmetricTransformation<DataFrameDomain, VectorDomain<AllDomain<T>>>, Error> where
+    T: 'static + Element + Clone + PartialEq {
+    make_select_column::<T>(input_domain, key).map(row_by_row_symmetric)
+}
+
+/// Constructs a [`Transformation`] that extracts the column `key` of a dataframe, which must contain `Option<T>`,
+/// like the columns read by [`make_parse_jsonl`].
+pub fn make_select_column_option<T>(input_domain: &DataFrameDomain, key: &str) -> Result<DataFrameTransformation<OptionVectorDomain<T>>, Error> where
+    T: 'static + Element + Clone + PartialEq {
+    select_column(input_domain, key, VectorDomain::new(OptionNullDomain::new(AllDomain::new())))
+}
+
+/// A predicate on values of type `T`, used by [`make_filter`] and [`make_filter_dataframe`].
+///
+/// Predicates are plain data, so they can be serialized (e.g., as JSON like `{"and": [{"ge": 65}, {"not": {"eq": 99}}]}`)
+/// and passed across the FFI boundary.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Predicate<T> {
+    Eq(T),
+    Ne(T),
+    Lt(T),
+    Le(T),
+    Gt(T),
+    Ge(T),
+    /// Inclusive on both ends.
+    Between(T, T),
+    In(Vec<T>),
+    And(Vec<Predicate<T>>),
+    Or(Vec<Predicate<T>>),
+    Not(Box<Predicate<T>>),
+}
+impl<T: PartialOrd> Predicate<T> {
+    pub fn eval(&self, value: &T) -> bool {
+        match self {
+            Predicate::Eq(x) => value == x,
+            Predicate::Ne(x) => value != x,
+            Predicate::Lt(x) => value < x,
+            Predicate::Le(x) => value <= x,
+            Predicate::Gt(x) => value > x,
+            Predicate::Ge(x) => value >= x,
+            Predicate::Between(lower, upper) => lower <= value && value <= upper,
+            Predicate::In(xs) => xs.contains(value),
+            Predicate::And(ps) => ps.iter().all(|p| p.eval(value)),
+            Predicate::Or(ps) => ps.iter().any(|p| p.eval(value)),
+            Predicate::Not(p) => !p.eval(value),
+        }
+    }
+}
+
+/// Constructs a [`Transformation`] of the identity function on `input_domain`, from [`HammingDistance`] to [`SymmetricDistance`].
+///
+/// Changing one record is the same as removing it and adding its replacement, so this is 2-stable.
+/// Chain it after transformations under [`HammingDistance`], like the readers, to filter their output with [`make_filter`].
+/// The stability relation fails for distances where `2 * d` overflows.
+pub fn make_hamming_to_symmetric<D>(input_domain: D) -> Transformation<D, D, HammingDistance, SymmetricDistance> where
+    D: 'static + Domain, D::Carrier: Clone {
+    let output_domain = input_domain.clone();
+    let function = |arg: &D::Carrier| -> D::Carrier { arg.clone() };
+    let input_metric = HammingDistance::new();
+    let output_metric = SymmetricDistance::new();
+    let stability_relation = StabilityRelation::new_fallible(|d_in: &i32, d_out: &i32| {
+        let d_in = d_in.checked_mul(2).ok_or_else(|| Error::InvalidArgument(format!("d_in * 2 overflows for d_in = {}", d_in)))?;
+        Ok(*d_out >= d_in)
+    });
+    let (input_domain, output_domain, function) = (Box::new(input_domain), Box::new(output_domain), Function::new(function));
+    let (input_metric, output_metric) = (Box::new(input_metric), Box::new(output_metric));
+    Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation }
+}
+
+/// Constructs a [`Transformation`] that keeps only the elements satisfying `predicate`.
+///
+/// Removing records changes the length of the data, so this is 1-stable under [`SymmetricDistance`].
+pub fn make_filter<T>(predicate: Predicate<T>) -> Transformation<VectorDomain<AllDomain<T>>, VectorDomain<AllDomain<T>>, SymmetricDistance, SymmetricDistance> where
+    T: 'static + Clone + PartialOrd {
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new_all();
+    let function = move |arg: &Vec<T>| -> Vec<T> {
+        arg.iter().filter(|e| predicate.eval(e)).cloned().collect()
+    };
+    let input_metric = SymmetricDistance::new();
+    let output_metric = SymmetricDistance::new();
+    let stability_relation = |d_in: &i32, d_out: &i32| *d_out >= *d_in;
+    Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation)
+}
+
+/// Constructs a [`Transformation`] that keeps only the rows of a dataframe whose column `key` satisfies `predicate`.
+///
+/// Removing records changes the length of the data, so this is 1-stable under [`SymmetricDistance`].
+pub fn make_filter_dataframe<T>(input_domain: &DataFrameDomain, key: &str, predicate: Predicate<T>) -> Result<Transformation<DataFrameDomain, DataFrameDomain, SymmetricDistance, SymmetricDistance>, Error> where
+    T: 'static + Element + Clone + PartialOrd {
+    check_column::<VectorDomain<AllDomain<T>>>(input_domain, key)?;
+    let key = key.to_owned();
+    let input_domain = input_domain.clone();
+    let output_domain = input_domain.clone();
+    let schema = input_domain.clone();
+    let function = move |arg: &DataFrame| -> DataFrame {
+        let col: &Vec<T> = arg.get(&key).expect("Missing dataframe column").as_form();
+        let indices: Vec<usize> = col.iter().enumerate().filter(|(_, e)| predicate.eval(e)).map(|(i, _)| i).collect();
+        schema.select_rows(arg, &indices)
+    };
+    let input_metric = SymmetricDistance::new();
+    let output_metric = SymmetricDistance::new();
+    let stability_relation = |d_in: &i32, d_out: &i32| *d_out >= *d_in;
+    Ok(Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation))
+}
+
+/// Selects up to `k` of `indices` uniformly at random, by reservoir sampling.
+fn reservoir_sample(indices: &[usize], k: usize) -> Vec<usize> {
+    let mut rng = rand::thread_rng();
+    let mut reservoir: Vec<usize> = indices.iter().take(k).cloned().collect();
+    for (i, index) in indices.iter().enumerate().skip(k) {
+        let j = rng.gen_range(0, i + 1);
+        if j < k {
+            reservoir[j] = *index;
+        }
+    }
+    reservoir
+}
+
+/// Constructs a [`Transformation`] of the identity function on the dataframes of `input_domain`, from [`HammingDistance`] to [`UserDistance`].
+///
+/// Changing one row can move it from one individual to another, changing the data of both, so this is 2-stable.
+/// Chain it after the readers to bound the contributions of each individual with [`make_bound_user_contributions`].
+/// The stability relation fails for distances where `2 * d` overflows.
+pub fn make_hamming_to_user(input_domain: &DataFrameDomain) -> Transformation<DataFrameDomain, DataFrameDomain, HammingDistance, UserDistance> {
+    let input_domain = input_domain.clone();
+    let output_domain = input_domain.clone();
+    let function = |arg: &DataFrame| -> DataFrame { arg.clone() };
+    let input_metric = HammingDistance::new();
+    let output_metric = UserDistance::new();
+    let stability_relation = StabilityRelation::new_fallible(|d_in: &i32, d_out: &i32| {
+        let d_in = d_in.checked_mul(2).ok_or_else(|| Error::InvalidArgument(format!("d_in * 2 overflows for d_in = {}", d_in)))?;
+        Ok(*d_out >= d_in)
+    });
+    let (input_domain, output_domain, function) = (Box::new(input_domain), Box::new(output_domain), Function::new(function));
+    let (input_metric, output_metric) = (Box::new(input_metric), Box::new(output_metric));
+    Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation }
+}
+
+/// Constructs a [`Transformation`] that keeps at most `k` rows of a dataframe for each distinct value of the identifier column `id_key`.
+///
+/// Rows are chosen uniformly at random for each identifier, and kept in their original order. Since each individual
+/// contributes at most `k` rows to the output, a [`UserDistance`] of `d` becomes a [`SymmetricDistance`] of at most `k * d`,
+/// so record-level measurements chained after this one give user-level guarantees.
+///
+/// Fails if `k` doesn't fit in an `i32` distance. The stability relation fails for distances where `k * d` overflows.
+pub fn make_bound_user_contributions<TK>(input_domain: &DataFrameDomain, id_key: &str, k: u32) -> Result<Transformation<DataFrameDomain, DataFrameDomain, UserDistance, SymmetricDistance>, Error> where
+    TK: 'static + Element + Clone + PartialEq + Eq + Hash {
+    check_column::<VectorDomain<AllDomain<TK>>>(input_domain, id_key)?;
+    let k_distance = i32::try_from(k).map_err(|_| Error::InvalidArgument(format!("k must be at most {}, got {}", i32::MAX, k)))?;
+    let id_key = id_key.to_owned();
+    let input_domain = input_domain.clone();
+    let output_domain = input_domain.clone();
+    let schema = input_domain.clone();
+    let function = move |arg: &DataFrame| -> DataFrame {
+        let ids: &Vec<TK> = arg.get(&id_key).expect("Missing dataframe column").as_form();
+        let mut groups: HashMap<&TK, Vec<usize>> = HashMap::new();
+        for (i, id) in ids.iter().enumerate() {
+            groups.entry(id).or_default().push(i);
+        }
+        let mut indices: Vec<usize> = groups.values().flat_map(|e| reservoir_sample(e, k as usize)).collect();
+        indices.sort_unstable();
+        schema.select_rows(arg, &indices)
+    };
+    let input_metric = UserDistance::new();
+    let output_metric = SymmetricDistance::new();
+    let stability_relation = StabilityRelation::new_fallible(move |d_in: &i32, d_out: &i32| {
+        let d_in = d_in.checked_mul(k_distance).ok_or_else(|| Error::InvalidArgument(format!("d_in * k overflows for d_in = {}", d_in)))?;
+        Ok(*d_out >= d_in)
+    });
+    let (input_domain, output_domain, function) = (Box::new(input_domain), Box::new(output_domain), Function::new(function));
+    let (input_metric, output_metric) = (Box::new(input_metric), Box::new(output_metric));
+    Ok(Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation })
+}
+
+/// Constructs a [`Transformation`] that splits a dataframe into one partition per value in `keys`, by the value of column `key`.
+///
+/// Partition `i` holds the rows whose `key` is `keys[i]`, in their original order. Rows whose `key` is not in `keys` are dropped,
+/// so the partitions never reveal which other values occur in the data. Each row lands in at most one partition,
+/// so this is 1-stable under [`SymmetricDistance`], summed over the partitions.
+pub fn make_partition_by<TK>(input_domain: &DataFrameDomain, key: &str, keys: Vec<TK>) -> Result<Transformation<DataFrameDomain, VectorDomain<DataFrameDomain>, SymmetricDistance, SymmetricDistance>, Error> where
+    TK: 'static + Element + Clone + PartialEq + Eq + Hash {
+    check_column::<VectorDomain<AllDomain<TK>>>(input_domain, key)?;
+    let key = key.to_owned();
+    let input_domain = input_domain.clone();
+    let output_domain = VectorDomain::new(input_domain.clone());
+    let schema = input_domain.clone();
+    let partitions: HashMap<TK, usize> = keys.iter().enumerate().map(|(i, k)| (k.clone(), i)).collect();
+    let function = move |arg: &DataFrame| -> Vec<DataFrame> {
+        let col: &Vec<TK> = arg.get(&key).expect("Missing dataframe column").as_form();
+        let mut indices = vec![Vec::new(); keys.len()];
+        for (i, e) in col.iter().enumerate() {
+            if let Some(partition) = partitions.get(e) {
+                indices[*partition].push(i);
+            }
+        }
+        indices.iter().map(|e| schema.select_rows(arg, e)).collect()
+    };
+    let input_metric = SymmetricDistance::new();
+    let output_metric = SymmetricDistance::new();
+    let stability_relation = |d_in: &i32, d_out: &i32| *d_out >= *d_in;
+    Ok(Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation))
 }
 
 fn clamp<T: Copy + PartialOrd>(lower: T, upper: T, x: &Vec<T>) -> Vec<T> {
@@ -200,7 +921,7 @@ fn clamp<T: Copy + PartialOrd>(lower: T, upper: T, x: &Vec<T>) -> Vec<T> {
 }
 
 pub fn make_clamp<T>(lower: T, upper: T) -> Transformation<VectorDomain<AllDomain<T>>, VectorDomain<IntervalDomain<T>>, HammingDistance, HammingDistance> where
-    T: 'static + Copy + PartialOrd {
+    T: 'static + Serialize + Copy + PartialOrd {
     let input_domain = VectorDomain::new_all();
     let output_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(lower), Bound::Included(upper)));
     let function = move |arg: &Vec<T>| -> Vec<T> {
@@ -209,39 +930,152 @@ pub fn make_clamp<T>(lower: T, upper: T) -> Transformation<VectorDomain<AllDomai
     new_1_stable_transformation(input_domain, output_domain, function)
 }
 
-pub fn make_bounded_sum_l1<T>(lower: T, upper: T) -> Transformation<VectorDomain<IntervalDomain<T>>, AllDomain<T>, HammingDistance, L1Sensitivity<T>> where
-    T: 'static + Clone + PartialOrd + Sum<T> /* + Sub<Output=T> + Mul<Output=T> + From<i32> */ {
-    let input_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(lower.clone()), Bound::Included(upper.clone())));
-    let output_domain = AllDomain::new();
-    let function = |arg: &Vec<T>| -> T {
-        arg.iter().cloned().sum()
+/// Like [`make_clamp`], but 1-stable under [`SymmetricDistance`], for data like that of [`make_filter`].
+pub fn make_clamp_symmetric<T>(lower: T, upper: T) -> SymmetricTransformation<VectorDomain<AllDomain<T>>, VectorDomain<IntervalDomain<T>>> where
+    T: 'static + Serialize + Copy + PartialOrd {
+    row_by_row_symmetric(make_clamp(lower, upper))
+}
+
+/// Constructs a [`Transformation`] that splits its input into `k` disjoint random chunks, applies `function` to each chunk,
+/// and clamps the `k` results to `[lower, upper]`.
+///
+/// This is the first half of sample-and-aggregate: `function` may be any estimator, since changing one record
+/// changes at most one chunk, and so at most one of the clamped results. Chain with an aggregate over
+/// `VectorDomain<IntervalDomain<T>>`, like [`make_private_quantile`](crate::meas::make_private_quantile).
+///
+/// Fails if `k` is zero.
+pub fn make_sample_and_aggregate<TI, T>(function: Function<VectorDomain<AllDomain<TI>>, AllDomain<T>>, k: usize, lower: T, upper: T) -> Result<HammingVectorTransformation<AllDomain<TI>, IntervalDomain<T>>, Error> where
+    TI: 'static + Clone, T: 'static + Serialize + Copy + PartialOrd {
+    if k == 0 {
+        return Err(Error::InvalidArgument("k must be positive".to_owned()))
+    }
+    let chunked = Function::<VectorDomain<AllDomain<TI>>, VectorDomain<AllDomain<T>>>::new(move |arg: &Vec<TI>| -> Vec<T> {
+        let mut shuffled = arg.clone();
+        shuffled.shuffle(&mut rand::thread_rng());
+        (0..k).map(|i| {
+            let chunk = shuffled[i * shuffled.len() / k..(i + 1) * shuffled.len() / k].to_vec();
+            function.eval(&chunk)
+        }).collect()
+    });
+    let clamp = make_clamp(lower, upper);
+    let function = Function::make_chain(&clamp.function, &chunked);
+    Ok(new_1_stable_transformation(VectorDomain::new_all(), *clamp.output_domain, move |arg: &Vec<TI>| function.eval(arg)))
+}
+
+/// Constructs a [`Transformation`] that replaces each value not in `categories` with `null`.
+///
+/// The output domain admits the `categories` and `null`.
+pub fn make_clamp_categorical<T>(categories: Vec<T>, null: T) -> Transformation<VectorDomain<AllDomain<T>>, VectorDomain<CategoricalDomain<T>>, HammingDistance, HammingDistance> where
+    T: 'static + Serialize + Clone + Eq + Hash {
+    let categories: HashSet<T> = categories.into_iter().collect();
+    let mut output_categories = categories.clone();
+    output_categories.insert(null.clone());
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new(CategoricalDomain::new(output_categories));
+    let function = move |arg: &Vec<T>| -> Vec<T> {
+        arg.iter().map(|e| if categories.contains(e) { e.clone() } else { null.clone() }).collect()
     };
-    let input_metric = HammingDistance::new();
-    let output_metric = L1Sensitivity::new();
-    let stability_relation = move |_d_in: &i32, _d_out: &T| {
-        // TODO: Sort out traits to make this work
-        // *d_out >= (upper.clone() - lower.clone()) * T::from(*d_in)
-        unimplemented!()
+    new_1_stable_transformation(input_domain, output_domain, function)
+}
+
+/// Constructs a [`Transformation`] that maps each value to the index of its bin, given strictly increasing `edges`.
+///
+/// Bin `i` holds the values in `[edges[i - 1], edges[i])`, so values below the first edge land in bin 0,
+/// and values at or above the last edge land in bin `edges.len()`. Values that aren't comparable to themselves
+/// (e.g., NaN) belong to no bin, and land in the unknown bin, `edges.len() + 1`.
+///
+/// Fails if `edges` aren't strictly increasing, or if any edge isn't comparable to itself (e.g., NaN).
+pub fn make_find_bin<T>(edges: Vec<T>) -> Result<HammingVectorTransformation<AllDomain<T>, IntervalDomain<usize>>, Error> where
+    T: 'static + PartialOrd {
+    if !edges.iter().all(|e| e.partial_cmp(e).is_some()) || !edges.windows(2).all(|w| w[0] < w[1]) {
+        return Err(Error::InvalidArgument("edges must be strictly increasing".to_owned()))
+    }
+    let input_domain = VectorDomain::new_all();
+    let unknown = edges.len() + 1;
+    let output_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(0), Bound::Included(unknown)));
+    let function = move |arg: &Vec<T>| -> Vec<usize> {
+        arg.iter().map(|e| if e.partial_cmp(e).is_some() { edges.partition_point(|edge| edge <= e) } else { unknown }).collect()
     };
-    Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation)
+    Ok(new_1_stable_transformation(input_domain, output_domain, function))
+}
+
+/// Constructs a [`Transformation`] that maps each value to its index in `categories`.
+///
+/// Values not in `categories` map to the unknown index, `categories.len()`.
+///
+/// Fails if `categories` aren't distinct.
+pub fn make_find<T>(categories: Vec<T>) -> Result<HammingVectorTransformation<AllDomain<T>, IntervalDomain<usize>>, Error> where
+    T: 'static + Eq + Hash {
+    let unknown = categories.len();
+    let indices: HashMap<T, usize> = categories.into_iter().enumerate().map(|(i, e)| (e, i)).collect();
+    if indices.len() != unknown {
+        return Err(Error::InvalidArgument("categories must be distinct".to_owned()))
+    }
+    let input_domain = VectorDomain::new_all();
+    let output_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(0), Bound::Included(unknown)));
+    let function = move |arg: &Vec<T>| -> Vec<usize> {
+        arg.iter().map(|e| indices.get(e).cloned().unwrap_or(unknown)).collect()
+    };
+    Ok(new_1_stable_transformation(input_domain, output_domain, function))
+}
+
+pub fn make_bounded_sum_l1<T>(lower: T, upper: T) -> Transformation<VectorDomain<IntervalDomain<T>>, AllDomain<T>, HammingDistance, L1Sensitivity<T>> where
+    T: 'static + Serialize + Clone + PartialOrd + Sum<T> /* + Sub<Output=T> + Mul<Output=T> + From<i32> */ {
+    let input_domain = Box::new(VectorDomain::new(IntervalDomain::new(Bound::Included(lower), Bound::Included(upper))));
+    let output_domain = Box::new(AllDomain::new());
+    let function = Function::new(|arg: &Vec<T>| -> T {
+        arg.iter().cloned().sum()
+    });
+    let input_metric = Box::new(HammingDistance::new());
+    let output_metric = Box::new(L1Sensitivity::new());
+    // TODO: Sort out traits to make this work
+    // *d_out >= (upper.clone() - lower.clone()) * T::from(*d_in)
+    let stability_relation = StabilityRelation::new_not_implemented("make_bounded_sum_l1");
+    Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation }
 }
 
 pub fn make_bounded_sum_l2<T>(lower: T, upper: T) -> Transformation<VectorDomain<IntervalDomain<T>>, AllDomain<T>, HammingDistance, L2Sensitivity<T>> where
-    T: 'static + Clone + PartialOrd + Sum<T> /* + Sub<Output=T> + Mul<Output=T> + From<i32> */ {
-    let input_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(lower.clone()), Bound::Included(upper.clone())));
-    let output_domain = AllDomain::new();
-    let function = |arg: &Vec<T>| -> T {
+    T: 'static + Serialize + Clone + PartialOrd + Sum<T> /* + Sub<Output=T> + Mul<Output=T> + From<i32> */ {
+    let input_domain = Box::new(VectorDomain::new(IntervalDomain::new(Bound::Included(lower), Bound::Included(upper))));
+    let output_domain = Box::new(AllDomain::new());
+    let function = Function::new(|arg: &Vec<T>| -> T {
         arg.iter().cloned().sum()
-    };
+    });
     // NOTE: can't make Q a type argument because you need to select a different stability relation depending on the concrete type
-    let input_metric = HammingDistance::new();
-    let output_metric = L2Sensitivity::new();
-    let stability_relation = move |_d_in: &i32, _d_out: &T| {
-        // TODO: Sort out traits to make this work
-        // *d_out >= (upper.clone() - lower.clone()) * T::from(*d_in)
-        unimplemented!()
+    let input_metric = Box::new(HammingDistance::new());
+    let output_metric = Box::new(L2Sensitivity::new());
+    // TODO: Sort out traits to make this work
+    // *d_out >= (upper.clone() - lower.clone()) * T::from(*d_in)
+    let stability_relation = StabilityRelation::new_not_implemented("make_bounded_sum_l2");
+    Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation }
+}
+
+/// Constructs a [`Transformation`] that sums data in `[lower, upper]`, under [`SymmetricDistance`].
+///
+/// Adding or removing a record moves the sum by at most `max(|lower|, |upper|)`, so the sensitivity is `d_in` times that.
+/// Chain it after [`make_clamp_symmetric`] to sum data like that of [`make_filter`].
+///
+/// Fails unless `lower <= upper`, with representable absolute values. The stability relation fails for distances
+/// where the sensitivity overflows.
+pub fn make_bounded_sum_symmetric<T>(lower: T, upper: T) -> Result<SymmetricSumTransformation<T>, Error> where
+    T: 'static + Serialize + CheckedArithmetic + CastFrom<i32> + Sum<T> {
+    let bound = match (lower.checked_abs(), upper.checked_abs()) {
+        (Some(lower_abs), Some(upper_abs)) if lower <= upper => if lower_abs < upper_abs { upper_abs } else { lower_abs },
+        _ => return Err(Error::InvalidArgument("lower must be at most upper, with representable absolute values".to_owned())),
     };
-    Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation)
+    let input_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(lower), Bound::Included(upper)));
+    let output_domain = AllDomain::new();
+    let function = |arg: &Vec<T>| -> T { arg.iter().copied().sum() };
+    let input_metric = SymmetricDistance::new();
+    let output_metric = L1Sensitivity::new();
+    let stability_relation = StabilityRelation::new_fallible(move |d_in: &i32, d_out: &T| {
+        let d_in = T::cast_from(d_in).and_then(|e| e.checked_mul(bound))
+            .ok_or_else(|| Error::InvalidArgument(format!("the sensitivity overflows for d_in = {}", d_in)))?;
+        Ok(*d_out >= d_in)
+    });
+    let (input_domain, output_domain, function) = (Box::new(input_domain), Box::new(output_domain), Function::new(function));
+    let (input_metric, output_metric) = (Box::new(input_metric), Box::new(output_metric));
+    Ok(Transformation { input_domain, output_domain, function, input_metric, output_metric, stability_relation })
 }
 
 pub fn make_count_l1<T>() -> Transformation<VectorDomain<AllDomain<T>>, AllDomain<u32>, HammingDistance, L1Sensitivity<i32>>  {
@@ -273,6 +1107,154 @@ pub fn make_count_l2<T>() -> Transformation<VectorDomain<AllDomain<T>>, AllDomai
 }
 
 
+/// A number that aggregates can be scaled and shifted by, as in [`make_lipschitz_mul`] and [`make_affine`],
+/// with arithmetic that reports overflow rather than wrapping.
+pub trait CheckedArithmetic: Copy + PartialOrd {
+    /// Whether `self` is finite. Integers always are.
+    fn is_finite(self) -> bool;
+    /// Returns `|self|`, or `None` if it isn't finite or can't be represented (like `i32::MIN.abs()`).
+    fn checked_abs(self) -> Option<Self>;
+    /// Returns `self * rhs`, or `None` if the product overflows or isn't finite.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Returns `self + rhs`, or `None` if the sum overflows or isn't finite.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Returns `self * rhs`, saturating at the bounds of the type (infinity for floats).
+    fn saturating_mul(self, rhs: Self) -> Self;
+    /// Returns `self + rhs`, saturating at the bounds of the type (infinity for floats).
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_checked_arithmetic_int {
+    ($($type:ty => $abs:expr),*) => {$(
+        impl CheckedArithmetic for $type {
+            fn is_finite(self) -> bool { true }
+            fn checked_abs(self) -> Option<Self> { $abs(self) }
+            fn checked_mul(self, rhs: Self) -> Option<Self> { <$type>::checked_mul(self, rhs) }
+            fn checked_add(self, rhs: Self) -> Option<Self> { <$type>::checked_add(self, rhs) }
+            fn saturating_mul(self, rhs: Self) -> Self { <$type>::saturating_mul(self, rhs) }
+            fn saturating_add(self, rhs: Self) -> Self { <$type>::saturating_add(self, rhs) }
+        }
+    )*}
+}
+impl_checked_arithmetic_int!(u8 => Some, u32 => Some, u64 => Some, i32 => i32::checked_abs, i64 => i64::checked_abs);
+
+macro_rules! impl_checked_arithmetic_float {
+    ($($type:ty),*) => {$(
+        impl CheckedArithmetic for $type {
+            fn is_finite(self) -> bool { <$type>::is_finite(self) }
+            fn checked_abs(self) -> Option<Self> { <$type>::is_finite(self).then(|| self.abs()) }
+            fn checked_mul(self, rhs: Self) -> Option<Self> { Some(self * rhs).filter(|e| e.is_finite()) }
+            fn checked_add(self, rhs: Self) -> Option<Self> { Some(self + rhs).filter(|e| e.is_finite()) }
+            fn saturating_mul(self, rhs: Self) -> Self { self * rhs }
+            fn saturating_add(self, rhs: Self) -> Self { self + rhs }
+        }
+    )*}
+}
+impl_checked_arithmetic_float!(f32, f64);
+
+/// Utility to create a new [`Transformation`] on an aggregate that applies `function`, which is `lipschitz`-Lipschitz.
+///
+/// The stability relation fails for distances whose image under `lipschitz` overflows.
+fn new_lipschitz_transformation<T>(function: impl Fn(T) -> T + 'static, lipschitz: T) -> Transformation<AllDomain<T>, AllDomain<T>, L1Sensitivity<T>, L1Sensitivity<T>> where
+    T: 'static + CheckedArithmetic {
+    let input_domain = AllDomain::new();
+    let output_domain = AllDomain::new();
+    let function = move |arg: &T| -> T { function(*arg) };
+    let input_metric = L1Sensitivity::new();
+    let output_metric = L1Sensitivity::new();
+    let stability_relation = move |d_in: &T, d_out: &T| d_in.checked_mul(lipschitz).is_some_and(|e| *d_out >= e);
+    Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation)
+}
+
+/// Utility to create a new [`Transformation`] that applies the monotonic `function` to each element of data in `[lower, upper]`.
+/// The output bounds are the image of the input bounds under `checked_function`, which must not overflow.
+/// `function` is applied to the data, and saturates so that elements outside the bounds can't overflow either.
+fn new_monotonic_vec_transformation<T>(lower: T, upper: T, checked_function: impl Fn(T) -> Option<T>, function: impl Fn(T) -> T + 'static) -> Result<HammingVectorTransformation<IntervalDomain<T>, IntervalDomain<T>>, Error> where
+    T: 'static + Serialize + CheckedArithmetic {
+    let (lower_out, upper_out) = match (checked_function(lower), checked_function(upper)) {
+        (Some(lower_out), Some(upper_out)) => (lower_out, upper_out),
+        _ => return Err(Error::InvalidArgument("the image of the bounds must not overflow".to_owned())),
+    };
+    let (lower_out, upper_out) = if lower_out <= upper_out { (lower_out, upper_out) } else { (upper_out, lower_out) };
+    let input_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(lower), Bound::Included(upper)));
+    let output_domain = VectorDomain::new(IntervalDomain::new(Bound::Included(lower_out), Bound::Included(upper_out)));
+    let function = move |arg: &Vec<T>| -> Vec<T> {
+        arg.iter().map(|e| function(*e)).collect()
+    };
+    Ok(new_1_stable_transformation(input_domain, output_domain, function))
+}
+
+/// Constructs a [`Transformation`] that multiplies an aggregate by `c`. This is `|c|`-stable.
+///
+/// Fails unless `c` is finite, with a representable absolute value.
+pub fn make_lipschitz_mul<T>(c: T) -> Result<L1AggregateTransformation<T>, Error> where
+    T: 'static + CheckedArithmetic {
+    let lipschitz = c.checked_abs().ok_or_else(|| Error::InvalidArgument("c must be finite, with a representable absolute value".to_owned()))?;
+    Ok(new_lipschitz_transformation(move |x| c.saturating_mul(x), lipschitz))
+}
+
+/// Constructs a [`Transformation`] that adds `shift` to an aggregate. This is 1-stable.
+///
+/// Fails unless `shift` is finite.
+pub fn make_shift<T>(shift: T) -> Result<L1AggregateTransformation<T>, Error> where
+    T: 'static + CheckedArithmetic {
+    if !shift.is_finite() {
+        return Err(Error::InvalidArgument("shift must be finite".to_owned()))
+    }
+    let input_domain = AllDomain::new();
+    let output_domain = AllDomain::new();
+    let function = move |arg: &T| -> T { arg.saturating_add(shift) };
+    let input_metric = L1Sensitivity::new();
+    let output_metric = L1Sensitivity::new();
+    let stability_relation = |d_in: &T, d_out: &T| *d_out >= *d_in;
+    Ok(Transformation::new(input_domain, output_domain, function, input_metric, output_metric, stability_relation))
+}
+
+/// Constructs a [`Transformation`] that maps an aggregate `x` to `a * x + b`. This is `|a|`-stable.
+///
+/// Fails unless `a` is finite, with a representable absolute value, and `b` is finite.
+pub fn make_affine<T>(a: T, b: T) -> Result<L1AggregateTransformation<T>, Error> where
+    T: 'static + CheckedArithmetic {
+    let lipschitz = a.checked_abs().ok_or_else(|| Error::InvalidArgument("a must be finite, with a representable absolute value".to_owned()))?;
+    if !b.is_finite() {
+        return Err(Error::InvalidArgument("b must be finite".to_owned()))
+    }
+    Ok(new_lipschitz_transformation(move |x| a.saturating_mul(x).saturating_add(b), lipschitz))
+}
+
+/// Constructs a [`Transformation`] that multiplies each element of data in `[lower, upper]` by `c`.
+///
+/// Fails unless `c` is finite, and the products of the bounds with `c` don't overflow.
+pub fn make_lipschitz_mul_vec<T>(lower: T, upper: T, c: T) -> Result<HammingVectorTransformation<IntervalDomain<T>, IntervalDomain<T>>, Error> where
+    T: 'static + Serialize + CheckedArithmetic {
+    if !c.is_finite() {
+        return Err(Error::InvalidArgument("c must be finite".to_owned()))
+    }
+    new_monotonic_vec_transformation(lower, upper, move |x| c.checked_mul(x), move |x| c.saturating_mul(x))
+}
+
+/// Constructs a [`Transformation`] that adds `shift` to each element of data in `[lower, upper]`.
+///
+/// Fails unless `shift` is finite, and the sums of the bounds with `shift` don't overflow.
+pub fn make_shift_vec<T>(lower: T, upper: T, shift: T) -> Result<HammingVectorTransformation<IntervalDomain<T>, IntervalDomain<T>>, Error> where
+    T: 'static + Serialize + CheckedArithmetic {
+    if !shift.is_finite() {
+        return Err(Error::InvalidArgument("shift must be finite".to_owned()))
+    }
+    new_monotonic_vec_transformation(lower, upper, move |x| x.checked_add(shift), move |x| x.saturating_add(shift))
+}
+
+/// Constructs a [`Transformation`] that maps each element `x` of data in `[lower, upper]` to `a * x + b`.
+///
+/// Fails unless `a` and `b` are finite, and the images of the bounds don't overflow.
+pub fn make_affine_vec<T>(lower: T, upper: T, a: T, b: T) -> Result<HammingVectorTransformation<IntervalDomain<T>, IntervalDomain<T>>, Error> where
+    T: 'static + Serialize + CheckedArithmetic {
+    if !(a.is_finite() && b.is_finite()) {
+        return Err(Error::InvalidArgument("a and b must be finite".to_owned()))
+    }
+    new_monotonic_vec_transformation(lower, upper, move |x| a.checked_mul(x).and_then(|e| e.checked_add(b)), move |x| a.saturating_mul(x).saturating_add(b))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::make_chain_tt;
@@ -304,6 +1286,137 @@ mod tests {
         assert_eq!(ret, expected);
     }
 
+    #[test]
+    fn test_make_parse_series_option() {
+        let transformation = make_parse_series_option::<i32>();
+        let arg = vec!["1".to_owned(), "foo".to_owned(), "3".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![Some(1), None, Some(3)]);
+    }
+
+    #[test]
+    fn test_make_parse_series_inherent() {
+        let transformation = make_parse_series_inherent::<f64>();
+        let arg = vec!["1.5".to_owned(), "foo".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret[0], 1.5);
+        assert!(ret[1].is_nan());
+    }
+
+    #[test]
+    fn test_make_impute_constant() {
+        let transformation = make_impute_constant::<OptionNullDomain<AllDomain<i32>>>(0);
+        let arg = vec![Some(1), None, Some(3)];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![1, 0, 3]);
+
+        let transformation = make_impute_constant::<InherentNullDomain<AllDomain<f64>>>(0.5);
+        let arg = vec![1.0, f64::NAN];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_make_impute_uniform_float() {
+        let transformation = make_impute_uniform_float::<InherentNullDomain<AllDomain<f64>>>(2.0, 3.0).unwrap();
+        let arg = vec![1.0, f64::NAN];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret[0], 1.0);
+        assert!(ret[1] >= 2.0 && ret[1] < 3.0);
+        assert!(make_impute_uniform_float::<InherentNullDomain<AllDomain<f64>>>(3.0, 2.0).is_err());
+        assert!(make_impute_uniform_float::<InherentNullDomain<AllDomain<f64>>>(f64::NAN, 2.0).is_err());
+        assert!(make_impute_uniform_float::<OptionNullDomain<AllDomain<f32>>>(0.0, f32::INFINITY).is_err());
+        assert!(make_impute_uniform_float::<OptionNullDomain<AllDomain<f64>>>(-f64::MAX, f64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_make_drop_null() {
+        let transformation = make_drop_null::<OptionNullDomain<AllDomain<i32>>>();
+        let arg = vec![Some(1), None, Some(3)];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![1, 3]);
+        assert!(transformation.stability_relation.eval(&1, &1).unwrap());
+        assert!(!transformation.stability_relation.eval(&2, &1).unwrap());
+    }
+
+    #[test]
+    fn test_make_drop_null_pipeline() {
+        use crate::core::make_chain_mt;
+        use crate::meas::make_base_laplace;
+        let read = make_chain_tt(&make_parse_series_option::<f64>(), &make_split_lines());
+        let to_symmetric = make_hamming_to_symmetric((*read.output_domain).clone());
+        let drop_null = make_drop_null::<OptionNullDomain<AllDomain<f64>>>();
+        let clamp = make_clamp_symmetric(0.0, 100.0);
+        let sum = make_bounded_sum_symmetric(0.0, 100.0).unwrap();
+        let laplace = make_base_laplace::<f64>(1e-9);
+        let aggregate = make_chain_tt(&sum, &make_chain_tt(&clamp, &drop_null));
+        let pipeline = make_chain_mt(&laplace, &make_chain_tt(&aggregate, &make_chain_tt(&to_symmetric, &read)));
+        let ret = pipeline.function.eval(&"70\n\n30\nx".to_owned());
+        assert!((ret - 100.0).abs() < 1e-6);
+
+        // Changing one record is two symmetric changes, each moving the sum by at most 100.
+        assert!(to_symmetric.stability_relation.eval(&1, &2).unwrap());
+        assert!(drop_null.stability_relation.eval(&2, &2).unwrap());
+        assert!(sum.stability_relation.eval(&2, &200.0).unwrap());
+        assert!(laplace.privacy_relation.eval(&200.0, &2e11).unwrap());
+    }
+
+    #[test]
+    fn test_make_cast() {
+        let transformation = make_cast::<f64, i32>();
+        let arg = vec![1.9, -1.9, f64::NAN, 1e10];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![Some(1), Some(-1), None, None]);
+
+        let transformation = make_cast::<String, u8>();
+        let arg = vec!["1".to_owned(), "256".to_owned(), "a".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![Some(1), None, None]);
+    }
+
+    #[test]
+    fn test_make_cast_default() {
+        let transformation = make_cast_default::<i64, u32>(7);
+        let arg = vec![1, -1, i64::MAX];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![1, 7, 7]);
+
+        let transformation = make_cast_default::<String, f64>(-1.0);
+        let arg = vec!["1.5".to_owned(), "a".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![1.5, -1.0]);
+
+        let transformation = make_cast_default::<bool, String>("".to_owned());
+        let arg = vec![true, false];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec!["true".to_owned(), "false".to_owned()]);
+    }
+
+    #[test]
+    fn test_make_cast_inherent() {
+        let transformation = make_cast_inherent::<String, f64>();
+        let arg = vec!["1.5".to_owned(), "a".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret[0], 1.5);
+        assert!(ret[1].is_nan());
+    }
+
+    #[test]
+    fn test_make_is_equal() {
+        let transformation = make_is_equal("a".to_owned());
+        let arg = vec!["a".to_owned(), "b".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![true, false]);
+    }
+
+    #[test]
+    fn test_make_is_null() {
+        let transformation = make_is_null::<InherentNullDomain<AllDomain<f64>>>();
+        let arg = vec![1.0, f64::NAN];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![false, true]);
+    }
+
     #[test]
     fn test_make_split_records() {
         let transformation = make_split_records(None);
@@ -344,9 +1457,135 @@ mod tests {
         assert_eq!(ret, expected);
     }
 
+    #[test]
+    fn test_make_split_csv() {
+        let keys = vec!["name".to_owned(), "note".to_owned()];
+        let transformation = make_split_csv(keys, CsvOptions::default());
+        let arg = "note,name\r\n\"a, \"\"quoted\"\" note\",ant\n\"two\nlines\",bat\n".to_owned();
+        let ret = transformation.function.eval(&arg);
+        let expected: DataFrame = vec![
+            ("name".to_owned(), Data::new(vec!["ant".to_owned(), "bat".to_owned()])),
+            ("note".to_owned(), Data::new(vec!["a, \"quoted\" note".to_owned(), "two\nlines".to_owned()])),
+        ].into_iter().collect();
+        assert_eq!(ret, expected);
+        assert!(transformation.output_domain.member(&ret));
+    }
+
+    #[test]
+    fn test_make_split_csv_missing_key() {
+        let keys = vec!["name".to_owned(), "missing".to_owned()];
+        assert!(csv_dataframe(&keys, &CsvOptions::default(), "name\nant\n").is_err());
+        let transformation = make_split_csv(keys, CsvOptions::default());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| transformation.function.eval(&"name\nant\n".to_owned())));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_make_split_csv_options() {
+        let keys = vec!["0".to_owned(), "1".to_owned()];
+        let options = CsvOptions { separator: ';', quote: '\'', escape: Some('\\'), has_header: false };
+        let transformation = make_split_csv(keys, options);
+        let arg = "'it\\'s;here';1\nfoo;2".to_owned();
+        let ret = transformation.function.eval(&arg);
+        let expected: DataFrame = vec![
+            ("0".to_owned(), Data::new(vec!["it's;here".to_owned(), "foo".to_owned()])),
+            ("1".to_owned(), Data::new(vec!["1".to_owned(), "2".to_owned()])),
+        ].into_iter().collect();
+        assert_eq!(ret, expected);
+    }
+
+    #[test]
+    fn test_make_parse_jsonl() {
+        let columns = vec![InputColumn::new::<String>("name"), InputColumn::new::<i32>("age"), InputColumn::new::<f64>("score")];
+        let transformation = make_parse_jsonl(columns);
+        let arg = "{\"name\": \"ant\", \"age\": 1, \"score\": 1.5}\n{\"name\": \"bat\", \"age\": \"two\"}\nnot json".to_owned();
+        let ret = transformation.function.eval(&arg);
+        let expected: DataFrame = vec![
+            ("name".to_owned(), Data::new(vec![Some("ant".to_owned()), Some("bat".to_owned()), None])),
+            ("age".to_owned(), Data::new(vec![Some(1), None, None])),
+            ("score".to_owned(), Data::new(vec![Some(1.5), None, None])),
+        ].into_iter().collect();
+        assert_eq!(ret, expected);
+        assert!(transformation.output_domain.member(&ret));
+
+        let select = make_select_column_option::<i32>(&transformation.output_domain, "age").unwrap();
+        assert_eq!(select.function.eval(&ret), vec![Some(1), None, None]);
+        assert!(make_select_column::<i32>(&transformation.output_domain, "age").is_err());
+    }
+
+    #[cfg(feature = "arrow")]
+    fn test_record_batch() -> arrow_array::RecordBatch {
+        use std::sync::Arc;
+        use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+        let name: ArrayRef = Arc::new(StringArray::from(vec![Some("ant"), None]));
+        let age: ArrayRef = Arc::new(Int64Array::from(vec![1, 2]));
+        let code: ArrayRef = Arc::new(StringArray::from(vec!["3", "three"]));
+        RecordBatch::try_from_iter(vec![("name", name), ("age", age), ("code", code)]).unwrap()
+    }
+
+    #[cfg(feature = "arrow")]
+    fn test_expected() -> DataFrame {
+        vec![
+            ("name".to_owned(), Data::new(vec![Some("ant".to_owned()), None])),
+            ("age".to_owned(), Data::new(vec![Some(1_i32), Some(2)])),
+            ("code".to_owned(), Data::new(vec![Some(3_i32), None])),
+            ("missing".to_owned(), Data::new(vec![None::<bool>, None])),
+        ].into_iter().collect()
+    }
+
+    #[cfg(feature = "arrow")]
+    fn test_columns() -> Vec<InputColumn> {
+        vec![InputColumn::new::<String>("name"), InputColumn::new::<i32>("age"), InputColumn::new::<i32>("code"), InputColumn::new::<bool>("missing")]
+    }
+
+    /// Checks that `transformation` fails on `arg`, rather than releasing a dataframe.
+    #[cfg(feature = "arrow")]
+    fn test_fails(transformation: &Transformation<AllDomain<Vec<u8>>, DataFrameDomain, HammingDistance, HammingDistance>, arg: &[u8]) {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| transformation.function.eval(&arg.to_vec())));
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_make_read_arrow_ipc() {
+        use arrow_ipc::writer::{FileWriter, StreamWriter};
+        let batch = test_record_batch();
+        let transformation = make_read_arrow_ipc(test_columns());
+
+        let mut writer = FileWriter::try_new(Vec::new(), &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        let arg = writer.into_inner().unwrap();
+        assert_eq!(transformation.function.eval(&arg), test_expected());
+
+        test_fails(&transformation, &arg[..arg.len() / 2]);
+
+        let mut writer = StreamWriter::try_new(Vec::new(), &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        let arg = writer.into_inner().unwrap();
+        assert_eq!(transformation.function.eval(&arg), test_expected());
+        test_fails(&transformation, &arg[..arg.len() / 2]);
+
+        test_fails(&transformation, b"not arrow");
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_make_read_parquet() {
+        use parquet::arrow::ArrowWriter;
+        let batch = test_record_batch();
+        let transformation = make_read_parquet(test_columns());
+        let mut writer = ArrowWriter::try_new(Vec::new(), batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        let arg = writer.into_inner().unwrap();
+        assert_eq!(transformation.function.eval(&arg), test_expected());
+
+        test_fails(&transformation, &arg[..arg.len() / 2]);
+        test_fails(&transformation, b"not parquet");
+    }
+
     #[test]
     fn test_make_parse_column() {
-        let transformation = make_parse_column::<i32>("1", true);
+        let transformation = make_parse_column::<i32>(&create_dataframe_domain(2), "1", true).unwrap();
         let arg: DataFrame = vec![
             ("0".to_owned(), Data::new(vec!["ant".to_owned(), "bat".to_owned(), "cat".to_owned()])),
             ("1".to_owned(), Data::new(vec!["1".to_owned(), "2".to_owned(), "".to_owned()])),
@@ -357,12 +1596,14 @@ mod tests {
             ("1".to_owned(), Data::new(vec![1, 2, 0])),
         ].into_iter().collect();
         assert_eq!(ret, expected);
+        assert!(transformation.output_domain.member(&ret));
+        assert!(!transformation.output_domain.member(&arg));
     }
 
     #[test]
     fn test_make_parse_columns() {
-        let transformation0 = make_parse_column::<i32>("1", true);
-        let transformation1 = make_parse_column::<f64>("2", true);
+        let transformation0 = make_parse_column::<i32>(&create_dataframe_domain(3), "1", true).unwrap();
+        let transformation1 = make_parse_column::<f64>(&transformation0.output_domain, "2", true).unwrap();
         let transformation = make_chain_tt(&transformation1, &transformation0);
         let arg: DataFrame = vec![
             ("0".to_owned(), Data::new(vec!["ant".to_owned(), "bat".to_owned(), "cat".to_owned()])),
@@ -380,7 +1621,7 @@ mod tests {
 
     #[test]
     fn test_make_select_column() {
-        let transformation = make_select_column::<String>("1");
+        let transformation = make_select_column::<String>(&create_dataframe_domain(2), "1").unwrap();
         let arg: DataFrame = vec![
             ("0".to_owned(), Data::new(vec!["ant".to_owned(), "bat".to_owned(), "cat".to_owned()])),
             ("1".to_owned(), Data::new(vec!["foo".to_owned(), "bar".to_owned(), "baz".to_owned()])),
@@ -390,6 +1631,199 @@ mod tests {
         assert_eq!(ret, expected);
     }
 
+    #[test]
+    fn test_make_select_column_bad_schema() {
+        let input_domain = create_dataframe_domain(2);
+        assert!(make_select_column::<String>(&input_domain, "2").is_err());
+        assert!(make_select_column::<i32>(&input_domain, "1").is_err());
+        let parse = make_parse_column::<i32>(&input_domain, "1", true).unwrap();
+        assert!(make_select_column::<i32>(&parse.output_domain, "1").is_ok());
+        assert!(make_parse_column::<i32>(&parse.output_domain, "1", true).is_err());
+    }
+
+    #[test]
+    fn test_predicate_json() {
+        let predicate: Predicate<i32> = serde_json::from_str(r#"{"and": [{"ge": 65}, {"not": {"in": [70, 80]}}]}"#).unwrap();
+        let expected = Predicate::And(vec![Predicate::Ge(65), Predicate::Not(Box::new(Predicate::In(vec![70, 80])))]);
+        assert_eq!(predicate, expected);
+        assert!(predicate.eval(&65));
+        assert!(!predicate.eval(&64));
+        assert!(!predicate.eval(&70));
+    }
+
+    #[test]
+    fn test_make_filter() {
+        let transformation = make_filter(Predicate::Or(vec![Predicate::Between(2, 3), Predicate::Eq(5)]));
+        let arg = vec![1, 2, 3, 4, 5];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn test_make_filter_dataframe() {
+        let parse = make_parse_column::<i32>(&create_dataframe_domain(2), "1", true).unwrap();
+        let transformation = make_filter_dataframe(&parse.output_domain, "1", Predicate::Ge(65)).unwrap();
+        let arg: DataFrame = vec![
+            ("0".to_owned(), Data::new(vec!["ant".to_owned(), "bat".to_owned(), "cat".to_owned()])),
+            ("1".to_owned(), Data::new(vec![70, 20, 65])),
+        ].into_iter().collect();
+        let ret = transformation.function.eval(&arg);
+        let expected: DataFrame = vec![
+            ("0".to_owned(), Data::new(vec!["ant".to_owned(), "cat".to_owned()])),
+            ("1".to_owned(), Data::new(vec![70, 65])),
+        ].into_iter().collect();
+        assert_eq!(ret, expected);
+        assert!(make_filter_dataframe(&parse.output_domain, "0", Predicate::Ge(65)).is_err());
+    }
+
+    #[test]
+    fn test_make_hamming_to_symmetric() {
+        let transformation = make_hamming_to_symmetric(VectorDomain::<AllDomain<i32>>::new_all());
+        assert_eq!(transformation.function.eval(&vec![1, 2]), vec![1, 2]);
+        assert!(transformation.stability_relation.eval(&1, &2).unwrap());
+        assert!(!transformation.stability_relation.eval(&1, &1).unwrap());
+        assert!(transformation.stability_relation.eval(&i32::MAX, &i32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_make_bounded_sum_symmetric() {
+        let transformation = make_bounded_sum_symmetric(-10, 5).unwrap();
+        assert_eq!(transformation.function.eval(&vec![-10, 5, 1]), -4);
+        assert!(transformation.stability_relation.eval(&2, &20).unwrap());
+        assert!(!transformation.stability_relation.eval(&2, &19).unwrap());
+        assert!(transformation.stability_relation.eval(&i32::MAX, &i32::MAX).is_err());
+        assert!(make_bounded_sum_symmetric(5, -10).is_err());
+        assert!(make_bounded_sum_symmetric(i32::MIN, 0).is_err());
+        assert!(make_bounded_sum_symmetric(0.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_make_filter_pipeline() {
+        use crate::core::make_chain_mt;
+        use crate::meas::make_base_laplace;
+        let laplace = make_base_laplace::<f64>(1e-9);
+        let clamp = make_clamp_symmetric(0.0, 100.0);
+        let sum = make_bounded_sum_symmetric(0.0, 100.0).unwrap();
+        let aggregate = make_chain_tt(&sum, &clamp);
+
+        // Vectors read under the Hamming distance are filtered under the symmetric distance.
+        let read = make_chain_tt(&make_parse_series::<f64>(true), &make_split_lines());
+        let to_symmetric = make_hamming_to_symmetric((*read.output_domain).clone());
+        let filter = make_filter(Predicate::Ge(65.0));
+        let pipeline = make_chain_mt(&laplace, &make_chain_tt(&aggregate, &make_chain_tt(&filter, &make_chain_tt(&to_symmetric, &read))));
+        let ret = pipeline.function.eval(&"70\n20\n130".to_owned());
+        assert!((ret - 170.0).abs() < 1e-6);
+
+        // As are dataframes, whose filtered columns are selected under the symmetric distance.
+        let read = make_split_csv(vec!["name".to_owned(), "age".to_owned()], CsvOptions::default());
+        let parse = make_parse_column::<f64>(&read.output_domain, "age", true).unwrap();
+        let read = make_chain_tt(&parse, &read);
+        let to_symmetric = make_hamming_to_symmetric((*read.output_domain).clone());
+        let filter = make_filter_dataframe(&read.output_domain, "age", Predicate::Ge(65.0)).unwrap();
+        let select = make_select_column_symmetric::<f64>(&filter.output_domain, "age").unwrap();
+        let pipeline = make_chain_mt(&laplace, &make_chain_tt(&aggregate, &make_chain_tt(&select, &make_chain_tt(&filter, &make_chain_tt(&to_symmetric, &read)))));
+        let ret = pipeline.function.eval(&"name,age\nant,70\nbat,20\ncat,130\n".to_owned());
+        assert!((ret - 170.0).abs() < 1e-6);
+
+        // Changing one record is two symmetric changes, each moving the sum by at most 100.
+        assert!(to_symmetric.stability_relation.eval(&1, &2).unwrap());
+        assert!(filter.stability_relation.eval(&2, &2).unwrap());
+        assert!(select.stability_relation.eval(&2, &2).unwrap());
+        assert!(clamp.stability_relation.eval(&2, &2).unwrap());
+        assert!(sum.stability_relation.eval(&2, &200.0).unwrap());
+        assert!(laplace.privacy_relation.eval(&200.0, &2e11).unwrap());
+    }
+
+    #[test]
+    fn test_make_bound_user_contributions() {
+        let parse = make_parse_column::<i32>(&create_dataframe_domain(2), "0", true).unwrap();
+        let transformation = make_bound_user_contributions::<i32>(&parse.output_domain, "0", 2).unwrap();
+        let arg: DataFrame = vec![
+            ("0".to_owned(), Data::new(vec![1, 1, 1, 2, 3, 3])),
+            ("1".to_owned(), Data::new(vec!["a".to_owned(), "b".to_owned(), "c".to_owned(), "d".to_owned(), "e".to_owned(), "f".to_owned()])),
+        ].into_iter().collect();
+        let ret = transformation.function.eval(&arg);
+        let ids: &Vec<i32> = ret["0"].as_form();
+        let values: &Vec<String> = ret["1"].as_form();
+        assert_eq!(ids, &vec![1, 1, 2, 3, 3]);
+        assert_eq!(&values[2..], &["d".to_owned(), "e".to_owned(), "f".to_owned()]);
+        assert!(transformation.stability_relation.eval(&1, &2).unwrap());
+        assert!(!transformation.stability_relation.eval(&2, &3).unwrap());
+        assert!(transformation.stability_relation.eval(&i32::MAX, &i32::MAX).is_err());
+        assert!(make_bound_user_contributions::<String>(&parse.output_domain, "0", 2).is_err());
+        assert!(make_bound_user_contributions::<i32>(&parse.output_domain, "0", u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_make_bound_user_contributions_pipeline() {
+        use crate::core::make_chain_mt;
+        use crate::meas::make_base_laplace;
+        let read = make_split_csv(vec!["id".to_owned(), "amount".to_owned()], CsvOptions::default());
+        let read = make_chain_tt(&make_parse_column::<f64>(&read.output_domain, "amount", true).unwrap(), &read);
+        let to_user = make_hamming_to_user(&read.output_domain);
+        let bound = make_bound_user_contributions::<String>(&to_user.output_domain, "id", 2).unwrap();
+        let select = make_select_column_symmetric::<f64>(&bound.output_domain, "amount").unwrap();
+        let clamp = make_clamp_symmetric(0.0, 100.0);
+        let sum = make_bounded_sum_symmetric(0.0, 100.0).unwrap();
+        let laplace = make_base_laplace::<f64>(1e-9);
+        let aggregate = make_chain_tt(&sum, &make_chain_tt(&clamp, &select));
+        let pipeline = make_chain_mt(&laplace, &make_chain_tt(&aggregate, &make_chain_tt(&bound, &make_chain_tt(&to_user, &read))));
+        let ret = pipeline.function.eval(&"id,amount\na,10\na,10\na,10\nb,30\n".to_owned());
+        assert!((ret - 50.0).abs() < 1e-6);
+
+        // Changing one row changes two individuals, who contribute at most two rows each, each moving the sum by at most 100.
+        assert!(to_user.stability_relation.eval(&1, &2).unwrap());
+        assert!(!to_user.stability_relation.eval(&1, &1).unwrap());
+        assert!(to_user.stability_relation.eval(&i32::MAX, &i32::MAX).is_err());
+        assert!(bound.stability_relation.eval(&2, &4).unwrap());
+        assert!(select.stability_relation.eval(&4, &4).unwrap());
+        assert!(clamp.stability_relation.eval(&4, &4).unwrap());
+        assert!(sum.stability_relation.eval(&4, &400.0).unwrap());
+        assert!(laplace.privacy_relation.eval(&400.0, &4e11).unwrap());
+    }
+
+    #[test]
+    fn test_make_partition_by() {
+        let input_domain = create_dataframe_domain(2);
+        let transformation = make_partition_by(&input_domain, "0", vec!["a".to_owned(), "b".to_owned(), "z".to_owned()]).unwrap();
+        let arg: DataFrame = vec![
+            ("0".to_owned(), Data::new(vec!["a".to_owned(), "b".to_owned(), "a".to_owned(), "c".to_owned()])),
+            ("1".to_owned(), Data::new(vec!["1".to_owned(), "2".to_owned(), "3".to_owned(), "4".to_owned()])),
+        ].into_iter().collect();
+        let ret = transformation.function.eval(&arg);
+        let values: Vec<&Vec<String>> = ret.iter().map(|e| e["1"].as_form()).collect();
+        assert_eq!(values, vec![&vec!["1".to_owned(), "3".to_owned()], &vec!["2".to_owned()], &vec![]]);
+        assert!(transformation.output_domain.member(&ret));
+    }
+
+    #[test]
+    fn test_make_partition_by_pipeline() {
+        use crate::core::{make_chain_mt, make_parallel_composition};
+        use crate::meas::make_base_laplace;
+        let read = make_split_csv(vec!["group".to_owned(), "amount".to_owned()], CsvOptions::default());
+        let read = make_chain_tt(&make_parse_column::<f64>(&read.output_domain, "amount", true).unwrap(), &read);
+        let to_symmetric = make_hamming_to_symmetric((*read.output_domain).clone());
+        let partition = make_partition_by(&to_symmetric.output_domain, "group", vec!["a".to_owned(), "b".to_owned()]).unwrap();
+        let select = make_select_column_symmetric::<f64>(&partition.output_domain.element_domain, "amount").unwrap();
+        let clamp = make_clamp_symmetric(0.0, 100.0);
+        let sum = make_bounded_sum_symmetric(0.0, 100.0).unwrap();
+        let laplace = make_base_laplace::<f64>(1e-9);
+        let noisy_sum = make_chain_mt(&laplace, &make_chain_tt(&sum, &make_chain_tt(&clamp, &select)));
+        let parallel = make_parallel_composition(&noisy_sum);
+        let pipeline = make_chain_mt(&parallel, &make_chain_tt(&partition, &make_chain_tt(&to_symmetric, &read)));
+        let ret = pipeline.function.eval(&"group,amount\na,10\nb,20\na,30\nc,40\n".to_owned());
+        assert_eq!(ret.len(), 2);
+        assert!((ret[0] - 40.0).abs() < 1e-6);
+        assert!((ret[1] - 20.0).abs() < 1e-6);
+
+        // Changing one row is two symmetric changes, summed over the partitions, each moving a sum by at most 100.
+        assert!(to_symmetric.stability_relation.eval(&1, &2).unwrap());
+        assert!(partition.stability_relation.eval(&2, &2).unwrap());
+        assert!(select.stability_relation.eval(&2, &2).unwrap());
+        assert!(sum.stability_relation.eval(&2, &200.0).unwrap());
+        assert!(laplace.privacy_relation.eval(&200.0, &2e11).unwrap());
+    }
+
     #[test]
     fn test_make_clamp() {
         let transformation = make_clamp(0, 10);
@@ -399,6 +1833,113 @@ mod tests {
         assert_eq!(ret, expected);
     }
 
+    #[test]
+    fn test_make_sample_and_aggregate() {
+        let function = Function::new(|arg: &Vec<i32>| arg.len() as i32);
+        let transformation = make_sample_and_aggregate(function.clone(), 4, 0, 2).unwrap();
+        let arg: Vec<i32> = (0..10).collect();
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![2, 2, 2, 2]);
+        assert!(transformation.output_domain.member(&ret));
+        assert!(make_sample_and_aggregate(function, 0, 0, 2).is_err());
+    }
+
+    #[test]
+    fn test_make_clamp_categorical() {
+        let categories = vec!["a".to_owned(), "b".to_owned()];
+        let transformation = make_clamp_categorical(categories, "unknown".to_owned());
+        let arg = vec!["a".to_owned(), "c".to_owned(), "b".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        let expected = vec!["a".to_owned(), "unknown".to_owned(), "b".to_owned()];
+        assert_eq!(ret, expected);
+        assert!(transformation.output_domain.member(&ret));
+        assert!(!transformation.output_domain.member(&arg));
+    }
+
+    #[test]
+    fn test_make_find_bin() {
+        let transformation = make_find_bin(vec![0.0, 10.0, 20.0]).unwrap();
+        let arg = vec![-5.0, 0.0, 9.9, 10.0, 25.0, f64::NAN];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![0, 1, 1, 2, 3, 4]);
+        assert!(transformation.output_domain.member(&ret));
+        assert!(make_find_bin(vec![0.0, 20.0, 10.0]).is_err());
+        assert!(make_find_bin(vec![0.0, 0.0]).is_err());
+        assert!(make_find_bin(vec![f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_make_find() {
+        let transformation = make_find(vec!["a".to_owned(), "b".to_owned()]).unwrap();
+        let arg = vec!["b".to_owned(), "c".to_owned(), "a".to_owned()];
+        let ret = transformation.function.eval(&arg);
+        assert_eq!(ret, vec![1, 2, 0]);
+        assert!(transformation.output_domain.member(&ret));
+        assert!(make_find(vec!["a".to_owned(), "a".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn test_make_lipschitz_mul() {
+        let transformation = make_lipschitz_mul(-0.01).unwrap();
+        assert_eq!(transformation.function.eval(&250.0), -2.5);
+        assert!(transformation.stability_relation.eval(&100.0, &1.0).unwrap());
+        assert!(!transformation.stability_relation.eval(&100.0, &0.5).unwrap());
+
+        // Distances whose image overflows can't be bounded.
+        let transformation = make_lipschitz_mul(i32::MAX).unwrap();
+        assert_eq!(transformation.function.eval(&2), i32::MAX);
+        assert!(transformation.stability_relation.eval(&1, &i32::MAX).unwrap());
+        assert!(!transformation.stability_relation.eval(&2, &i32::MAX).unwrap());
+        let transformation = make_lipschitz_mul(f64::MAX).unwrap();
+        assert!(!transformation.stability_relation.eval(&2.0, &f64::INFINITY).unwrap());
+    }
+
+    #[test]
+    fn test_make_lipschitz_mul_overflowing() {
+        assert!(make_lipschitz_mul(i32::MIN).is_err());
+    }
+
+    #[test]
+    fn test_make_lipschitz_mul_nan() {
+        assert!(make_lipschitz_mul(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_make_shift() {
+        let transformation = make_shift(5).unwrap();
+        assert_eq!(transformation.function.eval(&10), 15);
+        assert!(transformation.stability_relation.eval(&2, &2).unwrap());
+        assert!(!transformation.stability_relation.eval(&2, &1).unwrap());
+        assert!(make_shift(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_make_affine() {
+        let transformation = make_affine(-2, 1).unwrap();
+        assert_eq!(transformation.function.eval(&5), -9);
+        assert!(transformation.stability_relation.eval(&1, &2).unwrap());
+        assert!(!transformation.stability_relation.eval(&1, &1).unwrap());
+        assert!(make_affine(i32::MIN, 0).is_err());
+        assert!(make_affine(1.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_make_affine_vec() {
+        let transformation = make_affine_vec(0, 10, -2, 1).unwrap();
+        let ret = transformation.function.eval(&vec![0, 5, 10]);
+        assert_eq!(ret, vec![1, -9, -19]);
+        assert!(*transformation.output_domain == VectorDomain::new(IntervalDomain::new(Bound::Included(-19), Bound::Included(1))));
+        assert!(transformation.output_domain.member(&ret));
+        assert!(make_affine_vec(0.0, 10.0, f64::NAN, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_make_affine_vec_overflowing() {
+        assert!(make_affine_vec(0, i32::MAX, 2, 0).is_err());
+        assert!(make_lipschitz_mul_vec(0, i32::MAX, 2).is_err());
+        assert!(make_shift_vec(0, i32::MAX, 1).is_err());
+    }
+
     #[test]
     fn test_make_bounded_sum_l1() {
         let transformation = make_bounded_sum_l1::<i32>(0, 10);